@@ -0,0 +1,57 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use magnus::{
+    embed,
+    prelude::*,
+    rb_sys::{AsRawId, AsRawValue},
+    value::{Id, LazyId},
+    Value,
+};
+
+static CALL: LazyId = LazyId::new("call");
+
+fn funcall(c: &mut Criterion) {
+    let ruby = unsafe { embed::init() };
+    let callback: Value = ruby.eval("->(a, b, c = nil, d = nil) { a }").unwrap();
+    let call: Id = *CALL;
+
+    let mut group = c.benchmark_group("funcall");
+
+    // `funcall` with up to 3 arguments passes them inline to `rb_funcall`
+    group.bench_function("2 args", |b| {
+        b.iter(|| {
+            let res: i64 = callback
+                .funcall(call, (black_box(1), black_box(2)))
+                .unwrap();
+            res
+        })
+    });
+    // more than 3 arguments go through `rb_funcallv_kw`
+    group.bench_function("4 args", |b| {
+        b.iter(|| {
+            let res: i64 = callback
+                .funcall(call, (black_box(1), black_box(2), 3, 4))
+                .unwrap();
+            res
+        })
+    });
+    // baseline, calling the C API directly with no conversions
+    group.bench_function("2 args, rb_funcallv", |b| {
+        let args = [
+            ruby.integer_from_i64(1).as_raw(),
+            ruby.integer_from_i64(2).as_raw(),
+        ];
+        b.iter(|| unsafe {
+            rb_sys::rb_funcallv(
+                callback.as_raw(),
+                call.as_raw(),
+                args.len() as _,
+                black_box(args.as_ptr()),
+            )
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, funcall);
+criterion_main!(benches);
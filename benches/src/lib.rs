@@ -0,0 +1,5 @@
+//! Benchmarks for Magnus.
+//!
+//! These are kept out of the main workspace so their dependencies don't
+//! affect Magnus' minimum supported Rust version. Run with `cargo bench` from
+//! this directory.
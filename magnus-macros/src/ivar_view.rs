@@ -0,0 +1,105 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::{spanned::Spanned, Data, DataStruct, DeriveInput, Error, Fields, FieldsNamed, LitStr};
+
+use crate::util;
+
+pub fn expand_derive_ivar_view(input: DeriveInput) -> Result<TokenStream, Error> {
+    let mut write = false;
+    if let Some(attrs) = util::get_magnus_attrubute(&input.attrs)? {
+        attrs.parse_nested_meta(|meta| {
+            if meta.path.is_ident("write") {
+                write = true;
+                Ok(())
+            } else {
+                Err(meta.error("unsupported attribute"))
+            }
+        })?;
+    }
+
+    let named = match input.data {
+        Data::Struct(DataStruct {
+            fields: Fields::Named(FieldsNamed { ref named, .. }),
+            ..
+        }) => named,
+        _ => {
+            return Err(Error::new(
+                input.span(),
+                "IvarView can only be derived for structs with named fields",
+            ))
+        }
+    };
+
+    let mut reads = Vec::new();
+    let mut writes = Vec::new();
+    for field in named {
+        let ident = field.ident.as_ref().unwrap();
+        let mut ivar = None;
+        let mut default = false;
+        if let Some(attrs) = util::get_magnus_attrubute(&field.attrs)? {
+            attrs.parse_nested_meta(|meta| {
+                if meta.path.is_ident("ivar") {
+                    ivar = Some(meta.value()?.parse::<LitStr>()?.value());
+                    Ok(())
+                } else if meta.path.is_ident("default") {
+                    default = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported attribute"))
+                }
+            })?;
+        }
+        let ivar = ivar.unwrap_or_else(|| format!("@{}", ident.unraw()));
+        let id = quote! {{
+            static ID: magnus::value::LazyId = magnus::value::LazyId::new(#ivar);
+            *ID
+        }};
+        if default {
+            reads.push(quote! {
+                #ident: match obj.ivar_get::<_, Option<magnus::Value>>(#id)? {
+                    Some(val) => magnus::TryConvert::try_convert(val)?,
+                    None => Default::default(),
+                }
+            });
+        } else {
+            reads.push(quote! { #ident: obj.ivar_get(#id)? });
+        }
+        writes.push(quote! { obj.ivar_set(#id, self.#ident)?; });
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let write_to = if write {
+        quote! {
+            /// Set the instance variables of `obj` from the fields of `self`.
+            pub fn write_to<T>(self, obj: T) -> Result<(), magnus::Error>
+            where
+                T: magnus::Object,
+            {
+                #(#writes)*
+                Ok(())
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    Ok(quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// Read the instance variables of `obj` into a new instance of
+            /// this type.
+            pub fn from_obj<T>(obj: T) -> Result<Self, magnus::Error>
+            where
+                T: magnus::Object,
+            {
+                Ok(Self {
+                    #(#reads,)*
+                })
+            }
+
+            #write_to
+        }
+    })
+}
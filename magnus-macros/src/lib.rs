@@ -20,6 +20,8 @@ use proc_macro::TokenStream;
 use syn::parse_macro_input;
 
 mod init;
+mod ivar_view;
+mod transparent;
 mod typed_data;
 mod util;
 
@@ -126,11 +128,65 @@ pub fn init(attrs: TokenStream, item: TokenStream) -> TokenStream {
 ///   implmentation does not call Ruby.
 /// * `size` - Report the [`std::mem::size_of_val`] of the type to Ruby, used
 ///   to aid in deciding when to run the garbage collector.
+/// * `inspect` - Define an `#inspect` method on the Ruby class, using the
+///   type's [`Debug`](std::fmt::Debug) implementation.
+/// * `to_s` - Define a `#to_s` method on the Ruby class, using the type's
+///   [`Display`](std::fmt::Display) implementation. For both `inspect` and
+///   `to_s` long output is truncated to
+///   `magnus::typed_data::DERIVED_FMT_MAX_LEN` bytes, and a panic while
+///   formatting falls back to Ruby's default `#<Foo:0x...>` format.
+/// * `cmp` - Define `#<=>` and `#==` methods on the Ruby class, using the
+///   type's [`PartialOrd`] and [`PartialEq`] implementations, and include
+///   `Comparable`. `#<=>` returns `nil` when the other object is not the same
+///   type.
+/// * `hash` - Define `#hash` and `#eql?` methods on the Ruby class, using the
+///   type's [`Hash`](std::hash::Hash) and [`Eq`] implementations, allowing
+///   the wrapped objects to be used as keys in a Ruby `Hash`.
+/// * `add`, `sub`, `mul`, `div`, `rem` - Define the `#+`, `#-`, `#*`, `#/`,
+///   and `#%` operator methods on the Ruby class, using the type's
+///   [`Clone`] and [`Add`](std::ops::Add), [`Sub`](std::ops::Sub), etc.
+///   implementations. When the other operand is not the same type Ruby's
+///   coercion protocol is followed, calling `#coerce` on the other operand.
+/// * `neg` - Define the `#-@` (unary minus) method on the Ruby class, using
+///   the type's [`Clone`] and [`Neg`](std::ops::Neg) implementations.
+/// * `index = "..."` - Define the `#[]` method on the Ruby class, using the
+///   type's `magnus::typed_data::Index` implementation for the given index
+///   type. Returns `nil` when there is no element at the index.
+/// * `coerce = "..."` - Define the `#coerce` method on the Ruby class,
+///   converting the other operand to the given type and then to the wrapped
+///   type with [`From`]. This allows built in types to be the left operand of
+///   the operators defined with `add`, `mul`, etc, e.g. `2 * vector`.
+/// * `deconstruct` - Define `#deconstruct` and `#deconstruct_keys` methods on
+///   the Ruby class, allowing the wrapped objects to be used with Ruby's
+///   pattern matching (`case`/`in`). Fields are cloned and converted to Ruby,
+///   and `#deconstruct_keys` only converts the requested fields. For tuple
+///   structs only `#deconstruct` is defined.
+/// * `io` - Define `#read`, `#write`, `#each_line`, and `#eof?` methods on
+///   the Ruby class, using the type's `magnus::typed_data::Io`
+///   implementation, so the wrapped objects can be used as IO-like objects.
 /// * `unsafe_generics` - The derived implementation of [`TypedData`] is not
 ///   guaranteed to be correct for types with generics. If you are sure it is
 ///   for your type this attribute can be used to override the compile time
 ///   error usually generated for types with generics.
 ///
+/// Generated methods are defined when the class is first fetched with
+/// `TypedData::class`. A method is skipped if the class already defines a
+/// method of the same name, so user defined methods are never replaced.
+///
+/// # Field Attributes
+///
+/// The `#[magnus(...)]` attribute can be set on struct fields with the
+/// following values:
+///
+/// * `attr_reader` - Define a reader method on the Ruby class with the same
+///   name as the field, returning a clone of the field's value converted to
+///   Ruby. For `Cell<T>` and `RefCell<T>` fields the inner value is returned.
+/// * `attr_writer` - Define a writer method (`name=`) on the Ruby class. The
+///   field must be a `Cell<T>` or `RefCell<T>`.
+/// * `attr_accessor` - Both `attr_reader` and `attr_writer`.
+///
+/// See [`TypedData`](macro@TypedData) for more details.
+///
 /// # Variant Attributes
 ///
 /// The `#[magnus(...)]` attribute can be set on enum variants with the
@@ -167,6 +223,45 @@ pub fn init(attrs: TokenStream, item: TokenStream) -> TokenStream {
 /// }
 /// ```
 ///
+/// With generated attribute methods:
+///
+/// ```
+/// use std::cell::{Cell, RefCell};
+///
+/// use magnus::{class, define_class, function, prelude::*};
+///
+/// #[magnus::wrap(class = "Person")]
+/// struct Person {
+///     #[magnus(attr_reader)]
+///     id: u64,
+///     #[magnus(attr_accessor)]
+///     name: RefCell<String>,
+///     #[magnus(attr_accessor)]
+///     age: Cell<Option<u8>>,
+///     // not exposed to Ruby
+///     cache: Vec<u8>,
+/// }
+///
+/// impl Person {
+///     fn new(id: u64, name: String) -> Self {
+///         Self {
+///             id,
+///             name: RefCell::new(name),
+///             age: Cell::new(None),
+///             cache: Vec::new(),
+///         }
+///     }
+/// }
+///
+/// #[magnus::init]
+/// fn init() -> Result<(), magnus::Error> {
+///     let class = define_class("Person", class::object())?;
+///     class.define_singleton_method("new", function!(Person::new, 2))?;
+///     Ok(())
+/// }
+/// # let _ = Person::new(1, String::new()).cache;
+/// ```
+///
 /// With subclasses for enum variants:
 ///
 /// ```
@@ -242,11 +337,51 @@ pub fn derive_data_type_functions(input: TokenStream) -> TokenStream {
 /// * `compact` - Enable Ruby calling the `DataTypeFunctions::compact` function.
 /// * `wb_protected` - Enable the `wb_protected` flag.
 /// * `frozen_shareable` - Enable the `frozen_shareable` flag.
+/// * `inspect` - Define an `#inspect` method on the Ruby class, using the
+///   type's [`Debug`](std::fmt::Debug) implementation.
+/// * `to_s` - Define a `#to_s` method on the Ruby class, using the type's
+///   [`Display`](std::fmt::Display) implementation. For both `inspect` and
+///   `to_s` long output is truncated to
+///   `magnus::typed_data::DERIVED_FMT_MAX_LEN` bytes, and a panic while
+///   formatting falls back to Ruby's default `#<Foo:0x...>` format.
+/// * `cmp` - Define `#<=>` and `#==` methods on the Ruby class, using the
+///   type's [`PartialOrd`] and [`PartialEq`] implementations, and include
+///   `Comparable`. `#<=>` returns `nil` when the other object is not the same
+///   type.
+/// * `hash` - Define `#hash` and `#eql?` methods on the Ruby class, using the
+///   type's [`Hash`](std::hash::Hash) and [`Eq`] implementations, allowing
+///   the wrapped objects to be used as keys in a Ruby `Hash`.
+/// * `add`, `sub`, `mul`, `div`, `rem` - Define the `#+`, `#-`, `#*`, `#/`,
+///   and `#%` operator methods on the Ruby class, using the type's
+///   [`Clone`] and [`Add`](std::ops::Add), [`Sub`](std::ops::Sub), etc.
+///   implementations. When the other operand is not the same type Ruby's
+///   coercion protocol is followed, calling `#coerce` on the other operand.
+/// * `neg` - Define the `#-@` (unary minus) method on the Ruby class, using
+///   the type's [`Clone`] and [`Neg`](std::ops::Neg) implementations.
+/// * `index = "..."` - Define the `#[]` method on the Ruby class, using the
+///   type's `magnus::typed_data::Index` implementation for the given index
+///   type. Returns `nil` when there is no element at the index.
+/// * `coerce = "..."` - Define the `#coerce` method on the Ruby class,
+///   converting the other operand to the given type and then to the wrapped
+///   type with [`From`]. This allows built in types to be the left operand of
+///   the operators defined with `add`, `mul`, etc, e.g. `2 * vector`.
+/// * `deconstruct` - Define `#deconstruct` and `#deconstruct_keys` methods on
+///   the Ruby class, allowing the wrapped objects to be used with Ruby's
+///   pattern matching (`case`/`in`). Fields are cloned and converted to Ruby,
+///   and `#deconstruct_keys` only converts the requested fields. For tuple
+///   structs only `#deconstruct` is defined.
+/// * `io` - Define `#read`, `#write`, `#each_line`, and `#eof?` methods on
+///   the Ruby class, using the type's `magnus::typed_data::Io`
+///   implementation, so the wrapped objects can be used as IO-like objects.
 /// * `unsafe_generics` - The derived implementation of [`TypedData`] is not
 ///   guaranteed to be correct for types with generics. If you are sure it is
 ///   for your type this attribute can be used to override the compile time
 ///   error usually generated for types with generics.
 ///
+/// Generated methods are defined when the class is first fetched with
+/// `TypedData::class`. A method is skipped if the class already defines a
+/// method of the same name, so user defined methods are never replaced.
+///
 /// # Field Attributes
 ///
 /// The `#[magnus(...)]` attribute can be set on struct fields with the
@@ -254,6 +389,18 @@ pub fn derive_data_type_functions(input: TokenStream) -> TokenStream {
 ///
 /// * `opaque_attr_reader` - For a Ruby value wrapped in `Opaque`, creates a
 ///   accessor method that returns the unwrapped Ruby value.
+/// * `attr_reader` - Define a reader method on the Ruby class with the same
+///   name as the field, returning a clone of the field's value converted to
+///   Ruby. For `Cell<T>` and `RefCell<T>` fields the inner value is returned.
+///   `None` is returned to Ruby as `nil`.
+/// * `attr_writer` - Define a writer method (`name=`) on the Ruby class,
+///   setting the field to the value converted from Ruby. The field must be a
+///   `Cell<T>` or `RefCell<T>`, as wrapped data is only available by shared
+///   reference.
+/// * `attr_accessor` - Both `attr_reader` and `attr_writer`.
+///
+/// Fields without an attribute are not exposed to Ruby, so fields that can
+/// not be converted to/from Ruby values can simply be left unmarked.
 ///
 /// # Variant Attributes
 ///
@@ -399,3 +546,124 @@ pub fn derive_typed_data(input: TokenStream) -> TokenStream {
     }
     .into()
 }
+
+/// Derives reading a struct's fields from the instance variables of a Ruby
+/// object.
+///
+/// This generates an associated function `from_obj`, which reads each field
+/// from the instance variable of the same name (prefixed with `@`),
+/// converting it with `TryConvert`. Missing instance variables are read as
+/// `nil`, so can be mapped to `None` with an `Option<T>` field.
+///
+/// # Attributes
+///
+/// The `#[magnus(...)]` attribute can be set on the struct with the following
+/// values:
+///
+/// * `write` - also generate a method `write_to`, which sets the instance
+///   variables of an object from the struct's fields, converting them with
+///   `IntoValue`.
+///
+/// The `#[magnus(...)]` attribute can be set on fields with the following
+/// values:
+///
+/// * `ivar = "..."` - the name of the instance variable, including the `@`.
+///   Defaults to the field name prefixed with `@`.
+/// * `default` - use the field type's `Default` implementation when the
+///   instance variable is missing or `nil`.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{rb_assert, Error, IvarView, RObject, Ruby};
+///
+/// #[derive(IvarView)]
+/// #[magnus(write)]
+/// struct Config {
+///     name: String,
+///     #[magnus(ivar = "@max_retries", default)]
+///     retries: u32,
+///     timeout: Option<f64>,
+/// }
+///
+/// fn example(ruby: &Ruby) -> Result<(), Error> {
+///     let obj: RObject = ruby.eval(
+///         r#"
+///             class Config
+///               def initialize
+///                 @name = "example"
+///               end
+///             end
+///             Config.new
+///         "#,
+///     )?;
+///
+///     let mut config = Config::from_obj(obj)?;
+///     assert_eq!(config.name, "example");
+///     assert_eq!(config.retries, 0);
+///     assert_eq!(config.timeout, None);
+///
+///     config.retries = 3;
+///     config.write_to(obj)?;
+///     rb_assert!(ruby, "obj.instance_variable_get(:@max_retries) == 3", obj);
+///
+///     Ok(())
+/// }
+/// # Ruby::init(example).unwrap()
+/// ```
+#[proc_macro_derive(IvarView, attributes(magnus))]
+pub fn derive_ivar_view(input: TokenStream) -> TokenStream {
+    match ivar_view::expand_derive_ivar_view(parse_macro_input!(input)) {
+        Ok(tokens) => tokens,
+        Err(e) => e.into_compile_error(),
+    }
+    .into()
+}
+
+/// Derives the traits to use a newtype wrapping a Ruby type in place of the
+/// wrapped type.
+///
+/// This implements `ReprValue`, `IntoValue`, and `TryConvert` for a tuple
+/// struct with a single field, delegating to the wrapped type. This allows
+/// distinct Rust types for Ruby values with different meanings, that can be
+/// used as arguments and return values of functions bound as Ruby methods
+/// the same as the wrapped type.
+///
+/// The struct must be `#[repr(transparent)]`, and must also implement `Copy`
+/// and `Clone`. The wrapped type must be a type representing a Ruby object,
+/// such as `Value`, `Integer`, or `RString` (or another `Transparent`
+/// newtype), deriving for any other field type is a compile error.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{function, prelude::*, rb_assert, Error, Integer, Ruby, Transparent};
+///
+/// #[derive(Clone, Copy, Transparent)]
+/// #[repr(transparent)]
+/// struct UserId(Integer);
+///
+/// fn next_user_id(id: UserId) -> Result<UserId, Error> {
+///     Ok(UserId(id.0.funcall("succ", ())?))
+/// }
+///
+/// fn example(ruby: &Ruby) -> Result<(), Error> {
+///     ruby.define_global_function("next_user_id", function!(next_user_id, 1));
+///
+///     rb_assert!(ruby, "next_user_id(41) == 42");
+///
+///     let id: UserId = ruby.eval("42")?;
+///     assert!(id.is_kind_of(ruby.class_integer()));
+///
+///     Ok(())
+/// }
+/// # Ruby::init(example).unwrap()
+/// ```
+#[proc_macro_derive(Transparent)]
+pub fn derive_transparent(input: TokenStream) -> TokenStream {
+    match transparent::expand_derive_transparent(parse_macro_input!(input)) {
+        Ok(tokens) => tokens,
+        Err(e) => e.into_compile_error(),
+    }
+    .into()
+}
@@ -0,0 +1,79 @@
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::{
+    parse_quote, punctuated::Punctuated, spanned::Spanned, Data, DataStruct, DeriveInput, Error,
+    Fields, FieldsUnnamed, Meta, Token,
+};
+
+pub fn expand_derive_transparent(mut input: DeriveInput) -> Result<TokenStream, Error> {
+    let mut repr_transparent = false;
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("repr")) {
+        let reprs = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+        repr_transparent |= reprs.iter().any(|r| r.path().is_ident("transparent"));
+    }
+    if !repr_transparent {
+        return Err(Error::new(
+            input.ident.span(),
+            "Transparent requires #[repr(transparent)]",
+        ));
+    }
+
+    let inner = match input.data {
+        Data::Struct(DataStruct {
+            fields: Fields::Unnamed(FieldsUnnamed { ref unnamed, .. }),
+            ..
+        }) if unnamed.len() == 1 => unnamed[0].ty.clone(),
+        _ => {
+            return Err(Error::new(
+                input.span(),
+                "Transparent can only be derived for tuple structs with a single field",
+            ))
+        }
+    };
+
+    // the field must itself be a Ruby type (a magnus type, or another
+    // Transparent newtype), checked with the struct's own bounds so the error
+    // points at the field rather than an unsatisfied bound on the impls
+    let (impl_generics, _, where_clause) = input.generics.split_for_impl();
+    let assert_field = quote_spanned! {inner.span()=>
+        const _: () = {
+            #[allow(dead_code)]
+            fn assert_field_is_repr_value #impl_generics () #where_clause {
+                magnus::__private::assert_repr_value::<#inner>();
+            }
+        };
+    };
+
+    input
+        .generics
+        .make_where_clause()
+        .predicates
+        .push(parse_quote! {
+            #inner: magnus::value::ReprValue + magnus::IntoValue + magnus::TryConvert
+        });
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        #assert_field
+
+        unsafe impl #impl_generics magnus::__private::ReprValueSealed for #ident #ty_generics #where_clause {}
+
+        impl #impl_generics magnus::value::ReprValue for #ident #ty_generics #where_clause {}
+
+        impl #impl_generics magnus::IntoValue for #ident #ty_generics #where_clause {
+            #[inline]
+            fn into_value_with(self, handle: &magnus::Ruby) -> magnus::Value {
+                magnus::IntoValue::into_value_with(self.0, handle)
+            }
+        }
+
+        impl #impl_generics magnus::TryConvert for #ident #ty_generics #where_clause {
+            #[inline]
+            fn try_convert(val: magnus::Value) -> Result<Self, magnus::Error> {
+                <#inner as magnus::TryConvert>::try_convert(val).map(Self)
+            }
+        }
+    })
+}
@@ -1,7 +1,9 @@
 use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
+use syn::ext::IdentExt;
 use syn::{
     spanned::Spanned, Data, DataEnum, DataStruct, DeriveInput, Error, Fields, FieldsNamed, LitStr,
+    Type,
 };
 
 use crate::util;
@@ -37,6 +39,16 @@ pub fn expand_derive_typed_data(input: DeriveInput) -> Result<TokenStream, Error
     let mut wb_protected = false;
     let mut frozen_shareable = false;
     let mut unsafe_generics = false;
+    let mut inspect = false;
+    let mut to_s = false;
+    let mut cmp = false;
+    let mut hash = false;
+    let mut ops = Vec::new();
+    let mut neg = false;
+    let mut index = None;
+    let mut coerce = None;
+    let mut deconstruct = false;
+    let mut io = false;
 
     attrs.parse_nested_meta(|meta| {
         if meta.path.is_ident("class") {
@@ -66,6 +78,39 @@ pub fn expand_derive_typed_data(input: DeriveInput) -> Result<TokenStream, Error
         } else if meta.path.is_ident("unsafe_generics") {
             unsafe_generics = true;
             Ok(())
+        } else if meta.path.is_ident("inspect") {
+            inspect = true;
+            Ok(())
+        } else if meta.path.is_ident("to_s") {
+            to_s = true;
+            Ok(())
+        } else if meta.path.is_ident("cmp") {
+            cmp = true;
+            Ok(())
+        } else if meta.path.is_ident("hash") {
+            hash = true;
+            Ok(())
+        } else if let Some(op) = ["add", "sub", "mul", "div", "rem"]
+            .into_iter()
+            .find(|op| meta.path.is_ident(op))
+        {
+            ops.push(op);
+            Ok(())
+        } else if meta.path.is_ident("neg") {
+            neg = true;
+            Ok(())
+        } else if meta.path.is_ident("index") {
+            index = Some(meta.value()?.parse::<LitStr>()?.parse::<Type>()?);
+            Ok(())
+        } else if meta.path.is_ident("coerce") {
+            coerce = Some(meta.value()?.parse::<LitStr>()?.parse::<Type>()?);
+            Ok(())
+        } else if meta.path.is_ident("deconstruct") {
+            deconstruct = true;
+            Ok(())
+        } else if meta.path.is_ident("io") {
+            io = true;
+            Ok(())
         } else if meta.path.is_ident("free_immediatly") {
             Err(meta.error("unsupported attribute (use free_immediately)"))
         } else {
@@ -98,6 +143,326 @@ pub fn expand_derive_typed_data(input: DeriveInput) -> Result<TokenStream, Error
     let ident = &input.ident;
     let generics = &input.generics;
 
+    let mut methods = Vec::new();
+    if inspect {
+        methods.push(quote! {
+            magnus::__private::define_derived_method(
+                class,
+                "inspect",
+                magnus::method!(magnus::typed_data::derived_inspect::<#ident>, 0),
+            )
+            .unwrap();
+        });
+    }
+    if to_s {
+        methods.push(quote! {
+            magnus::__private::define_derived_method(
+                class,
+                "to_s",
+                magnus::method!(magnus::typed_data::derived_to_s::<#ident>, 0),
+            )
+            .unwrap();
+        });
+    }
+    if cmp {
+        methods.push(quote! {
+            magnus::__private::define_derived_method(
+                class,
+                "<=>",
+                magnus::method!(<#ident as magnus::typed_data::Cmp>::cmp, 1),
+            )
+            .unwrap();
+            magnus::__private::define_derived_method(
+                class,
+                "==",
+                magnus::method!(magnus::typed_data::derived_eq::<#ident>, 1),
+            )
+            .unwrap();
+            class.include_module(ruby.module_comparable()).unwrap();
+        });
+    }
+    if hash {
+        methods.push(quote! {
+            magnus::__private::define_derived_method(
+                class,
+                "hash",
+                magnus::method!(<#ident as magnus::typed_data::Hash>::hash, 0),
+            )
+            .unwrap();
+            magnus::__private::define_derived_method(
+                class,
+                "eql?",
+                magnus::method!(<#ident as magnus::typed_data::IsEql>::is_eql, 1),
+            )
+            .unwrap();
+        });
+    }
+    for op in ops {
+        let (trait_ident, method) = match op {
+            "add" => (quote! { Add }, "+"),
+            "sub" => (quote! { Sub }, "-"),
+            "mul" => (quote! { Mul }, "*"),
+            "div" => (quote! { Div }, "/"),
+            "rem" => (quote! { Rem }, "%"),
+            _ => unreachable!(),
+        };
+        let func = syn::Ident::new(op, attrs.span());
+        methods.push(quote! {
+            magnus::__private::define_derived_method(
+                class,
+                #method,
+                magnus::method!(<#ident as magnus::typed_data::#trait_ident>::#func, 1),
+            )
+            .unwrap();
+        });
+    }
+    if neg {
+        methods.push(quote! {
+            magnus::__private::define_derived_method(
+                class,
+                "-@",
+                magnus::method!(<#ident as magnus::typed_data::Neg>::neg, 0),
+            )
+            .unwrap();
+        });
+    }
+    if let Some(index) = index {
+        methods.push(quote! {
+            magnus::__private::define_derived_method(
+                class,
+                "[]",
+                magnus::method!(<#ident as magnus::typed_data::Index<#index>>::index, 1),
+            )
+            .unwrap();
+        });
+    }
+    if let Some(coerce) = coerce {
+        methods.push(quote! {
+            magnus::__private::define_derived_method(
+                class,
+                "coerce",
+                magnus::method!(<#ident as magnus::typed_data::Coerce<#coerce>>::coerce, 1),
+            )
+            .unwrap();
+        });
+    }
+    if io {
+        methods.push(quote! {
+            magnus::__private::define_derived_method(
+                class,
+                "read",
+                magnus::method!(<#ident as magnus::typed_data::Io>::read, -1),
+            )
+            .unwrap();
+            magnus::__private::define_derived_method(
+                class,
+                "write",
+                magnus::method!(<#ident as magnus::typed_data::Io>::write, -1),
+            )
+            .unwrap();
+            magnus::__private::define_derived_method(
+                class,
+                "each_line",
+                magnus::method!(<#ident as magnus::typed_data::Io>::each_line, -1),
+            )
+            .unwrap();
+            magnus::__private::define_derived_method(
+                class,
+                "eof?",
+                magnus::method!(<#ident as magnus::typed_data::Io>::is_eof, 0),
+            )
+            .unwrap();
+        });
+    }
+    let mut accessors = Vec::new();
+    if let Data::Struct(DataStruct {
+        fields: Fields::Named(FieldsNamed { ref named, .. }),
+        ..
+    }) = input.data
+    {
+        for field in named {
+            let attrs = match util::get_magnus_attrubute(&field.attrs)? {
+                Some(v) => v,
+                None => continue,
+            };
+            let mut opaque_read = false;
+            let mut read = false;
+            let mut write = false;
+            attrs.parse_nested_meta(|meta| {
+                if meta.path.is_ident("opaque_attr_reader") {
+                    opaque_read = true;
+                    Ok(())
+                } else if meta.path.is_ident("attr_reader") {
+                    read = true;
+                    Ok(())
+                } else if meta.path.is_ident("attr_writer") {
+                    write = true;
+                    Ok(())
+                } else if meta.path.is_ident("attr_accessor") {
+                    read = true;
+                    write = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported attribute"))
+                }
+            })?;
+            let field_ident = field.ident.as_ref().unwrap();
+            let ty = &field.ty;
+            if opaque_read {
+                accessors.push(quote! {
+                    #[inline]
+                    fn #field_ident(&self) -> <#ty as magnus::value::OpaqueVal>::Val {
+                        let handle = magnus::Ruby::get().unwrap();
+                        handle.get_inner(self.#field_ident)
+                    }
+                });
+            }
+            let ruby_name = field_ident.unraw().to_string();
+            let cell = util::interior_mutable(ty);
+            if read {
+                let reader = match cell {
+                    Some((util::Cell::Cell, _)) => quote! {
+                        |rb_self: &#ident| rb_self.#field_ident.get()
+                    },
+                    Some((util::Cell::RefCell, _)) => quote! {
+                        |ruby: &magnus::Ruby, rb_self: &#ident| {
+                            rb_self.#field_ident.try_borrow().map(|v| Clone::clone(&*v)).map_err(|e| {
+                                magnus::Error::new(ruby.exception_runtime_error(), e.to_string())
+                            })
+                        }
+                    },
+                    None => quote! {
+                        |rb_self: &#ident| Clone::clone(&rb_self.#field_ident)
+                    },
+                };
+                methods.push(quote! {
+                    magnus::__private::define_derived_method(
+                        class,
+                        #ruby_name,
+                        magnus::method!(#reader, 0),
+                    )
+                    .unwrap();
+                });
+            }
+            if write {
+                let writer_name = format!("{}=", ruby_name);
+                let writer = match cell {
+                    Some((util::Cell::Cell, inner)) => quote! {
+                        |rb_self: &#ident, val: #inner| rb_self.#field_ident.set(val)
+                    },
+                    Some((util::Cell::RefCell, inner)) => quote! {
+                        |ruby: &magnus::Ruby, rb_self: &#ident, val: #inner| {
+                            *rb_self.#field_ident.try_borrow_mut().map_err(|e| {
+                                magnus::Error::new(ruby.exception_runtime_error(), e.to_string())
+                            })? = val;
+                            Ok::<_, magnus::Error>(())
+                        }
+                    },
+                    None => {
+                        return Err(Error::new_spanned(
+                            ty,
+                            "`attr_writer` and `attr_accessor` require a `Cell` or `RefCell` field",
+                        ))
+                    }
+                };
+                methods.push(quote! {
+                    magnus::__private::define_derived_method(
+                        class,
+                        #writer_name,
+                        magnus::method!(#writer, 1),
+                    )
+                    .unwrap();
+                });
+            }
+        }
+    }
+
+    if deconstruct {
+        let fields = match input.data {
+            Data::Struct(DataStruct { ref fields, .. }) => fields,
+            _ => {
+                return Err(Error::new(
+                    attrs.span(),
+                    "`deconstruct` is only supported for structs",
+                ))
+            }
+        };
+        let values = fields.iter().enumerate().map(|(i, field)| {
+            let member = match field.ident {
+                Some(ref field_ident) => quote! { #field_ident },
+                None => syn::Index::from(i).into_token_stream(),
+            };
+            field_value(&member, &field.ty)
+        });
+        let len = fields.len();
+        methods.push(quote! {
+            magnus::__private::define_derived_method(
+                class,
+                "deconstruct",
+                magnus::method!(
+                    |ruby: &magnus::Ruby, rb_self: &#ident| {
+                        let ary = ruby.ary_new_capa(#len);
+                        #(ary.push(#values?)?;)*
+                        Ok::<_, magnus::Error>(ary)
+                    },
+                    0
+                ),
+            )
+            .unwrap();
+        });
+        if let Fields::Named(FieldsNamed { ref named, .. }) = fields {
+            let names = named
+                .iter()
+                .map(|field| field.ident.as_ref().unwrap().unraw().to_string())
+                .collect::<Vec<_>>();
+            let values = named
+                .iter()
+                .map(|field| {
+                    let field_ident = field.ident.as_ref().unwrap();
+                    field_value(&quote! { #field_ident }, &field.ty)
+                })
+                .collect::<Vec<_>>();
+            methods.push(quote! {
+                magnus::__private::define_derived_method(
+                    class,
+                    "deconstruct_keys",
+                    magnus::method!(
+                        |ruby: &magnus::Ruby, rb_self: &#ident, keys: Option<magnus::RArray>| {
+                            let hash = ruby.hash_new();
+                            match keys {
+                                None => {
+                                    #(hash.aset(ruby.to_symbol(#names), #values?)?;)*
+                                }
+                                Some(keys) => {
+                                    for key in keys.to_vec::<magnus::Value>()? {
+                                        let name = match magnus::Symbol::from_value(key) {
+                                            Some(sym) => sym.name()?,
+                                            None => continue,
+                                        };
+                                        match &*name {
+                                            #(#names => hash.aset(key, #values?)?,)*
+                                            _ => (),
+                                        }
+                                    }
+                                }
+                            }
+                            Ok::<_, magnus::Error>(hash)
+                        },
+                        1
+                    ),
+                )
+                .unwrap();
+            });
+        }
+    }
+
+    if !methods.is_empty() && !generics.to_token_stream().is_empty() {
+        return Err(Error::new_spanned(
+            generics,
+            "generating methods is not supported for types with generics",
+        ));
+    }
+
     let mut arms = Vec::new();
     if let Data::Enum(DataEnum { ref variants, .. }) = input.data {
         for variant in variants.into_iter() {
@@ -134,10 +499,18 @@ pub fn expand_derive_typed_data(input: DeriveInput) -> Result<TokenStream, Error
             });
         }
     }
+    // methods are defined on the base class when it is first fetched, make
+    // sure that happens before wrapping as a variant's class
+    let ensure_methods = if !methods.is_empty() {
+        quote! { Self::class(ruby); }
+    } else {
+        quote! {}
+    };
     let class_for = if !arms.is_empty() {
         quote! {
             fn class_for(ruby: &magnus::Ruby, value: &Self) -> magnus::RClass {
                 use magnus::{class, Module, Class, RClass, value::{Lazy, ReprValue}};
+                #ensure_methods
                 #[allow(unreachable_patterns)]
                 match value {
                     #(#arms,)*
@@ -149,40 +522,6 @@ pub fn expand_derive_typed_data(input: DeriveInput) -> Result<TokenStream, Error
         quote! {}
     };
 
-    let mut accessors = Vec::new();
-    if let Data::Struct(DataStruct {
-        fields: Fields::Named(FieldsNamed { ref named, .. }),
-        ..
-    }) = input.data
-    {
-        for field in named {
-            let attrs = match util::get_magnus_attrubute(&field.attrs)? {
-                Some(v) => v,
-                None => continue,
-            };
-            let mut read = false;
-            attrs.parse_nested_meta(|meta| {
-                if meta.path.is_ident("opaque_attr_reader") {
-                    read = true;
-                    Ok(())
-                } else {
-                    Err(meta.error("unsupported attribute"))
-                }
-            })?;
-            let ident = field.ident.as_ref().unwrap();
-            let ty = &field.ty;
-            if read {
-                accessors.push(quote! {
-                    #[inline]
-                    fn #ident(&self) -> <#ty as magnus::value::OpaqueVal>::Val {
-                        let handle = magnus::Ruby::get().unwrap();
-                        handle.get_inner(self.#ident)
-                    }
-                });
-            }
-        }
-    }
-
     let accessor_impl = if !accessors.is_empty() {
         quote! {
             impl #ident {
@@ -220,13 +559,32 @@ pub fn expand_derive_typed_data(input: DeriveInput) -> Result<TokenStream, Error
 
         unsafe impl #generics magnus::TypedData for #ident #generics {
             fn class(ruby: &magnus::Ruby) -> magnus::RClass {
+                match Self::try_class(ruby) {
+                    Ok(class) => class,
+                    Err(e) => panic!("{}", e),
+                }
+            }
+
+            fn try_class(ruby: &magnus::Ruby) -> Result<magnus::RClass, magnus::Error> {
                 use magnus::{class, Module, Class, RClass, value::{Lazy, ReprValue}};
                 static CLASS: Lazy<RClass> = Lazy::new(|ruby| {
                     let class: RClass = ruby.class_object().funcall("const_get", (#class,)).unwrap();
                     class.undef_default_alloc_func();
+                    #(#methods)*
                     class
                 });
-                ruby.get_inner(&CLASS)
+                // check before initialising CLASS, so if the class isn't yet
+                // defined CLASS isn't poisoned and can be retried later
+                if Lazy::try_get_inner(&CLASS).is_none() {
+                    let defined: bool = ruby.class_object().funcall("const_defined?", (#class,))?;
+                    if !defined {
+                        return Err(magnus::Error::new(
+                            ruby.exception_name_error(),
+                            format!("class `{}` for wrapped type `{}` is not defined", #class, stringify!(#ident)),
+                        ));
+                    }
+                }
+                Ok(ruby.get_inner(&CLASS))
             }
 
             fn data_type() -> &'static magnus::DataType {
@@ -239,3 +597,21 @@ pub fn expand_derive_typed_data(input: DeriveInput) -> Result<TokenStream, Error
     };
     Ok(tokens)
 }
+
+// Expression evaluating to a clone of the `member` field of `rb_self`, as a
+// `Result<_, magnus::Error>`.
+fn field_value(member: &TokenStream, ty: &Type) -> TokenStream {
+    match util::interior_mutable(ty) {
+        Some((util::Cell::Cell, _)) => quote! {
+            Ok::<_, magnus::Error>(rb_self.#member.get())
+        },
+        Some((util::Cell::RefCell, _)) => quote! {
+            rb_self.#member.try_borrow().map(|v| Clone::clone(&*v)).map_err(|e| {
+                magnus::Error::new(ruby.exception_runtime_error(), e.to_string())
+            })
+        },
+        None => quote! {
+            Ok::<_, magnus::Error>(Clone::clone(&rb_self.#member))
+        },
+    }
+}
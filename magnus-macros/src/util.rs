@@ -1,4 +1,4 @@
-use syn::{spanned::Spanned, Attribute, Error};
+use syn::{spanned::Spanned, Attribute, Error, GenericArgument, PathArguments, Type};
 
 pub fn get_magnus_attrubute(attrs: &[Attribute]) -> Result<Option<&Attribute>, Error> {
     let attrs = attrs
@@ -19,3 +19,34 @@ pub fn get_magnus_attrubute(attrs: &[Attribute]) -> Result<Option<&Attribute>, E
     }
     Ok(Some(attrs[0]))
 }
+
+pub enum Cell {
+    Cell,
+    RefCell,
+}
+
+/// If `ty` is a `Cell<T>` or `RefCell<T>` returns the kind of cell and `T`.
+///
+/// This is purely syntactic, so only matches types written as `Cell<T>` or
+/// `RefCell<T>` (optionally with a path, e.g. `std::cell::Cell<T>`), not
+/// aliases.
+pub fn interior_mutable(ty: &Type) -> Option<(Cell, &Type)> {
+    let segment = match ty {
+        Type::Path(path) if path.qself.is_none() => path.path.segments.last()?,
+        _ => return None,
+    };
+    let cell = if segment.ident == "Cell" {
+        Cell::Cell
+    } else if segment.ident == "RefCell" {
+        Cell::RefCell
+    } else {
+        return None;
+    };
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+            GenericArgument::Type(inner) => Some((cell, inner)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
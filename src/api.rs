@@ -1,7 +1,7 @@
 //! This module/file's name is a hack to get the `impl Ruby` defined here to
 //! show first in docs. This module shouldn't be exposed publicly.
 
-use std::{cell::RefCell, marker::PhantomData};
+use std::{cell::RefCell, ffi::CStr, marker::PhantomData, os::raw::c_char};
 
 use rb_sys::ruby_native_thread_p;
 
@@ -13,6 +13,11 @@ extern "C" {
     fn ruby_thread_has_gvl_p() -> ::std::os::raw::c_int;
 }
 
+// Declared in ruby/version.h, which isn't covered by rb-sys' bindings.
+extern "C" {
+    static ruby_version: [c_char; 0];
+}
+
 use crate::{error::RubyUnavailableError, value::ReprValue};
 
 #[derive(Clone, Copy)]
@@ -112,6 +117,7 @@ impl RubyGvlState {
 /// * [`nil`](#nil)
 /// * [`Proc`](#proc) - Ruby's blocks as objects
 /// * [`Process`](#process) - external processes
+/// * [`Random`](#random) - random number generation
 /// * [`Range`](#range)
 /// * [`RArray`](#rarray)
 /// * [`RbEncoding`](#rbencoding) - string encoding
@@ -130,6 +136,7 @@ impl RubyGvlState {
 /// * [`Time`](#time)
 /// * [`true`](#true)
 /// * [`typed_data::Obj`](#typed_dataobj) - wrapping Rust data in a Ruby object
+/// * [Version](#version) - runtime Ruby version and feature detection
 pub struct Ruby(PhantomData<*mut ()>);
 
 /// # Accessing `Ruby`
@@ -179,3 +186,114 @@ impl Ruby {
         Self(PhantomData)
     }
 }
+
+/// # Version
+///
+/// Functions to query the version of the running Ruby interpreter.
+///
+/// Magnus gates some APIs on the version of Ruby it was compiled against.
+/// These functions report the version of Ruby actually running, allowing an
+/// extension to branch at runtime.
+impl Ruby {
+    /// Returns the version of the running Ruby as `(major, minor, teeny)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let (major, minor, teeny) = ruby.version();
+    ///     let version = format!("{}.{}.{}", major, minor, teeny);
+    ///     assert_eq!(version, ruby.eval::<String>("RUBY_VERSION")?);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn version(&self) -> (u8, u8, u8) {
+        let version = unsafe { CStr::from_ptr(ruby_version.as_ptr()) };
+        let mut parts = version.to_bytes().split(|b| *b == b'.').map(|part| {
+            part.iter()
+                .take_while(|b| b.is_ascii_digit())
+                .fold(0_u8, |acc, b| {
+                    acc.saturating_mul(10).saturating_add(b - b'0')
+                })
+        });
+        (
+            parts.next().unwrap_or(0),
+            parts.next().unwrap_or(0),
+            parts.next().unwrap_or(0),
+        )
+    }
+
+    fn version_gte(&self, major: u8, minor: u8) -> bool {
+        let (running_major, running_minor, _) = self.version();
+        (running_major, running_minor) >= (major, minor)
+    }
+
+    /// Returns whether the running Ruby supports Ractors (Ruby 3.0 and
+    /// later).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     assert_eq!(ruby.supports_ractor(), ruby.eval::<bool>("!!defined?(Ractor)")?);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn supports_ractor(&self) -> bool {
+        self.version_gte(3, 0)
+    }
+
+    /// Returns whether the running Ruby supports `Class#subclasses` (Ruby 3.1
+    /// and later).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     assert_eq!(
+    ///         ruby.supports_subclasses(),
+    ///         ruby.eval::<bool>("Class.method_defined?(:subclasses)")?
+    ///     );
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn supports_subclasses(&self) -> bool {
+        self.version_gte(3, 1)
+    }
+
+    /// Returns whether the running Ruby supports the `Data` class (Ruby 3.2
+    /// and later).
+    ///
+    /// See also [`Ruby::define_data`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     assert_eq!(
+    ///         ruby.supports_data(),
+    ///         ruby.eval::<bool>("Object.const_defined?(:Data) && Data.respond_to?(:define)")?
+    ///     );
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn supports_data(&self) -> bool {
+        self.version_gte(3, 2)
+    }
+}
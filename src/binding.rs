@@ -0,0 +1,206 @@
+use std::fmt;
+
+use crate::{
+    error::Error,
+    into_value::IntoValue,
+    module::Module,
+    object::Object,
+    symbol::IntoSymbol,
+    try_convert::TryConvert,
+    value::{
+        private::{self, ReprValue as _},
+        NonZeroValue, ReprValue, Value,
+    },
+    Ruby,
+};
+
+/// # `Binding`
+///
+/// Functions that can be used to create instances of Ruby's `Binding` class.
+///
+/// See also the [`Binding`] type.
+impl Ruby {
+    /// Create a new `Binding`, a copy of Ruby's `TOPLEVEL_BINDING`.
+    ///
+    /// New local variables set on the returned binding are only visible to
+    /// code evaluated with that binding. This is the same scope `ERB` uses by
+    /// default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let binding = ruby.binding_new()?;
+    ///     binding.local_variable_set("a", 1)?;
+    ///     assert_eq!(binding.eval::<i64>("a + 1")?, 2);
+    ///
+    ///     assert!(ruby.binding_new()?.eval::<i64>("a").is_err());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn binding_new(&self) -> Result<Binding, Error> {
+        self.class_object()
+            .const_get::<_, Binding>("TOPLEVEL_BINDING")?
+            .funcall("dup", ())
+    }
+}
+
+/// A Value known to be an instance of Ruby's `Binding` class.
+///
+/// A `Binding` captures a scope, allowing code to be evaluated in that scope
+/// and its local variables to be read and written.
+///
+/// See the [`ReprValue`] and [`Object`] traits for additional methods
+/// available on this type. See [`Ruby`](Ruby#binding) for methods to create a
+/// `Binding`.
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct Binding(NonZeroValue);
+
+impl Binding {
+    /// Return `Some(Binding)` if `val` is a `Binding`, `None` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Binding, Error, Ruby, Value};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     assert!(Binding::from_value(ruby.eval::<Value>("binding")?).is_some());
+    ///     assert!(Binding::from_value(ruby.eval::<Value>("nil")?).is_none());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    #[inline]
+    pub fn from_value(val: Value) -> Option<Self> {
+        val.is_kind_of(Ruby::get_with(val).class_binding())
+            .then(|| unsafe { Self(NonZeroValue::new_unchecked(val)) })
+    }
+
+    /// Evaluate the string of Ruby code `code` in the scope of `self`,
+    /// converting the result to a `T`.
+    ///
+    /// As with [`Ruby::eval`] errors if the code raises or the conversion
+    /// fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let binding = ruby.binding_new()?;
+    ///     binding.local_variable_set("name", "World")?;
+    ///     let greeting: String = binding.eval(r#""Hello, #{name}!""#)?;
+    ///     assert_eq!(greeting, "Hello, World!");
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn eval<T>(self, code: &str) -> Result<T, Error>
+    where
+        T: TryConvert,
+    {
+        self.funcall("eval", (code,))
+    }
+
+    /// Return the value of the local variable `name` in `self`, converted to
+    /// a `T`.
+    ///
+    /// Errors with a `NameError` if the local variable is not defined.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let binding = ruby.binding_new()?;
+    ///     let _: i64 = binding.eval("count = 3")?;
+    ///     assert_eq!(binding.local_variable_get::<_, i64>("count")?, 3);
+    ///
+    ///     assert!(binding.local_variable_get::<_, i64>("missing").is_err());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn local_variable_get<N, T>(self, name: N) -> Result<T, Error>
+    where
+        N: IntoSymbol,
+        T: TryConvert,
+    {
+        let handle = Ruby::get_with(self);
+        self.funcall("local_variable_get", (name.into_symbol_with(&handle),))
+    }
+
+    /// Set the local variable `name` in `self` to `val`.
+    ///
+    /// If the local variable is not already defined it will be created, and
+    /// will only be visible to code evaluated with `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let binding = ruby.binding_new()?;
+    ///     binding.local_variable_set("count", 3)?;
+    ///     assert_eq!(binding.eval::<i64>("count * 2")?, 6);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn local_variable_set<N, T>(self, name: N, val: T) -> Result<(), Error>
+    where
+        N: IntoSymbol,
+        T: IntoValue,
+    {
+        let handle = Ruby::get_with(self);
+        let _: Value = self.funcall(
+            "local_variable_set",
+            (name.into_symbol_with(&handle), handle.into_value(val)),
+        )?;
+        Ok(())
+    }
+}
+
+impl fmt::Display for Binding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", unsafe { self.to_s_infallible() })
+    }
+}
+
+impl fmt::Debug for Binding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.inspect())
+    }
+}
+
+impl IntoValue for Binding {
+    #[inline]
+    fn into_value_with(self, _: &Ruby) -> Value {
+        self.0.get()
+    }
+}
+
+impl Object for Binding {}
+
+unsafe impl private::ReprValue for Binding {}
+
+impl ReprValue for Binding {}
+
+impl TryConvert for Binding {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        Self::from_value(val).ok_or_else(|| Error::type_error("Binding", val))
+    }
+}
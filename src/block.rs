@@ -6,19 +6,20 @@ use std::{
     fmt,
     mem::{forget, size_of},
     os::raw::c_int,
+    panic::{catch_unwind, AssertUnwindSafe},
     slice,
 };
 
 use rb_sys::{
-    rb_block_given_p, rb_block_proc, rb_data_typed_object_wrap, rb_obj_is_proc, rb_proc_arity,
-    rb_proc_call_kw, rb_proc_lambda_p, rb_proc_new, rb_yield, rb_yield_splat, rb_yield_values_kw,
-    VALUE,
+    rb_block_call_kw, rb_block_given_p, rb_block_proc, rb_data_typed_object_wrap, rb_obj_is_proc,
+    rb_proc_arity, rb_proc_call_kw, rb_proc_lambda_p, rb_proc_new, rb_yield, rb_yield_splat,
+    rb_yield_values_kw, VALUE,
 };
 
 use crate::{
     data_type_builder,
     enumerator::Enumerator,
-    error::{ensure, protect, Error},
+    error::{protect, raise, with_ensure, Error},
     gc,
     into_value::{kw_splat, ArgList, IntoValue, RArrayArgList},
     method::{Block, BlockReturn},
@@ -28,7 +29,7 @@ use crate::{
     typed_data::{DataType, DataTypeFunctions},
     value::{
         private::{self, ReprValue as _},
-        NonZeroValue, ReprValue, Value,
+        Id, Lazy, NonZeroValue, ReprValue, Value,
     },
     Ruby,
 };
@@ -422,6 +423,93 @@ impl Proc {
     pub fn is_lambda(self) -> bool {
         unsafe { Value::new(rb_proc_lambda_p(self.as_rb_value())).to_bool() }
     }
+
+    /// Returns a curried version of `self`.
+    ///
+    /// The curried proc will take arguments one or more at a time, calling
+    /// `self` once enough arguments have been supplied. `arity` sets the
+    /// number of arguments required, if `None` the arity of `self` is used.
+    ///
+    /// Returns `Err` if `self` is a lambda and `arity` is given but not
+    /// compatible with the arguments `self` accepts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{block::Proc, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let proc: Proc = ruby.eval("lambda {|a, b, c| a + b + c}")?;
+    ///     let curried = proc.curry(None)?;
+    ///     let curried: Proc = curried.call((1,))?;
+    ///     let curried: Proc = curried.call((2,))?;
+    ///     let res: i64 = curried.call((3,))?;
+    ///     assert_eq!(res, 6);
+    ///
+    ///     let proc: Proc = ruby.eval("lambda {|*args| args.sum}")?;
+    ///     let curried: Proc = proc.curry(Some(2))?.call((1,))?;
+    ///     let res: i64 = curried.call((2,))?;
+    ///     assert_eq!(res, 3);
+    ///
+    ///     let proc: Proc = ruby.eval("lambda {|a, b| a + b}")?;
+    ///     assert!(proc.curry(Some(3)).is_err());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn curry(self, arity: Option<usize>) -> Result<Proc, Error> {
+        match arity {
+            Some(arity) => self.funcall("curry", (arity,)),
+            None => self.funcall("curry", ()),
+        }
+    }
+
+    /// Returns a new lambda that calls `self` with `args` followed by any
+    /// arguments the new lambda is called with.
+    ///
+    /// Keyword arguments given in `args` are merged with any keyword
+    /// arguments the new lambda is called with, and any block passed to the
+    /// new lambda is passed on to `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{block::Proc, kwargs, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let proc: Proc = ruby.eval("lambda {|a, b, c| a * b + c}")?;
+    ///     let partial = proc.partial((2, 3))?;
+    ///     let res: i64 = partial.call((4,))?;
+    ///     assert_eq!(res, 10);
+    ///
+    ///     let proc: Proc = ruby.eval("lambda {|a, scale:, offset:| a * scale + offset}")?;
+    ///     let partial = proc.partial((kwargs!("scale" => 10),))?;
+    ///     let res: i64 = partial.call((4, kwargs!("offset" => 2)))?;
+    ///     assert_eq!(res, 42);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn partial<A>(self, args: A) -> Result<Proc, Error>
+    where
+        A: RArrayArgList,
+    {
+        static PARTIAL: Lazy<Proc> = Lazy::new(|ruby| {
+            ruby.eval("->(f, a, kw) { ->(*b, **k, &blk) { f.call(*a, *b, **kw, **k, &blk) } }")
+                .unwrap()
+        });
+        let handle = Ruby::get_with(self);
+        let kw_splat = kw_splat(&args);
+        let args = args.into_array_arg_list_with(&handle);
+        let kw = if kw_splat {
+            args.pop::<Value>()?
+        } else {
+            handle.hash_new().as_value()
+        };
+        handle.get_inner(&PARTIAL).call((self, args, kw))
+    }
 }
 
 impl fmt::Display for Proc {
@@ -523,6 +611,58 @@ where
     unsafe { (&mut (*ptr).0 as *mut F, value) }
 }
 
+/// Call `method` on `recv` with `args`, and a block that calls `func` with
+/// the first value yielded to it.
+///
+/// `func` is only borrowed for the duration of the call, so this must not be
+/// used with methods that retain the block.
+pub(crate) fn block_call_closure<F>(
+    recv: Value,
+    method: Id,
+    args: &[Value],
+    mut func: F,
+) -> Result<(), Error>
+where
+    F: FnMut(Value) -> Result<(), Error>,
+{
+    unsafe extern "C" fn call<F>(
+        yielded_arg: VALUE,
+        callback_arg: VALUE,
+        _argc: c_int,
+        _argv: *const VALUE,
+        _blockarg: VALUE,
+    ) -> VALUE
+    where
+        F: FnMut(Value) -> Result<(), Error>,
+    {
+        let func = &mut *(callback_arg as *mut F);
+        let res = match catch_unwind(AssertUnwindSafe(|| func(Value::new(yielded_arg)))) {
+            Ok(v) => v,
+            Err(e) => Err(Error::from_panic(e)),
+        };
+        match res {
+            Ok(()) => Ruby::get_unchecked().qnil().as_rb_value(),
+            Err(e) => raise(e),
+        }
+    }
+
+    let call_func =
+        call::<F> as unsafe extern "C" fn(VALUE, VALUE, c_int, *const VALUE, VALUE) -> VALUE;
+
+    protect(|| unsafe {
+        Value::new(rb_block_call_kw(
+            recv.as_rb_value(),
+            method.as_rb_id(),
+            args.len() as c_int,
+            args.as_ptr() as *const VALUE,
+            Some(call_func),
+            &mut func as *mut F as VALUE,
+            0,
+        ))
+    })?;
+    Ok(())
+}
+
 /// # Blocks
 ///
 /// Functions to enable working with Ruby blocks.
@@ -690,6 +830,15 @@ impl Ruby {
 
     /// Yields a Ruby Array to the block given to the current method.
     ///
+    /// The elements of `vals` are yielded as separate values, like Ruby's
+    /// `yield *vals`. A block with a single parameter (or using `_1`/`it`)
+    /// will only receive the first element.
+    ///
+    /// To yield an array so that a block with a single parameter receives
+    /// the whole array, while a block with multiple parameters destructures
+    /// it (as with `Hash#each` yielding `[key, value]` pairs), pass the array
+    /// to [`yield_value`](Ruby::yield_value) instead.
+    ///
     /// **Note:** A method using `yield_splat` converted to an Enumerator with
     /// `to_enum`/[`Value::enumeratorize`] will result in a non-functional
     /// Enumerator on versions of Ruby before 3.1. See [`YieldSplat`] for an
@@ -738,6 +887,35 @@ impl Ruby {
     /// }
     /// # Ruby::init(example).unwrap()
     /// ```
+    ///
+    /// Comparing `yield_splat` and `yield_value` with blocks taking one or
+    /// two parameters:
+    ///
+    /// ```
+    /// use magnus::{function, rb_assert, Error, Ruby, Value};
+    ///
+    /// fn splat(ruby: &Ruby) -> Result<Value, Error> {
+    ///     ruby.yield_splat(ruby.ary_from_vec(vec![1, 2]))
+    /// }
+    ///
+    /// fn pair(ruby: &Ruby) -> Result<Value, Error> {
+    ///     ruby.yield_value(ruby.ary_from_vec(vec![1, 2]))
+    /// }
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     ruby.define_global_function("splat", function!(splat, 0));
+    ///     ruby.define_global_function("pair", function!(pair, 0));
+    ///
+    ///     rb_assert!(ruby, "splat {|a, b| [a, b]} == [1, 2]");
+    ///     rb_assert!(ruby, "splat {|a| a} == 1");
+    ///
+    ///     rb_assert!(ruby, "pair {|a, b| [a, b]} == [1, 2]");
+    ///     rb_assert!(ruby, "pair {|a| a} == [1, 2]");
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
     pub fn yield_splat<T>(&self, vals: RArray) -> Result<T, Error>
     where
         T: TryConvert,
@@ -989,8 +1167,8 @@ where
 // with the fibers used in Ruby itself to implement `Enumerator#next`.
 // We have to use protect in `yield` because otherwise Ruby code can
 // `break`/`return` through Rust code and break Rust invariants.
-// This gives up using `protect` by instead using `ensure`, not exposing the
-// `yield` call to user code, and maintaining the invariants ourselves. As it
+// This gives up using `protect` by instead using `with_ensure`, not exposing
+// the `yield` call to user code, and maintaining the invariants ourselves. As it
 // can still be `brake`/`return`ed though it can't be public as it's only safe
 // to call as the last thing in one of our method wrappers (where the raise
 // would normally go). Returning an iterator from a method will trigger this.
@@ -1006,7 +1184,7 @@ where
                   // closure might never reach the end, so wouldn't drop. The second
                   // closure is always run, and always after the first, so we do the
                   // drop there
-    ensure(
+    with_ensure(
         || {
             for val in &mut *ptr {
                 rb_yield(handle.into_value(val).as_rb_value());
@@ -1028,7 +1206,7 @@ where
     let handle = Ruby::get_unchecked();
     let ptr = &mut iter as *mut I;
     forget(iter);
-    ensure(
+    with_ensure(
         || {
             for val in &mut *ptr {
                 let kw_splat = kw_splat(&val);
@@ -1055,7 +1233,7 @@ where
 {
     let ptr = &mut iter as *mut I;
     forget(iter);
-    ensure(
+    with_ensure(
         || {
             for val in &mut *ptr {
                 rb_yield_splat(val.as_rb_value());
@@ -1073,6 +1251,13 @@ where
 ///
 /// `I` must implement `Iterator<Item = T>`, where `T` implements [`IntoValue`].
 ///
+/// The `Enumerator` variant is created by the method before any iterator is
+/// yielded, so the Enumerator's `size` can't be taken from `I`, even if it
+/// implements [`ExactSizeIterator`]. Supply the size by hand when creating
+/// the Enumerator, with
+/// [`enumeratorize_exact_size`](ReprValue::enumeratorize_exact_size) or
+/// [`enumeratorize_with_size`](ReprValue::enumeratorize_with_size).
+///
 /// # Examples
 ///
 /// ```
@@ -1116,6 +1301,8 @@ pub enum Yield<I> {
 ///
 /// `I` must implement `Iterator<Item = T>`, where `T` implements [`ArgList`].
 ///
+/// See [`Yield`] for giving the Enumerator a `size`.
+///
 /// # Examples
 ///
 /// ```
@@ -1162,6 +1349,8 @@ pub enum YieldValues<I> {
 ///
 /// `I` must implement `Iterator<Item = RArray>`.
 ///
+/// See [`Yield`] for giving the Enumerator a `size`.
+///
 /// # Examples
 ///
 /// ```
@@ -0,0 +1,380 @@
+use std::{fmt, os::raw::c_int};
+
+use rb_sys::{rb_method_call_kw, VALUE};
+
+use crate::{
+    block::Proc,
+    error::{protect, Error},
+    into_value::{kw_splat, ArgList, IntoValue},
+    object::Object,
+    symbol::Symbol,
+    try_convert::TryConvert,
+    value::{
+        private::{self, ReprValue as _},
+        NonZeroValue, ReprValue, Value,
+    },
+    Ruby,
+};
+
+/// A Value known to be an instance of Ruby's `Method` class, a method bound
+/// to a receiver.
+///
+/// This type is named `BoundMethod` to avoid confusion with the
+/// [`Method`](crate::method::Method) trait.
+///
+/// See [`ReprValue::method`] to get a `BoundMethod`. See the [`ReprValue`]
+/// and [`Object`] traits for additional methods available on this type.
+///
+/// As with other Ruby types, a `BoundMethod` stored in a heap allocated
+/// data structure must be wrapped in an [`Opaque`](crate::value::Opaque) and
+/// marked, or registered with [`gc::register_mark_object`](crate::gc::register_mark_object),
+/// to prevent it being garbage collected.
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct BoundMethod(NonZeroValue);
+
+impl BoundMethod {
+    /// Return `Some(BoundMethod)` if `val` is a `Method`, `None` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{BoundMethod, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     assert!(BoundMethod::from_value(ruby.eval("1.method(:+)")?).is_some());
+    ///     assert!(BoundMethod::from_value(ruby.eval("Integer.instance_method(:+)")?).is_none());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    #[inline]
+    pub fn from_value(val: Value) -> Option<Self> {
+        val.is_kind_of(Ruby::get_with(val).class_method())
+            .then(|| unsafe { Self(NonZeroValue::new_unchecked(val)) })
+    }
+
+    #[inline]
+    pub(crate) unsafe fn from_value_unchecked(val: Value) -> Self {
+        Self(NonZeroValue::new_unchecked(val))
+    }
+
+    /// Call `self` with `args`.
+    ///
+    /// Returns `Ok(T)` if the method returns without error and the return
+    /// value converts to a `T`, or returns `Err` if the method raises or the
+    /// conversion fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let method = ruby.str_new("example").method("center")?;
+    ///     let res: String = method.call((11, "*"))?;
+    ///     assert_eq!(res, "**example**");
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn call<A, T>(self, args: A) -> Result<T, Error>
+    where
+        A: ArgList,
+        T: TryConvert,
+    {
+        let handle = Ruby::get_with(self);
+        let kw_splat = kw_splat(&args);
+        let args = args.into_arg_list_with(&handle);
+        let slice = args.as_ref();
+        unsafe {
+            protect(|| {
+                Value::new(rb_method_call_kw(
+                    slice.len() as c_int,
+                    slice.as_ptr() as *const VALUE,
+                    self.as_rb_value(),
+                    kw_splat as c_int,
+                ))
+            })
+            .and_then(TryConvert::try_convert)
+        }
+    }
+
+    /// Return the object `self` is bound to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let s = ruby.str_new("example");
+    ///     assert!(s.method("upcase")?.receiver()?.equal(s)?);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn receiver(self) -> Result<Value, Error> {
+        self.funcall("receiver", ())
+    }
+
+    /// Return the name of `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let method = ruby.str_new("example").method("upcase")?;
+    ///     assert_eq!(method.name()?.name()?, "upcase");
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn name(self) -> Result<Symbol, Error> {
+        self.funcall("name", ())
+    }
+
+    /// Return the class or module that defines `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let method = ruby.ary_new().method("map")?;
+    ///     assert!(method.owner()?.equal(ruby.class_array())?);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn owner(self) -> Result<Value, Error> {
+        self.funcall("owner", ())
+    }
+
+    /// Dissociate `self` from its receiver, returning an [`UnboundMethod`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let unbound = ruby.str_new("a").method("upcase")?.unbind()?;
+    ///     let res: String = unbound.bind(ruby.str_new("b"))?.call(())?;
+    ///     assert_eq!(res, "B");
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn unbind(self) -> Result<UnboundMethod, Error> {
+        self.funcall("unbind", ())
+    }
+
+    /// Return `self` converted to a [`Proc`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let proc = ruby.str_new("example").method("upcase")?.to_proc()?;
+    ///     let res: String = proc.call(())?;
+    ///     assert_eq!(res, "EXAMPLE");
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn to_proc(self) -> Result<Proc, Error> {
+        self.funcall("to_proc", ())
+    }
+}
+
+impl fmt::Display for BoundMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", unsafe { self.to_s_infallible() })
+    }
+}
+
+impl fmt::Debug for BoundMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.inspect())
+    }
+}
+
+impl IntoValue for BoundMethod {
+    #[inline]
+    fn into_value_with(self, _: &Ruby) -> Value {
+        self.0.get()
+    }
+}
+
+impl Object for BoundMethod {}
+
+unsafe impl private::ReprValue for BoundMethod {}
+
+impl ReprValue for BoundMethod {}
+
+impl TryConvert for BoundMethod {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        Self::from_value(val).ok_or_else(|| {
+            Error::new(
+                Ruby::get_with(val).exception_type_error(),
+                format!("no implicit conversion of {} into Method", unsafe {
+                    val.classname()
+                },),
+            )
+        })
+    }
+}
+
+/// A Value known to be an instance of Ruby's `UnboundMethod` class, a method
+/// not bound to a receiver.
+///
+/// See [`BoundMethod::unbind`] and [`Module::instance_method`] to get an
+/// `UnboundMethod`. See the [`ReprValue`] and [`Object`] traits for
+/// additional methods available on this type.
+///
+/// [`Module::instance_method`]: crate::Module::instance_method
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct UnboundMethod(NonZeroValue);
+
+impl UnboundMethod {
+    /// Return `Some(UnboundMethod)` if `val` is an `UnboundMethod`, `None`
+    /// otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby, UnboundMethod};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     assert!(UnboundMethod::from_value(ruby.eval("Integer.instance_method(:+)")?).is_some());
+    ///     assert!(UnboundMethod::from_value(ruby.eval("1.method(:+)")?).is_none());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    #[inline]
+    pub fn from_value(val: Value) -> Option<Self> {
+        val.is_kind_of(Ruby::get_with(val).class_unbound_method())
+            .then(|| unsafe { Self(NonZeroValue::new_unchecked(val)) })
+    }
+
+    /// Bind `self` to `obj`, returning a [`BoundMethod`].
+    ///
+    /// Returns `Err` if `obj` is not a kind of the class or module that
+    /// defines `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let unbound = ruby.class_string().instance_method("length")?;
+    ///     let len: usize = unbound.bind(ruby.str_new("example"))?.call(())?;
+    ///     assert_eq!(len, 7);
+    ///
+    ///     assert!(unbound.bind(ruby.ary_new()).is_err());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn bind<T>(self, obj: T) -> Result<BoundMethod, Error>
+    where
+        T: IntoValue,
+    {
+        let obj = Ruby::get_with(self).into_value(obj);
+        self.funcall("bind", (obj,))
+    }
+
+    /// Return the name of `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let unbound = ruby.class_string().instance_method("length")?;
+    ///     assert_eq!(unbound.name()?.name()?, "length");
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn name(self) -> Result<Symbol, Error> {
+        self.funcall("name", ())
+    }
+
+    /// Return the class or module that defines `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let unbound = ruby.class_string().instance_method("length")?;
+    ///     assert!(unbound.owner()?.equal(ruby.class_string())?);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn owner(self) -> Result<Value, Error> {
+        self.funcall("owner", ())
+    }
+}
+
+impl fmt::Display for UnboundMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", unsafe { self.to_s_infallible() })
+    }
+}
+
+impl fmt::Debug for UnboundMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.inspect())
+    }
+}
+
+impl IntoValue for UnboundMethod {
+    #[inline]
+    fn into_value_with(self, _: &Ruby) -> Value {
+        self.0.get()
+    }
+}
+
+impl Object for UnboundMethod {}
+
+unsafe impl private::ReprValue for UnboundMethod {}
+
+impl ReprValue for UnboundMethod {}
+
+impl TryConvert for UnboundMethod {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        Self::from_value(val).ok_or_else(|| {
+            Error::new(
+                Ruby::get_with(val).exception_type_error(),
+                format!("no implicit conversion of {} into UnboundMethod", unsafe {
+                    val.classname()
+                },),
+            )
+        })
+    }
+}
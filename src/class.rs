@@ -2,10 +2,15 @@
 //!
 //! See also [`Ruby`](Ruby#core-classes) for more class related methods.
 
-use std::{borrow::Cow, ffi::CStr, fmt, mem::transmute, os::raw::c_int};
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    ffi::CStr,
+    fmt,
+    mem::transmute,
+    os::raw::c_int,
+};
 
-#[cfg(ruby_gte_3_1)]
-use rb_sys::rb_cRefinement;
 use rb_sys::{
     self, rb_alloc_func_t, rb_cArray, rb_cBasicObject, rb_cBinding, rb_cClass, rb_cComplex,
     rb_cDir, rb_cEncoding, rb_cEnumerator, rb_cFalseClass, rb_cFile, rb_cFloat, rb_cHash, rb_cIO,
@@ -13,11 +18,16 @@ use rb_sys::{
     rb_cObject, rb_cProc, rb_cRandom, rb_cRange, rb_cRational, rb_cRegexp, rb_cStat, rb_cString,
     rb_cStruct, rb_cSymbol, rb_cThread, rb_cTime, rb_cTrueClass, rb_cUnboundMethod, rb_class2name,
     rb_class_new, rb_class_new_instance_kw, rb_class_superclass, rb_define_alloc_func,
-    rb_get_alloc_func, rb_obj_alloc, rb_undef_alloc_func, ruby_value_type, VALUE,
+    rb_get_alloc_func, rb_obj_alloc, rb_undef_alloc_func, ruby_fl_type, ruby_value_type, VALUE,
 };
+#[cfg(ruby_gte_3_1)]
+use rb_sys::{rb_cRefinement, rb_class_subclasses};
 
+#[cfg(any(ruby_gte_3_1, docsrs))]
+use crate::r_array::{RArray, TypedArray};
 use crate::{
     error::{protect, Error},
+    gc,
     into_value::{kw_splat, ArgList, IntoValue},
     module::Module,
     object::Object,
@@ -25,7 +35,7 @@ use crate::{
     typed_data::TypedData,
     value::{
         private::{self, ReprValue as _},
-        NonZeroValue, ReprValue, Value,
+        IntoId, NonZeroValue, OpaqueId, ReprValue, Value,
     },
     Ruby,
 };
@@ -68,6 +78,31 @@ impl RClass {
     pub(crate) unsafe fn from_rb_value_unchecked(val: VALUE) -> Self {
         Self(NonZeroValue::new_unchecked(Value::new(val)))
     }
+
+    /// Return a [`ClassKey`] identifying `self`, for looking up `self` in
+    /// a Rust collection.
+    ///
+    /// This does not prevent `self` being garbage collected, so keys that are
+    /// stored should be created with [`ClassKey::register`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, ClassKey, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let key = ClassKey::register(ruby.class_string());
+    ///     assert_eq!(ruby.str_new("example").class().as_key(), key);
+    ///     assert_ne!(ruby.class_symbol().as_key(), key);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    #[inline]
+    pub fn as_key(self) -> ClassKey {
+        ClassKey(self.as_rb_value())
+    }
 }
 
 impl fmt::Display for RClass {
@@ -110,6 +145,213 @@ impl TryConvert for RClass {
     }
 }
 
+/// An identity key for a Ruby class, for use in Rust collections.
+///
+/// Comparing [`RClass`]es with Ruby's `==` requires a method call, whereas
+/// `ClassKey` compares and hashes on the identity of the class, so it can be
+/// used as the key of a [`HashMap`](std::collections::HashMap), e.g. for
+/// dispatching on the class of an object.
+///
+/// A `ClassKey` can't be converted back to an [`RClass`], and so is [`Send`]
+/// and [`Sync`].
+///
+/// Use [`ClassKey::register`] to create keys that will be stored, and
+/// [`RClass::as_key`] to create keys that are only used for lookup.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use magnus::{prelude::*, ClassKey, Error, Ruby, Value};
+///
+/// fn example(ruby: &Ruby) -> Result<(), Error> {
+///     let mut handlers: HashMap<ClassKey, fn(Value) -> &'static str> = HashMap::new();
+///     handlers.insert(ClassKey::register(ruby.class_string()), |_| "string");
+///     handlers.insert(ClassKey::register(ruby.class_integer()), |_| "integer");
+///
+///     let val = ruby.eval::<Value>("42")?;
+///     let handler = handlers[&val.class().as_key()];
+///     assert_eq!(handler(val), "integer");
+///
+///     let val = ruby.eval::<Value>(":example")?;
+///     assert!(handlers.get(&val.class().as_key()).is_none());
+///
+///     Ok(())
+/// }
+/// # Ruby::init(example).unwrap()
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[repr(transparent)]
+pub struct ClassKey(VALUE);
+
+impl ClassKey {
+    /// Create a new `ClassKey` for `class`, registering `class` with Ruby's
+    /// garbage collector so that it will never be collected or moved.
+    ///
+    /// This ensures the key can't later match a different class allocated at
+    /// the same address. Classes are usually long lived, but this will leak
+    /// anonymous classes, so avoid calling this for an unbounded number of
+    /// classes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{ClassKey, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let key = ClassKey::register(ruby.class_array());
+    ///     assert_eq!(key, ruby.class_array().as_key());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn register(class: RClass) -> Self {
+        gc::register_mark_object(class);
+        class.as_key()
+    }
+}
+
+impl PartialEq<RClass> for ClassKey {
+    #[inline]
+    fn eq(&self, other: &RClass) -> bool {
+        self.0 == other.as_rb_value()
+    }
+}
+
+/// An opt-in cache of the results of [`ReprValue::respond_to`], per class.
+///
+/// Checking if many objects respond to a method, e.g. when serialising, can
+/// be costly. `RespondToCache` memoises the result for each class and method,
+/// so repeated checks for objects of the same class are a hash lookup.
+///
+/// Objects with a singleton class are always checked directly, as their
+/// methods may differ from others of the same class.
+///
+/// # Caveats
+///
+/// The cache is never invalidated automatically. Results will be stale if a
+/// method is defined or removed, or a module included, after the class was
+/// cached. Call [`clear`](RespondToCache::clear) after such changes, or avoid
+/// this cache where that is a concern.
+///
+/// `respond_to_missing?` implementations that depend on the state of the
+/// object, rather than its class, will give incorrect results.
+///
+/// Each class checked is registered with Ruby's garbage collector (see
+/// [`ClassKey::register`]) and so will never be collected.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{class::RespondToCache, Error, RArray, Ruby};
+///
+/// fn example(ruby: &Ruby) -> Result<(), Error> {
+///     let mut cache = RespondToCache::new();
+///     let vals: RArray = ruby.eval(r#"["a", "b", 1, :c]"#)?;
+///
+///     let mut count = 0;
+///     for val in vals {
+///         if cache.respond_to(val, "upcase", false)? {
+///             count += 1;
+///         }
+///     }
+///     assert_eq!(count, 3);
+///
+///     Ok(())
+/// }
+/// # Ruby::init(example).unwrap()
+/// ```
+#[derive(Debug, Default)]
+pub struct RespondToCache {
+    results: HashMap<(ClassKey, OpaqueId, bool), bool>,
+    registered: HashSet<ClassKey>,
+}
+
+impl RespondToCache {
+    /// Create a new, empty, `RespondToCache`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check if `val` responds to the given Ruby method, using the cached
+    /// result for `val`'s class if there is one.
+    ///
+    /// See [`ReprValue::respond_to`] for details of the arguments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{class::RespondToCache, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let mut cache = RespondToCache::new();
+    ///     let s = ruby.str_new("example");
+    ///     assert!(cache.respond_to(s, "to_str", false)?);
+    ///     assert!(!cache.respond_to(s, "puts", false)?);
+    ///     assert!(cache.respond_to(s, "puts", true)?);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn respond_to<T, M>(
+        &mut self,
+        val: T,
+        method: M,
+        include_private: bool,
+    ) -> Result<bool, Error>
+    where
+        T: ReprValue,
+        M: IntoId,
+    {
+        let handle = Ruby::get_with(val);
+        let id = method.into_id_with(&handle);
+        let class = val.class();
+        let is_singleton = unsafe {
+            class.r_basic_unchecked().as_ref().flags & ruby_fl_type::RUBY_FL_SINGLETON as VALUE != 0
+        };
+        if is_singleton {
+            return val.respond_to(id, include_private);
+        }
+        let key = (class.as_key(), OpaqueId::from(id), include_private);
+        if let Some(res) = self.results.get(&key) {
+            return Ok(*res);
+        }
+        let res = val.respond_to(id, include_private)?;
+        if self.registered.insert(key.0) {
+            ClassKey::register(class);
+        }
+        self.results.insert(key, res);
+        Ok(res)
+    }
+
+    /// Discard all cached results.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{class::RespondToCache, Error, Ruby, Value};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let mut cache = RespondToCache::new();
+    ///     let val = ruby.eval::<Value>("Example = Class.new; Example.new")?;
+    ///     assert!(!cache.respond_to(val, "example", false)?);
+    ///
+    ///     let _: Value = ruby.eval("class Example; def example; end; end")?;
+    ///     cache.clear();
+    ///     assert!(cache.respond_to(val, "example", false)?);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn clear(&mut self) {
+        self.results.clear();
+    }
+}
+
 /// Functions available on all types representing a Ruby class.
 pub trait Class: Module {
     /// The type representing an instance of the class `Self`.
@@ -265,7 +507,8 @@ pub trait Class: Module {
 
     /// Returns the parent class of `self`.
     ///
-    /// Returns `Err` if `self` can not have a parent class.
+    /// Returns `Ok(None)` if `self` has no parent class, i.e. is
+    /// `BasicObject`, and `Err` if `self` can not have a parent class.
     ///
     /// # Examples
     ///
@@ -273,9 +516,11 @@ pub trait Class: Module {
     /// use magnus::{prelude::*, Error, Ruby};
     ///
     /// fn example(ruby: &Ruby) -> Result<(), Error> {
-    ///     let klass = ruby.class_hash().superclass()?;
+    ///     let klass = ruby.class_hash().superclass()?.unwrap();
     ///     assert!(klass.equal(ruby.class_object())?);
     ///
+    ///     assert!(ruby.class_basic_object().superclass()?.is_none());
+    ///
     ///     Ok(())
     /// }
     /// # Ruby::init(example).unwrap()
@@ -285,17 +530,51 @@ pub trait Class: Module {
     /// use magnus::{prelude::*, Error, Ruby};
     ///
     /// fn example(ruby: &Ruby) -> Result<(), Error> {
-    ///     let klass = ruby.exception_exception().superclass()?;
+    ///     let klass = ruby.exception_exception().superclass()?.unwrap();
     ///     assert!(klass.equal(ruby.class_object())?);
     ///
     ///     Ok(())
     /// }
     /// # Ruby::init(example).unwrap()
     /// ```
-    fn superclass(self) -> Result<RClass, Error> {
+    fn superclass(self) -> Result<Option<RClass>, Error> {
+        protect(|| unsafe { Value::new(rb_class_superclass(self.as_rb_value())) })
+            .map(RClass::from_value)
+    }
+
+    /// Returns the direct subclasses of `self`.
+    ///
+    /// Singleton classes are not included. The order of the subclasses is
+    /// unspecified.
+    ///
+    /// Only available with Ruby 3.1 and later, which added `Class#subclasses`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, rb_assert, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let base = ruby.define_class("Base", ruby.class_object())?;
+    ///     let a = ruby.define_class("A", base)?;
+    ///     let b = ruby.define_class("B", base)?;
+    ///     let _ = ruby.define_class("C", a)?;
+    ///
+    ///     let subclasses = base.subclasses()?.to_r_array();
+    ///     rb_assert!(ruby, "subclasses.sort_by(&:name) == [A, B]", subclasses);
+    ///     assert!(b.subclasses()?.is_empty());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    #[cfg(any(ruby_gte_3_1, docsrs))]
+    #[cfg_attr(docsrs, doc(cfg(ruby_gte_3_1)))]
+    fn subclasses(self) -> Result<TypedArray<RClass>, Error> {
         protect(|| unsafe {
-            RClass::from_rb_value_unchecked(rb_class_superclass(self.as_rb_value()))
+            RArray::from_rb_value_unchecked(rb_class_subclasses(self.as_rb_value()))
         })
+        .and_then(RArray::typecheck)
     }
 
     /// Return the name of `self`.
@@ -598,6 +877,8 @@ impl Ruby {
 
     /// Return Ruby's `Binding` class.
     ///
+    /// See also the [`Binding`](crate::Binding) type.
+    ///
     /// # Examples
     ///
     /// ```
@@ -0,0 +1,65 @@
+//! Conversions between [`rust_decimal::Decimal`] and Ruby's `BigDecimal`.
+
+use rust_decimal::Decimal;
+
+use crate::{
+    class::RClass,
+    error::Error,
+    integer::Integer,
+    into_value::TryIntoValue,
+    module::Module,
+    r_string::RString,
+    try_convert::TryConvert,
+    value::{ReprValue, Value},
+    Ruby,
+};
+
+fn big_decimal_loaded(ruby: &Ruby) -> Result<bool, Error> {
+    ruby.class_object()
+        .funcall("const_defined?", ("BigDecimal",))
+}
+
+/// Converts to a Ruby `BigDecimal` via the decimal string representation.
+///
+/// `bigdecimal` will be required if it has not already been loaded. Errors if
+/// `bigdecimal` can not be loaded.
+#[cfg_attr(docsrs, doc(cfg(feature = "rust_decimal")))]
+impl TryIntoValue for Decimal {
+    fn try_into_value_with(self, ruby: &Ruby) -> Result<Value, Error> {
+        if !big_decimal_loaded(ruby)? {
+            ruby.require("bigdecimal")?;
+        }
+        // Go via the string representation, rather than a float, so the value
+        // is exact.
+        ruby.module_kernel()
+            .funcall("BigDecimal", (self.to_string(),))
+    }
+}
+
+/// Converts from a Ruby `BigDecimal` or `Integer`, via the decimal string
+/// representation.
+///
+/// Errors if the value can not be represented exactly as a `Decimal`, e.g. it
+/// has too many digits, or is `NaN` or infinite.
+#[cfg_attr(docsrs, doc(cfg(feature = "rust_decimal")))]
+impl TryConvert for Decimal {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        let ruby = Ruby::get_with(val);
+        let s: RString = if Integer::from_value(val).is_some() {
+            val.funcall("to_s", ())?
+        } else if big_decimal_loaded(&ruby)?
+            && val.is_kind_of(ruby.class_object().const_get::<_, RClass>("BigDecimal")?)
+        {
+            val.funcall("to_s", ("F",))?
+        } else {
+            return Err(Error::type_error("Decimal", val));
+        };
+        let s = s.to_string()?;
+        Decimal::from_str_exact(&s).map_err(|e| {
+            Error::new(
+                ruby.exception_range_error(),
+                format!("{} can not be converted to Decimal: {}", s, e),
+            )
+        })
+    }
+}
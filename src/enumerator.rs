@@ -3,8 +3,11 @@ use std::fmt;
 use rb_sys::VALUE;
 
 use crate::{
+    block::Proc,
+    class::RClass,
     error::Error,
-    into_value::IntoValue,
+    into_value::{ArgList, IntoValue},
+    module::Module,
     object::Object,
     try_convert::TryConvert,
     value::{
@@ -14,6 +17,61 @@ use crate::{
     Ruby,
 };
 
+/// # `Enumerator`
+///
+/// Functions that can be used to create Ruby `Enumerator`s.
+///
+/// See also the [`Enumerator`] type.
+impl Ruby {
+    /// Create a new `Enumerator` from a Rust closure.
+    ///
+    /// This is equivalent to `Enumerator.new { |y| ... }` in Ruby. `func` is
+    /// called with a [`Yielder`] each time the enumerator is iterated, and
+    /// should push values with [`Yielder::yield_value`] or
+    /// [`Yielder::yield_values`].
+    ///
+    /// Ruby takes care of suspending and resuming `func` (with a Fiber) when
+    /// the enumerator is iterated externally with `next`, and of raising
+    /// `StopIteration` once `func` returns.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{rb_assert, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let enumerator = ruby.enumerator_new(|_ruby, y| {
+    ///         let (mut a, mut b) = (0_u64, 1_u64);
+    ///         loop {
+    ///             y.yield_value::<_, ()>(a)?;
+    ///             (a, b) = (b, a + b);
+    ///         }
+    ///     })?;
+    ///
+    ///     rb_assert!(ruby, "enumerator.take(6) == [0, 1, 1, 2, 3, 5]", enumerator);
+    ///     rb_assert!(ruby, "enumerator.next == 0", enumerator);
+    ///     rb_assert!(ruby, "enumerator.next == 1", enumerator);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn enumerator_new<F>(&self, mut func: F) -> Result<Enumerator, Error>
+    where
+        F: 'static + Send + FnMut(&Ruby, Yielder) -> Result<(), Error>,
+    {
+        let block: Proc = self.proc_from_fn(move |ruby, args, _block| {
+            let yielder = args.first().copied().ok_or_else(|| {
+                Error::new(ruby.exception_arg_error(), "expected Enumerator::Yielder")
+            })?;
+            func(ruby, unsafe {
+                Yielder(NonZeroValue::new_unchecked(yielder))
+            })
+        });
+        self.class_enumerator().funcall_with_block("new", (), block)
+    }
+}
+
 /// Wrapper type for a Value known to be an instance of Ruby's Enumerator class.
 ///
 /// `Enumerator` implements [`Iterator`], however Rust's iterators are a pull
@@ -122,3 +180,139 @@ impl TryConvert for Enumerator {
         })
     }
 }
+
+/// Wrapper type for a Value known to be an instance of Ruby's
+/// `Enumerator::Yielder` class.
+///
+/// A `Yielder` is passed to the closure given to [`Ruby::enumerator_new`],
+/// and is used to push values to the consumer of the enumerator.
+///
+/// See the [`ReprValue`] and [`Object`] traits for additional methods
+/// available on this type.
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct Yielder(NonZeroValue);
+
+impl Yielder {
+    /// Return `Some(Yielder)` if `val` is an `Enumerator::Yielder`, `None`
+    /// otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby, Yielder};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let val = ruby.eval("Enumerator.new { |y| y << y }.next")?;
+    ///     assert!(Yielder::from_value(val).is_some());
+    ///     assert!(Yielder::from_value(ruby.eval("[]")?).is_none());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    #[inline]
+    pub fn from_value(val: Value) -> Option<Self> {
+        let yielder_class: RClass = Ruby::get_with(val)
+            .class_enumerator()
+            .const_get("Yielder")
+            .ok()?;
+        val.is_kind_of(yielder_class)
+            .then(|| unsafe { Self(NonZeroValue::new_unchecked(val)) })
+    }
+
+    /// Push `val` to the consumer of the enumerator.
+    ///
+    /// Returns the result of the consumer's block, this is generally `nil`
+    /// when the enumerator is iterated externally with `next`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{rb_assert, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let enumerator = ruby.enumerator_new(|_ruby, y| {
+    ///         for c in ["a", "b", "c"] {
+    ///             y.yield_value::<_, ()>(c)?;
+    ///         }
+    ///         Ok(())
+    ///     })?;
+    ///
+    ///     rb_assert!(ruby, r#"enumerator.to_a == ["a", "b", "c"]"#, enumerator);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn yield_value<T, U>(self, val: T) -> Result<U, Error>
+    where
+        T: IntoValue,
+        U: TryConvert,
+    {
+        self.funcall("yield", (val,))
+    }
+
+    /// Push multiple values to the consumer of the enumerator.
+    ///
+    /// The values are passed to the consumer's block as separate arguments.
+    ///
+    /// Returns the result of the consumer's block.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{rb_assert, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let enumerator = ruby.enumerator_new(|_ruby, y| {
+    ///         y.yield_values::<_, ()>(("a", 1))?;
+    ///         y.yield_values::<_, ()>(("b", 2))?;
+    ///         Ok(())
+    ///     })?;
+    ///
+    ///     rb_assert!(ruby, r#"enumerator.to_h == {"a" => 1, "b" => 2}"#, enumerator);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn yield_values<T, U>(self, vals: T) -> Result<U, Error>
+    where
+        T: ArgList,
+        U: TryConvert,
+    {
+        self.funcall("yield", vals)
+    }
+}
+
+impl fmt::Display for Yielder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", unsafe { self.to_s_infallible() })
+    }
+}
+
+impl fmt::Debug for Yielder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", ReprValue::inspect(*self))
+    }
+}
+
+impl IntoValue for Yielder {
+    #[inline]
+    fn into_value_with(self, _: &Ruby) -> Value {
+        self.0.get()
+    }
+}
+
+impl Object for Yielder {}
+
+unsafe impl private::ReprValue for Yielder {}
+
+impl ReprValue for Yielder {}
+
+impl TryConvert for Yielder {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        Self::from_value(val).ok_or_else(|| Error::type_error("Enumerator::Yielder", val))
+    }
+}
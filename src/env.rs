@@ -0,0 +1,132 @@
+use crate::{
+    error::Error,
+    module::Module,
+    r_hash::{ForEach, RHash},
+    r_string::RString,
+    value::{ReprValue, Value},
+    Ruby,
+};
+
+/// # `ENV`
+///
+/// Functions for working with environment variables, as seen by Ruby's `ENV`.
+///
+/// These go via `ENV` rather than [`std::env`], so see changes made from
+/// Ruby code, and behave as Ruby code would expect. Values are returned as
+/// frozen strings in the locale encoding, as with `ENV`.
+impl Ruby {
+    fn env(&self) -> Result<Value, Error> {
+        self.class_object().const_get("ENV")
+    }
+
+    /// Return the value of the environment variable `key`, or `None` if it is
+    /// not set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     ruby.env_set("MAGNUS_EXAMPLE", "hello")?;
+    ///     let val = ruby.env_get("MAGNUS_EXAMPLE")?.unwrap();
+    ///     assert_eq!(val.to_string()?, "hello");
+    ///     assert!(ruby.env_get("MAGNUS_UNSET_EXAMPLE")?.is_none());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn env_get(&self, key: &str) -> Result<Option<RString>, Error> {
+        self.env()?.funcall("[]", (key,))
+    }
+
+    /// Set the environment variable `key` to `value`.
+    ///
+    /// Errors if `key` or `value` contain a null byte, or `key` contains `=`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{rb_assert, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     ruby.env_set("MAGNUS_EXAMPLE", "hello")?;
+    ///     rb_assert!(ruby, r#"ENV["MAGNUS_EXAMPLE"] == "hello""#);
+    ///
+    ///     assert!(ruby.env_set("MAGNUS=EXAMPLE", "hello").is_err());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn env_set(&self, key: &str, value: &str) -> Result<(), Error> {
+        self.env()?.funcall::<_, _, Value>("[]=", (key, value))?;
+        Ok(())
+    }
+
+    /// Remove the environment variable `key`, returning its previous value,
+    /// if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     ruby.env_set("MAGNUS_EXAMPLE", "hello")?;
+    ///     let old = ruby.env_delete("MAGNUS_EXAMPLE")?.unwrap();
+    ///     assert_eq!(old.to_string()?, "hello");
+    ///     assert!(ruby.env_get("MAGNUS_EXAMPLE")?.is_none());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn env_delete(&self, key: &str) -> Result<Option<RString>, Error> {
+        self.env()?.funcall("delete", (key,))
+    }
+
+    /// Run `func` for each environment variable name and value.
+    ///
+    /// `func` is called with a snapshot of the environment, so it is safe to
+    /// modify the environment from `func`. Returning [`ForEach::Delete`] from
+    /// `func` removes the variable from the environment.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{r_hash::ForEach, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     ruby.env_set("MAGNUS_EXAMPLE", "hello")?;
+    ///     let mut found = false;
+    ///     ruby.env_foreach(|key, value| {
+    ///         if key.to_string()? == "MAGNUS_EXAMPLE" {
+    ///             found = value.to_string()? == "hello";
+    ///             return Ok(ForEach::Stop);
+    ///         }
+    ///         Ok(ForEach::Continue)
+    ///     })?;
+    ///     assert!(found);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn env_foreach<F>(&self, mut func: F) -> Result<(), Error>
+    where
+        F: FnMut(RString, RString) -> Result<ForEach, Error>,
+    {
+        let env = self.env()?;
+        let hash: RHash = env.funcall("to_h", ())?;
+        hash.foreach(|key: RString, value: RString| {
+            let res = func(key, value)?;
+            if let ForEach::Delete = res {
+                env.funcall::<_, _, Value>("delete", (key,))?;
+                return Ok(ForEach::Continue);
+            }
+            Ok(res)
+        })
+    }
+}
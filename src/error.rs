@@ -12,8 +12,10 @@ use rb_sys::{
 use crate::{
     class::Class,
     exception::Exception,
+    integer::Integer,
     into_value::IntoValue,
     module::Module,
+    r_array::RArray,
     value::{private::ReprValue as _, ReprValue, Value},
     ExceptionClass, Ruby,
 };
@@ -88,6 +90,192 @@ impl Ruby {
         let s = CString::new(s).unwrap();
         unsafe { rb_warning(s.as_ptr()) };
     }
+
+    /// Emit `s` as a warning with Ruby's `Kernel#warn`.
+    ///
+    /// Unlike [`Ruby::warning`] this outputs the warning unless warnings have
+    /// been disabled (e.g. with `-W0`), and not only in verbose mode. The
+    /// warning is emitted through Ruby's `Warning` module, so can be silenced
+    /// or captured by overriding `Warning.warn`.
+    ///
+    /// Errors if `Warning.warn` raises an exception, for example when a test
+    /// suite is configured to turn warnings into errors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     ruby.warn("something unexpected happened")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn warn(&self, s: &str) -> Result<(), Error> {
+        let _: Value = self.module_kernel().funcall("warn", (s,))?;
+        Ok(())
+    }
+
+    /// Emit `s` as a warning in `category` with Ruby's `Kernel#warn`.
+    ///
+    /// As [`Ruby::warn`], but the warning is only output if `category` is
+    /// enabled, e.g. deprecation warnings are only output when
+    /// `Warning[:deprecated]` is `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{error::WarningCategory, prelude::*, Error, Ruby, Value};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let _: Value = ruby.eval(
+    ///         r#"
+    ///             $warnings = []
+    ///             Warning[:deprecated] = true
+    ///             def Warning.warn(msg, category: nil)
+    ///               $warnings << [msg, category]
+    ///             end
+    ///         "#,
+    ///     )?;
+    ///
+    ///     ruby.warn_category("`foo` is deprecated", WarningCategory::Deprecated)?;
+    ///
+    ///     let warnings: Value = ruby.eval("$warnings")?;
+    ///     magnus::rb_assert!(
+    ///         ruby,
+    ///         r#"warnings == [["`foo` is deprecated\n", :deprecated]]"#,
+    ///         warnings
+    ///     );
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    #[cfg(any(ruby_gte_3_0, docsrs))]
+    #[cfg_attr(docsrs, doc(cfg(ruby_gte_3_0)))]
+    pub fn warn_category(&self, s: &str, category: WarningCategory) -> Result<(), Error> {
+        let category = self.to_symbol(category.name());
+        let _: Value = self
+            .module_kernel()
+            .funcall("warn", (s, crate::kwargs!(self, "category" => category)))?;
+        Ok(())
+    }
+
+    /// Return the current Ruby call stack as an Array of Strings, as with
+    /// Ruby's `Kernel#caller`.
+    ///
+    /// `start` is the number of frames to omit from the top of the stack, and
+    /// `length` limits the number of frames returned, or all remaining frames
+    /// are returned if `None`. Returns an empty Array if `start` is greater
+    /// than the size of the stack.
+    ///
+    /// This is useful for logging, or for warnings that should point at the
+    /// Ruby code calling a method. See also [`Ruby::caller_locations`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{function, rb_assert, Error, RArray, Ruby};
+    ///
+    /// fn stack(ruby: &Ruby) -> Result<RArray, Error> {
+    ///     ruby.caller(0, None)
+    /// }
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     ruby.define_global_function("stack", function!(stack, 0));
+    ///
+    ///     rb_assert!(ruby, "stack.all?(String)");
+    ///     rb_assert!(
+    ///         ruby,
+    ///         r#"
+    ///             def outer
+    ///               stack
+    ///             end
+    ///             outer.any? { |frame| frame.include?("outer") }
+    ///         "#
+    ///     );
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn caller(&self, start: usize, length: Option<usize>) -> Result<RArray, Error> {
+        let res: Option<RArray> = match length {
+            Some(length) => self.module_kernel().funcall("caller", (start, length))?,
+            None => self.module_kernel().funcall("caller", (start,))?,
+        };
+        Ok(res.unwrap_or_else(|| self.ary_new()))
+    }
+
+    /// Return the current Ruby call stack as an Array of
+    /// `Thread::Backtrace::Location` objects, as with Ruby's
+    /// `Kernel#caller_locations`.
+    ///
+    /// As [`Ruby::caller`], but the returned objects give structured access
+    /// to each frame's `path`, `lineno`, and `label`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{function, rb_assert, Error, RArray, Ruby};
+    ///
+    /// fn locations(ruby: &Ruby) -> Result<RArray, Error> {
+    ///     ruby.caller_locations(0, None)
+    /// }
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     ruby.define_global_function("locations", function!(locations, 0));
+    ///
+    ///     rb_assert!(
+    ///         ruby,
+    ///         r#"
+    ///             def outer
+    ///               locations
+    ///             end
+    ///             outer.any? { |loc| loc.label.end_with?("outer") }
+    ///         "#
+    ///     );
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn caller_locations(&self, start: usize, length: Option<usize>) -> Result<RArray, Error> {
+        let res: Option<RArray> = match length {
+            Some(length) => self
+                .module_kernel()
+                .funcall("caller_locations", (start, length))?,
+            None => self.module_kernel().funcall("caller_locations", (start,))?,
+        };
+        Ok(res.unwrap_or_else(|| self.ary_new()))
+    }
+}
+
+/// Categories of warnings, for [`Ruby::warn_category`].
+#[cfg(any(ruby_gte_3_0, docsrs))]
+#[cfg_attr(docsrs, doc(cfg(ruby_gte_3_0)))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum WarningCategory {
+    /// Deprecation warnings, `:deprecated`.
+    Deprecated,
+    /// Warnings for experimental features, `:experimental`.
+    Experimental,
+    /// Warnings for performance issues, `:performance`. Only supported by
+    /// Ruby 3.3 and later, earlier versions will return an error.
+    Performance,
+}
+
+#[cfg(any(ruby_gte_3_0, docsrs))]
+impl WarningCategory {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Deprecated => "deprecated",
+            Self::Experimental => "experimental",
+            Self::Performance => "performance",
+        }
+    }
 }
 
 /// Shorthand for `std::result::Result<T, magnus::Error>`.
@@ -115,6 +303,12 @@ impl Error {
     /// Create a new `Error` that can be raised as a Ruby `Exception` with
     /// `msg`.
     ///
+    /// The Ruby exception isn't created until the error is returned to Ruby,
+    /// and `msg` is a [`Cow`], so a `&'static str` message doesn't allocate.
+    /// There is no variant taking a closure to format the message lazily, as
+    /// the closure could capture Ruby values, which would then be hidden from
+    /// the garbage collector on the heap.
+    ///
     /// # Examples
     ///
     /// ```
@@ -152,6 +346,196 @@ impl Error {
         Self(ErrorType::Error(class, msg.into()))
     }
 
+    /// Create a new error with the `cause` of the Ruby exception set to
+    /// `cause`.
+    ///
+    /// This is useful when translating a lower level error into a more
+    /// specific one, as the original error is preserved and will be shown in
+    /// Ruby's backtrace output, and is available from Ruby with
+    /// `Exception#cause`.
+    ///
+    /// Unlike [`Error::new`] the Ruby exception is created immediately. If
+    /// `cause` is not a Ruby exception (e.g. it was created with
+    /// [`Ruby::iter_break_value`]) it is ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{function, prelude::*, Error, Exception, Ruby};
+    ///
+    /// fn parse(ruby: &Ruby, s: String) -> Result<i64, Error> {
+    ///     ruby.eval::<i64>(&format!("Integer({:?})", s))
+    ///         .map_err(|e| Error::with_cause(ruby.exception_runtime_error(), "invalid config", e))
+    /// }
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     ruby.define_global_function("parse", function!(parse, 1));
+    ///
+    ///     let error: Exception = ruby.eval(
+    ///         r#"
+    ///             begin
+    ///               parse("ten")
+    ///             rescue => e
+    ///               e
+    ///             end
+    ///         "#,
+    ///     )?;
+    ///     assert!(error.is_kind_of(ruby.exception_runtime_error()));
+    ///     let cause: Exception = error.funcall("cause", ())?;
+    ///     assert!(cause.is_kind_of(ruby.exception_arg_error()));
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn with_cause<T>(class: ExceptionClass, msg: T, cause: Error) -> Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        let cause = match cause.0 {
+            ErrorType::Jump(_) | ErrorType::UnavaliableError(_) => return Self::new(class, msg),
+            _ => cause.into_exception(),
+        };
+        let handle = Ruby::get_with(class);
+        let exception = Self::new(class, msg).into_exception();
+        // Ruby sets the cause when the exception is raised, raise it here and
+        // keep the result, the cause will be preserved when re-raised.
+        match handle.module_kernel().funcall::<_, _, Value>(
+            "raise",
+            (exception, crate::kwargs!(&handle, "cause" => cause)),
+        ) {
+            Ok(_) => unreachable!("raise returned"),
+            Err(e) => e,
+        }
+    }
+
+    /// Create a new `TypeError` for when `got` could not be converted to
+    /// `expected`.
+    ///
+    /// The message matches the wording Ruby uses for failed implicit
+    /// conversions, e.g. `no implicit conversion of Array into Integer`.
+    /// `nil`, `true`, and `false` are described by value rather than class,
+    /// as Ruby does.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let err = Error::type_error("Integer", ruby.ary_new());
+    ///     assert!(err.is_kind_of(ruby.exception_type_error()));
+    ///     assert_eq!(err.to_string(), "TypeError: no implicit conversion of Array into Integer");
+    ///
+    ///     let err = Error::type_error("String", ruby.qnil());
+    ///     assert_eq!(err.to_string(), "TypeError: no implicit conversion of nil into String");
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn type_error<T>(expected: &str, got: T) -> Self
+    where
+        T: ReprValue,
+    {
+        let handle = Ruby::get_with(got);
+        let desc = if got.is_nil() {
+            Cow::Borrowed("nil")
+        } else if got.as_value().is_true() {
+            Cow::Borrowed("true")
+        } else if got.as_value().is_false() {
+            Cow::Borrowed("false")
+        } else {
+            Cow::Owned(got.class_name())
+        };
+        Self::new(
+            handle.exception_type_error(),
+            format!("no implicit conversion of {} into {}", desc, expected),
+        )
+    }
+
+    /// Create a new `ArgumentError` for when `got` is the right type, but not
+    /// a valid value for `expected`.
+    ///
+    /// The message matches the wording Ruby uses for invalid arguments, e.g.
+    /// `invalid value for Integer(): "abc"`, with `got` shown as by
+    /// `inspect`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let err = Error::arg_error("Integer()", ruby.str_new("abc"));
+    ///     assert!(err.is_kind_of(ruby.exception_arg_error()));
+    ///     assert_eq!(err.to_string(), r#"ArgumentError: invalid value for Integer(): "abc""#);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn arg_error<T>(expected: &str, got: T) -> Self
+    where
+        T: ReprValue,
+    {
+        let handle = Ruby::get_with(got);
+        Self::new(
+            handle.exception_arg_error(),
+            format!("invalid value for {}: {}", expected, got.inspect()),
+        )
+    }
+
+    /// Create a new `RangeError` for when `got` is out of range for
+    /// `expected`.
+    ///
+    /// The message matches the wording Ruby uses for out of range values. For
+    /// Integers this is e.g. ``integer 300 too big to convert to `u8'``,
+    /// otherwise e.g. `float Infinity out of range of i64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let err = Error::range_error("u8", ruby.integer_from_i64(300));
+    ///     assert!(err.is_kind_of(ruby.exception_range_error()));
+    ///     assert_eq!(err.to_string(), "RangeError: integer 300 too big to convert to `u8'");
+    ///
+    ///     let err = Error::range_error("u8", ruby.integer_from_i64(-1));
+    ///     assert_eq!(err.to_string(), "RangeError: integer -1 too small to convert to `u8'");
+    ///
+    ///     let err = Error::range_error("i64", ruby.float_from_f64(f64::INFINITY));
+    ///     assert_eq!(err.to_string(), "RangeError: float Infinity out of range of i64");
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn range_error<T>(expected: &str, got: T) -> Self
+    where
+        T: ReprValue,
+    {
+        let handle = Ruby::get_with(got);
+        let msg = if let Some(i) = Integer::from_value(got.as_value()) {
+            format!(
+                "integer {} too {} to convert to `{}'",
+                got.inspect(),
+                if i.is_negative() { "small" } else { "big" },
+                expected
+            )
+        } else {
+            format!(
+                "{} {} out of range of {}",
+                got.class_name().to_lowercase(),
+                got.inspect(),
+                expected
+            )
+        };
+        Self::new(handle.exception_range_error(), msg)
+    }
+
     pub(crate) fn from_tag(tag: Tag) -> Self {
         Self(ErrorType::Jump(tag))
     }
@@ -254,21 +638,93 @@ impl Error {
     /// # Panics
     ///
     /// Panics if called on an `Error::Jump` and `Error::UnavailableError`.
-    fn exception(self) -> Exception {
-        let handle = unsafe { Ruby::get_unchecked() };
-        match self.0 {
-            ErrorType::Jump(_) => panic!("Error::exception() called on {}", self),
+    /// Returns the Ruby exception `self` represents, if there is one.
+    ///
+    /// Errors created in Rust with [`Error::new`] will have an exception
+    /// instantiated from their class and message. Errors representing a
+    /// non-local jump (`break`, `throw`, etc) and errors raised outside of
+    /// Ruby will return `None`.
+    ///
+    /// This allows for inspecting details of the exception, such as its
+    /// message, backtrace, or instance variables.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, Error, Exception, Ruby, Value};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let err = ruby
+    ///         .eval::<Value>(
+    ///             "
+    ///       class ExampleError < StandardError
+    ///         def initialize(msg, code)
+    ///           super(msg)
+    ///           @code = code
+    ///         end
+    ///       end
+    ///       raise ExampleError.new(\"failed\", 42)
+    ///     ",
+    ///         )
+    ///         .unwrap_err();
+    ///
+    ///     let exception: Exception = err.exception().unwrap();
+    ///     assert!(exception.is_kind_of(ruby.exception_standard_error()));
+    ///     assert_eq!(exception.message()?.to_string()?, "failed");
+    ///     assert_eq!(exception.ivar_get::<_, i64>("@code")?, 42);
+    ///
+    ///     let err = Error::new(ruby.exception_arg_error(), "bad argument");
+    ///     let exception = err.exception().unwrap();
+    ///     assert!(exception.is_kind_of(ruby.exception_arg_error()));
+    ///     assert_eq!(exception.message()?.to_string()?, "bad argument");
+    ///
+    ///     assert!(Error::iter_break(ruby.qnil()).exception().is_none());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn exception(&self) -> Option<Exception> {
+        match &self.0 {
+            ErrorType::Jump(_) | ErrorType::UnavaliableError(_) => None,
             ErrorType::Error(class, msg) => {
+                let handle = Ruby::get_with(*class);
                 match class.new_instance((handle.str_new(msg.as_ref()),)) {
-                    Ok(e) | Err(Error(ErrorType::Exception(e))) => e,
+                    Ok(e) | Err(Error(ErrorType::Exception(e))) => Some(e),
                     Err(err) => unreachable!("*very* unexpected error: {}", err),
                 }
             }
-            ErrorType::Exception(e) => e,
-            ErrorType::UnavaliableError(_) => panic!("Error::exception() called on {}", self),
+            ErrorType::Exception(e) => Some(*e),
+        }
+    }
+
+    fn into_exception(self) -> Exception {
+        match self.exception() {
+            Some(e) => e,
+            None => panic!("Error::exception() called on {}", self),
         }
     }
 
+    /// Prefix the message of `self` with `segment` of the path to the value
+    /// that failed to convert. See [`try_convert::with_path`].
+    ///
+    /// [`try_convert::with_path`]: crate::try_convert::with_path
+    pub(crate) fn with_path_segment<T>(self, segment: T) -> Self
+    where
+        T: fmt::Display,
+    {
+        let (class, msg) = match self.0 {
+            ErrorType::Error(class, msg) => (class, msg.into_owned()),
+            ErrorType::Exception(e) => (e.exception_class(), e.to_string()),
+            _ => return self,
+        };
+        let msg = match msg.strip_prefix("at ") {
+            Some(rest) if rest.starts_with('[') => format!("at {}{}", segment, rest),
+            _ => format!("at {}: {}", segment, msg),
+        };
+        Self::new(class, msg)
+    }
+
     /// Returns the [`ErrorType`] for self.
     pub fn error_type(&self) -> &ErrorType {
         &self.0
@@ -320,6 +776,43 @@ impl fmt::Display for Error {
     }
 }
 
+/// Create an `Error` from an existing exception instance.
+///
+/// When the `Error` is returned to Ruby this exact object is raised, it is not
+/// re-created from its class and message, so any instance variables or other
+/// state set on the exception are preserved.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{function, prelude::*, rb_assert, Error, Exception, Ruby};
+///
+/// fn fail(ruby: &Ruby) -> Result<(), Error> {
+///     let exc: Exception = ruby
+///         .exception_runtime_error()
+///         .new_instance(("request failed",))?;
+///     exc.ivar_set("@status", 503)?;
+///     Err(exc.into())
+/// }
+///
+/// fn example(ruby: &Ruby) -> Result<(), Error> {
+///     ruby.define_global_function("fail", function!(fail, 0));
+///
+///     rb_assert!(
+///         ruby,
+///         r#"
+///             begin
+///               fail
+///             rescue => e
+///               e.message == "request failed" && e.instance_variable_get(:@status) == 503
+///             end
+///         "#
+///     );
+///
+///     Ok(())
+/// }
+/// # Ruby::init(example).unwrap()
+/// ```
 impl From<Exception> for Error {
     fn from(val: Exception) -> Self {
         Self(ErrorType::Exception(val))
@@ -485,7 +978,7 @@ where
     }
 }
 
-pub(crate) fn ensure<F1, F2, T>(func: F1, ensure: F2) -> T
+pub(crate) fn with_ensure<F1, F2, T>(func: F1, ensure: F2) -> T
 where
     F1: FnOnce() -> T,
     F2: FnOnce(),
@@ -531,7 +1024,7 @@ pub(crate) fn raise(e: Error) -> ! {
     match e.0 {
         ErrorType::Jump(tag) => tag.resume(),
         _ => {
-            unsafe { rb_exc_raise(e.exception().as_rb_value()) }
+            unsafe { rb_exc_raise(e.into_exception().as_rb_value()) }
             // friendly reminder: we really never get here, and as such won't
             // drop any values still in scope, make sure everything has been
             // consumed/dropped
@@ -550,6 +1043,51 @@ pub(crate) fn bug_from_panic(e: Box<dyn Any + Send + 'static>, or: &str) -> ! {
     bug(&msg)
 }
 
+/// Returns `Err` with the error returned by `f` if `cond` is `false`.
+///
+/// The error is only created if `cond` is `false`, so any formatting of the
+/// message is skipped when validation passes. For checking the number of
+/// arguments see [`Ruby::check_arity`], and [`Ruby::check_arg`] for the
+/// common case of an `ArgumentError`.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{error, function, Error, Ruby};
+///
+/// fn withdraw(ruby: &Ruby, balance: u64, amount: u64) -> Result<u64, Error> {
+///     error::ensure(amount <= balance, || {
+///         Error::new(
+///             ruby.exception_range_error(),
+///             format!("can't withdraw {} from {}", amount, balance),
+///         )
+///     })?;
+///     Ok(balance - amount)
+/// }
+///
+/// fn example(ruby: &Ruby) -> Result<(), Error> {
+///     ruby.define_global_function("withdraw", function!(withdraw, 2));
+///
+///     assert_eq!(ruby.eval::<u64>("withdraw(10, 4)")?, 6);
+///     let err = ruby.eval::<u64>("withdraw(4, 10)").unwrap_err();
+///     assert!(err.is_kind_of(ruby.exception_range_error()));
+///
+///     Ok(())
+/// }
+/// # Ruby::init(example).unwrap()
+/// ```
+#[inline]
+pub fn ensure<F>(cond: bool, f: F) -> Result<()>
+where
+    F: FnOnce() -> Error,
+{
+    if cond {
+        Ok(())
+    } else {
+        Err(f())
+    }
+}
+
 /// Immediately terminate the process, printing Ruby internal state for
 /// debugging.
 pub fn bug(s: &str) -> ! {
@@ -23,6 +23,7 @@ use crate::{
     module::Module,
     object::Object,
     r_array::RArray,
+    r_string::RString,
     try_convert::TryConvert,
     value::{
         private::{self, ReprValue as _},
@@ -34,7 +35,8 @@ use crate::{
 /// Wrapper type for a Value known to be an instance of Ruby's Exception class.
 ///
 /// See the [`ReprValue`] and [`Object`] traits for additional methods
-/// available on this type.
+/// available on this type, such as [`ReprValue::class_name`] to get the name
+/// of the exception's class.
 #[derive(Clone, Copy)]
 #[repr(transparent)]
 pub struct Exception(NonZeroValue);
@@ -85,6 +87,75 @@ impl Exception {
     pub fn exception_class(self) -> ExceptionClass {
         unsafe { ExceptionClass::from_rb_value_unchecked(self.class().as_rb_value()) }
     }
+
+    /// Returns the message of `self`.
+    ///
+    /// This calls the Ruby method `message`, which may be overridden by
+    /// exception subclasses, and so can fail.
+    ///
+    /// See also [`Error::exception`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Exception, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let e: Exception = ruby.eval(r#"StandardError.new("example")"#)?;
+    ///     assert_eq!(e.message()?.to_string()?, "example");
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn message(self) -> Result<RString, Error> {
+        self.funcall("message", ())
+    }
+
+    /// Returns the formatted message of `self`, as Ruby would print it for an
+    /// uncaught exception, including the backtrace and any causes.
+    ///
+    /// If `highlight` is `true` the message will include ANSI escape
+    /// sequences for bold/underlined text, pass `false` when the output is
+    /// destined for a log file or other non-terminal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby, Value};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let err = ruby
+    ///         .eval::<Value>(
+    ///             r#"
+    ///       begin
+    ///         raise ArgumentError, "inner"
+    ///       rescue
+    ///         raise "outer"
+    ///       end
+    ///     "#,
+    ///         )
+    ///         .unwrap_err();
+    ///     let e = err.exception().unwrap();
+    ///
+    ///     let msg = e.full_message(false)?.to_string()?;
+    ///     assert!(msg.contains("outer (RuntimeError)"));
+    ///     assert!(msg.contains("inner (ArgumentError)"));
+    ///     assert!(!msg.contains('\x1b'));
+    ///
+    ///     assert!(e.full_message(true)?.to_string()?.contains('\x1b'));
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn full_message(self, highlight: bool) -> Result<RString, Error> {
+        let handle = Ruby::get_with(self);
+        self.funcall(
+            "full_message",
+            (crate::kwargs!(&handle, "highlight" => highlight),),
+        )
+    }
 }
 
 impl fmt::Display for Exception {
@@ -118,6 +189,8 @@ impl IntoValue for Exception {
     }
 }
 
+impl Object for Exception {}
+
 unsafe impl private::ReprValue for Exception {}
 
 impl ReprValue for Exception {}
@@ -143,8 +216,50 @@ impl TryConvert for Exception {
 
 /// A Value known to be an instance of Class and subclass of Exception.
 ///
+/// Instances of an `ExceptionClass` can be created with
+/// [`Class::new_instance`], which returns an [`Exception`]. The arguments
+/// are passed to the exception class's `initialize` method, so custom
+/// exceptions can take arguments other than a message. An [`Exception`] can
+/// be converted to an [`Error`] to be raised.
+///
+/// As `ExceptionClass` can only be created from a class inheriting from
+/// `Exception` (e.g. with [`TryConvert`] or [`Ruby::define_error`]), it
+/// isn't possible to attempt to raise a class that is not an exception.
+///
 /// See the [`ReprValue`] and [`Object`] traits for additional methods
 /// available on this type.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{prelude::*, Error, Exception, ExceptionClass, Ruby};
+///
+/// fn example(ruby: &Ruby) -> Result<(), Error> {
+///     let class: ExceptionClass = ruby.eval(
+///         r#"
+///             class HttpError < StandardError
+///               attr_reader :status
+///
+///               def initialize(status, msg)
+///                 super(msg)
+///                 @status = status
+///               end
+///             end
+///             HttpError
+///         "#,
+///     )?;
+///
+///     let exc: Exception = class.new_instance((404, "not found"))?;
+///     assert_eq!(exc.funcall::<_, _, i64>("status", ())?, 404);
+///     assert_eq!(exc.funcall::<_, _, String>("message", ())?, "not found");
+///     let _: Error = exc.into();
+///
+///     assert!(ruby.eval::<ExceptionClass>("Object").is_err());
+///
+///     Ok(())
+/// }
+/// # Ruby::init(example).unwrap()
+/// ```
 #[derive(Clone, Copy)]
 #[repr(transparent)]
 pub struct ExceptionClass(NonZeroValue);
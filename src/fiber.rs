@@ -266,6 +266,12 @@ impl Ruby {
 
 /// Wrapper type for a Value known to be an instance of Ruby's Fiber class.
 ///
+/// A fiber belongs to the Ruby thread that created it, and can only be
+/// resumed, transferred to, or raised in from that thread. Attempting to
+/// resume a fiber from a different thread results in a `FiberError`. Each
+/// fiber also has its own stack, so Rust code running in one fiber must not
+/// hold references to the stack of another.
+///
 /// See the [`ReprValue`] and [`Object`] traits for additional methods
 /// available on this type. See [`Ruby`](Ruby#fiber) for methods to create a
 /// `Fiber` and [`Ruby::fiber_yield`].
@@ -12,6 +12,7 @@ use crate::{
     into_value::IntoValue,
     numeric::Numeric,
     r_rational::RRational,
+    r_string::RString,
     try_convert::TryConvert,
     value::{
         private::{self, ReprValue as _},
@@ -202,6 +203,156 @@ impl Float {
     pub fn rationalize(self) -> RRational {
         unsafe { RRational::from_rb_value_unchecked(rb_flt_rationalize(self.as_rb_value())) }
     }
+
+    /// Returns whether `self` is zero (either `0.0` or `-0.0`).
+    ///
+    /// This does not call any Ruby methods.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     assert!(ruby.float_from_f64(0.0).is_zero());
+    ///     assert!(ruby.float_from_f64(-0.0).is_zero());
+    ///     assert!(!ruby.float_from_f64(0.1).is_zero());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn is_zero(self) -> bool {
+        self.to_f64() == 0.0
+    }
+
+    /// Returns whether `self` is greater than zero.
+    ///
+    /// As with Ruby's `Float#positive?`, this is `false` for `NaN`.
+    ///
+    /// This does not call any Ruby methods.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     assert!(ruby.float_from_f64(0.1).is_positive());
+    ///     assert!(ruby.float_from_f64(f64::INFINITY).is_positive());
+    ///     assert!(!ruby.float_from_f64(0.0).is_positive());
+    ///     assert!(!ruby.float_from_f64(f64::NAN).is_positive());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn is_positive(self) -> bool {
+        self.to_f64() > 0.0
+    }
+
+    /// Returns whether `self` is less than zero.
+    ///
+    /// As with Ruby's `Float#negative?`, this is `false` for `-0.0` and `NaN`.
+    ///
+    /// This does not call any Ruby methods.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     assert!(ruby.float_from_f64(-0.1).is_negative());
+    ///     assert!(!ruby.float_from_f64(-0.0).is_negative());
+    ///     assert!(!ruby.float_from_f64(f64::NAN).is_negative());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn is_negative(self) -> bool {
+        self.to_f64() < 0.0
+    }
+
+    /// Returns `self` formatted as a Ruby string, exactly as Ruby's
+    /// `Float#to_s`.
+    ///
+    /// The shortest representation that round-trips is used, in scientific
+    /// notation if the exponent is less than -4 or greater than 15. Special
+    /// values are formatted as `Infinity`, `-Infinity`, and `NaN`.
+    ///
+    /// This does not call any Ruby methods, so is unaffected by `Float#to_s`
+    /// being redefined.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     assert_eq!(ruby.float_from_f64(1.0).to_r_string().to_string()?, "1.0");
+    ///     assert_eq!(ruby.float_from_f64(0.1).to_r_string().to_string()?, "0.1");
+    ///     assert_eq!(ruby.float_from_f64(1e20).to_r_string().to_string()?, "1.0e+20");
+    ///     assert_eq!(ruby.float_from_f64(-1.5e-7).to_r_string().to_string()?, "-1.5e-07");
+    ///     assert_eq!(
+    ///         ruby.float_from_f64(f64::INFINITY).to_r_string().to_string()?,
+    ///         "Infinity"
+    ///     );
+    ///     assert_eq!(ruby.float_from_f64(f64::NAN).to_r_string().to_string()?, "NaN");
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn to_r_string(self) -> RString {
+        let ruby = Ruby::get_with(self);
+        ruby.enc_str_new(format_float(self.to_f64()), ruby.usascii_encoding())
+    }
+}
+
+// Follows the implementation of `flo_to_s` in Ruby's numeric.c.
+fn format_float(value: f64) -> String {
+    if value.is_nan() {
+        return String::from("NaN");
+    }
+    if value.is_infinite() {
+        return String::from(if value > 0.0 { "Infinity" } else { "-Infinity" });
+    }
+
+    // Rust's `{:e}` formatting gives the shortest digits that round-trip
+    let sci = format!("{:e}", value.abs());
+    let (mantissa, exp) = sci.split_once('e').unwrap();
+    let digits = mantissa.replace('.', "");
+    // position of the decimal point relative to the start of `digits`
+    let decpt = exp.parse::<i32>().unwrap() + 1;
+
+    let mut s = String::with_capacity(digits.len() + 8);
+    if value.is_sign_negative() {
+        s.push('-');
+    }
+    if decpt > 0 && decpt <= f64::DIGITS as i32 + 1 {
+        let decpt = decpt as usize;
+        if digits.len() <= decpt {
+            s.push_str(&digits);
+            s.push_str(&"0".repeat(decpt - digits.len()));
+            s.push_str(".0");
+        } else {
+            s.push_str(&digits[..decpt]);
+            s.push('.');
+            s.push_str(&digits[decpt..]);
+        }
+    } else if decpt <= 0 && decpt > -4 {
+        s.push_str("0.");
+        s.push_str(&"0".repeat(-decpt as usize));
+        s.push_str(&digits);
+    } else {
+        s.push_str(&digits[..1]);
+        s.push('.');
+        s.push_str(if digits.len() > 1 { &digits[1..] } else { "0" });
+        s.push_str(&format!("e{:+03}", decpt - 1));
+    }
+    s
 }
 
 impl fmt::Display for Float {
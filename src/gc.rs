@@ -14,7 +14,7 @@ use crate::{
     error::{protect, Error},
     r_hash::RHash,
     symbol::IntoSymbol,
-    value::{private::ReprValue as _, ReprValue, Value},
+    value::{private::ReprValue as _, InnerValue, Opaque, ReprValue, Value},
     Ruby,
 };
 
@@ -278,6 +278,69 @@ where
     unsafe { rb_gc_unregister_address(valref as *const _ as *mut VALUE) }
 }
 
+/// Registers `value` to never be garbage collected, returning a handle that
+/// can be used to retrieve the value later, from any Ruby thread.
+///
+/// A `static` holding a Ruby value is unsound without informing Ruby's
+/// garbage collector of it. `register_global` stores `value` in memory that
+/// lives for the rest of the program and registers it with the garbage
+/// collector, so it will be kept alive for the whole life of the Ruby VM.
+///
+/// As with [`register_mark_object`], this is essentially a deliberate memory
+/// leak, so should only be used for a small number of long-lived values.
+///
+/// See also [`Lazy`](crate::value::Lazy) for values that should be
+/// initialised on first use.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{gc, rb_assert, Error, Ruby};
+///
+/// fn example(ruby: &Ruby) -> Result<(), Error> {
+///     let config = ruby.hash_new();
+///     config.aset("debug", true)?;
+///     let global = gc::register_global(config);
+///
+///     // `global` can be stored and sent to other Ruby threads
+///
+///     ruby.gc_start();
+///
+///     let config = ruby.get_inner(global);
+///     rb_assert!(ruby, r#"config["debug"] == true"#, config);
+///
+///     Ok(())
+/// }
+/// # Ruby::init(example).unwrap()
+/// ```
+pub fn register_global<T>(value: T) -> GlobalId<T>
+where
+    T: ReprValue,
+{
+    let global: &'static Opaque<T> = Box::leak(Box::new(Opaque::from(value)));
+    unsafe { rb_gc_register_address(global as *const _ as *mut VALUE) };
+    GlobalId(global)
+}
+
+/// A handle to a Ruby value registered with [`register_global`].
+///
+/// `GlobalId` is [`Send`] + [`Sync`], so can be stored in a `static`. The
+/// value can be retrieved with [`Ruby::get_inner`].
+#[derive(Clone, Copy)]
+pub struct GlobalId<T: 'static>(&'static Opaque<T>);
+
+impl<T> InnerValue for GlobalId<T>
+where
+    T: ReprValue,
+{
+    type Value = T;
+
+    #[inline]
+    fn get_inner_with(self, ruby: &Ruby) -> Self::Value {
+        ruby.get_inner(*self.0)
+    }
+}
+
 /// # GC
 ///
 /// Functions for working with Ruby's Garbage Collector.
@@ -370,6 +433,71 @@ impl Ruby {
         unsafe { rb_gc_start() };
     }
 
+    /// Trigger a full GC run, followed by compaction, as with Ruby's
+    /// `GC.compact`.
+    ///
+    /// Compaction moves objects in memory, so this can be used to test that
+    /// the [`DataTypeFunctions::compact`](crate::DataTypeFunctions::compact)
+    /// implementation of a wrapped type correctly updates its references, and
+    /// that values that aren't updated are marked with [`Marker::mark`] so
+    /// they are pinned.
+    ///
+    /// Returns `Err` if compaction is not supported on the current platform.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{rb_assert, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let ary = ruby.ary_from_vec(vec!["a", "b", "c"]);
+    ///     // not all platforms support compaction
+    ///     let _ = ruby.gc_compact();
+    ///     rb_assert!(ruby, r#"ary == ["a", "b", "c"]"#, ary);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn gc_compact(&self) -> Result<(), Error> {
+        self.module_gc().funcall::<_, _, Value>("compact", ())?;
+        Ok(())
+    }
+
+    /// Enable or disable GC stress mode, as with Ruby's `GC.stress=`.
+    ///
+    /// In stress mode Ruby runs the garbage collector at every opportunity,
+    /// making bugs such as unmarked references in a wrapped type apparent
+    /// immediately, rather than intermittently. This makes Ruby very slow, so
+    /// should only be enabled for tests.
+    ///
+    /// Returns whether stress mode was previously enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{rb_assert, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let was_stressed = ruby.gc_stress(true)?;
+    ///     let ary = ruby.ary_new();
+    ///     for i in 0..10 {
+    ///         ary.push(i.to_string())?;
+    ///     }
+    ///     ruby.gc_stress(was_stressed)?;
+    ///     rb_assert!(ruby, r#"ary.last == "9""#, ary);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn gc_stress(&self, enabled: bool) -> Result<bool, Error> {
+        let gc = self.module_gc();
+        let prev = gc.funcall::<_, _, Value>("stress", ())?;
+        gc.funcall::<_, _, Value>("stress=", (enabled,))?;
+        Ok(prev.to_bool())
+    }
+
     /// Inform Ruby of external memory usage.
     ///
     /// The Ruby GC is run when Ruby thinks it's running out of memory, but
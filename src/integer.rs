@@ -1,13 +1,18 @@
 use std::{
     fmt,
-    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign},
-    os::raw::c_long,
+    ops::{
+        Add, AddAssign, BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Div,
+        DivAssign, Mul, MulAssign, Shl, ShlAssign, Shr, ShrAssign, Sub, SubAssign,
+    },
+    os::raw::{c_int, c_long, c_void},
 };
 
 use rb_sys::{
-    rb_big_cmp, rb_big_div, rb_big_eq, rb_big_minus, rb_big_mul, rb_big_norm, rb_big_plus,
-    rb_int2big, rb_ll2inum, rb_to_int, rb_ull2inum, ruby_special_consts, ruby_value_type, Qtrue,
-    VALUE,
+    rb_absint_singlebit_p, rb_absint_size, rb_big2str, rb_big_and, rb_big_cmp, rb_big_div,
+    rb_big_eq, rb_big_lshift, rb_big_minus, rb_big_mul, rb_big_norm, rb_big_or, rb_big_plus,
+    rb_big_rshift, rb_big_sign, rb_big_xor, rb_fix2str, rb_int2big, rb_integer_pack, rb_ll2inum,
+    rb_to_int, rb_ull2inum, ruby_special_consts, ruby_value_type, Qtrue, INTEGER_PACK_2COMP,
+    INTEGER_PACK_BIG_ENDIAN, INTEGER_PACK_LITTLE_ENDIAN, VALUE,
 };
 
 use crate::{
@@ -15,6 +20,7 @@ use crate::{
     into_value::IntoValue,
     numeric::Numeric,
     r_bignum::RBignum,
+    r_string::RString,
     try_convert::TryConvert,
     value::{
         private::{self, ReprValue as _},
@@ -490,6 +496,73 @@ impl Integer {
         }
     }
 
+    /// Convert `self` to an `i128`. Returns `Err` if `self` is out of range
+    /// for `i128`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Integer, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     assert_eq!(ruby.eval::<Integer>("2 ** 100")?.to_i128()?, 1 << 100);
+    ///     assert_eq!(ruby.eval::<Integer>("-(2 ** 127)")?.to_i128()?, i128::MIN);
+    ///     assert!(ruby.eval::<Integer>("2 ** 127")?.to_i128().is_err());
+    ///     assert!(ruby.eval::<Integer>("-(2 ** 127) - 1")?.to_i128().is_err());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn to_i128(self) -> Result<i128, Error> {
+        if let IntegerType::Fixnum(fix) = self.integer_type() {
+            return Ok(i128::from(fix.to_i64()));
+        }
+        if self.bit_length() > 127 {
+            return Err(Error::new(
+                Ruby::get_with(self).exception_range_error(),
+                "bignum too big to convert into `i128`",
+            ));
+        }
+        Ok(i128::from_le_bytes(self.pack_16_bytes()))
+    }
+
+    /// Convert `self` to a `u128`. Returns `Err` if `self` is negative or out
+    /// of range for `u128`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Integer, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     assert_eq!(ruby.eval::<Integer>("2 ** 128 - 1")?.to_u128()?, u128::MAX);
+    ///     assert!(ruby.eval::<Integer>("2 ** 128")?.to_u128().is_err());
+    ///     assert!(ruby.eval::<Integer>("-1")?.to_u128().is_err());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn to_u128(self) -> Result<u128, Error> {
+        if let IntegerType::Fixnum(fix) = self.integer_type() {
+            return fix.to_u64().map(u128::from);
+        }
+        if self.is_negative() {
+            return Err(Error::new(
+                Ruby::get_with(self).exception_range_error(),
+                "can't convert negative integer to unsigned",
+            ));
+        }
+        if self.bit_length() > 128 {
+            return Err(Error::new(
+                Ruby::get_with(self).exception_range_error(),
+                "bignum too big to convert into `u128`",
+            ));
+        }
+        Ok(u128::from_le_bytes(self.pack_16_bytes()))
+    }
+
     /// Normalize `self`. If `self` is a `Fixnum`, returns `self`. If `self` is
     /// a `Bignum`, if it is small enough to fit in a `Fixnum`, returns a
     /// `Fixnum` with the same value. Otherwise, returns `self`.
@@ -502,6 +575,261 @@ impl Integer {
         }
     }
 
+    /// Returns whether `self` is zero.
+    ///
+    /// This does not call any Ruby methods.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     assert!(ruby.integer_from_i64(0).is_zero());
+    ///     assert!(!ruby.integer_from_i64(-1).is_zero());
+    ///     assert!(!ruby.integer_from_u64(u64::MAX).is_zero());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn is_zero(self) -> bool {
+        match self.norm().integer_type() {
+            IntegerType::Fixnum(fix) => fix.to_isize() == 0,
+            IntegerType::Bignum(_) => false,
+        }
+    }
+
+    /// Returns whether `self` is greater than zero.
+    ///
+    /// This does not call any Ruby methods.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     assert!(ruby.integer_from_i64(1).is_positive());
+    ///     assert!(ruby.integer_from_u64(u64::MAX).is_positive());
+    ///     assert!(!ruby.integer_from_i64(0).is_positive());
+    ///     assert!(!ruby.integer_from_i64(i64::MIN).is_positive());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn is_positive(self) -> bool {
+        match self.norm().integer_type() {
+            IntegerType::Fixnum(fix) => fix.to_isize() > 0,
+            IntegerType::Bignum(big) => unsafe { rb_big_sign(big.as_rb_value()) != 0 },
+        }
+    }
+
+    /// Returns whether `self` is less than zero.
+    ///
+    /// This does not call any Ruby methods.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     assert!(ruby.integer_from_i64(-1).is_negative());
+    ///     assert!(ruby.integer_from_i64(i64::MIN).is_negative());
+    ///     assert!(!ruby.integer_from_i64(0).is_negative());
+    ///     assert!(!ruby.integer_from_u64(u64::MAX).is_negative());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn is_negative(self) -> bool {
+        match self.norm().integer_type() {
+            IntegerType::Fixnum(fix) => fix.to_isize() < 0,
+            IntegerType::Bignum(big) => unsafe { rb_big_sign(big.as_rb_value()) == 0 },
+        }
+    }
+
+    /// Returns the number of bits required to represent `self`, not including
+    /// the sign bit.
+    ///
+    /// As with Ruby's `Integer#bit_length`, for negative numbers this is the
+    /// number of bits in the two's complement representation, excluding the
+    /// infinite run of leading 1 bits, so `-1` has a bit length of `0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     assert_eq!(ruby.integer_from_i64(0).bit_length(), 0);
+    ///     assert_eq!(ruby.integer_from_i64(255).bit_length(), 8);
+    ///     assert_eq!(ruby.integer_from_i64(-256).bit_length(), 8);
+    ///     assert_eq!(ruby.integer_from_i64(-257).bit_length(), 9);
+    ///     assert_eq!(ruby.integer_from_u64(u64::MAX).bit_length(), 64);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn bit_length(self) -> usize {
+        match self.integer_type() {
+            IntegerType::Fixnum(fix) => {
+                let i = fix.to_i64();
+                let i = if i < 0 { !i } else { i };
+                (i64::BITS - i.leading_zeros()) as usize
+            }
+            IntegerType::Bignum(big) => unsafe {
+                let mut nlz_bits = 0;
+                let size = rb_absint_size(big.as_rb_value(), &mut nlz_bits);
+                let bits = size * 8 - nlz_bits as usize;
+                // for negative numbers the bit length is that of `abs - 1`,
+                // which is only different if `abs` is a power of 2
+                if rb_big_sign(big.as_rb_value()) == 0
+                    && rb_absint_singlebit_p(big.as_rb_value()) != 0
+                {
+                    bits - 1
+                } else {
+                    bits
+                }
+            },
+        }
+    }
+
+    /// Returns the two's complement representation of `self` as a byte
+    /// vector in big-endian (most significant byte first) order.
+    ///
+    /// The returned vector is the minimum length required to represent
+    /// `self`, including a sign bit, so positive numbers with the most
+    /// significant bit of their top byte set will have a leading `0x00` byte.
+    ///
+    /// See also [`Integer::to_le_bytes`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     assert_eq!(ruby.integer_from_i64(0).to_be_bytes(), vec![0]);
+    ///     assert_eq!(ruby.integer_from_i64(0x1234).to_be_bytes(), vec![0x12, 0x34]);
+    ///     assert_eq!(ruby.integer_from_i64(255).to_be_bytes(), vec![0, 255]);
+    ///     assert_eq!(ruby.integer_from_i64(-1).to_be_bytes(), vec![255]);
+    ///     assert_eq!(ruby.integer_from_i64(-256).to_be_bytes(), vec![255, 0]);
+    ///
+    ///     let i: magnus::Integer = ruby.eval("2 ** 64")?;
+    ///     assert_eq!(i.to_be_bytes(), vec![1, 0, 0, 0, 0, 0, 0, 0, 0]);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn to_be_bytes(self) -> Vec<u8> {
+        self.pack_bytes(INTEGER_PACK_BIG_ENDIAN)
+    }
+
+    /// Returns the two's complement representation of `self` as a byte
+    /// vector in little-endian (least significant byte first) order.
+    ///
+    /// See [`Integer::to_be_bytes`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     assert_eq!(ruby.integer_from_i64(0x1234).to_le_bytes(), vec![0x34, 0x12]);
+    ///     assert_eq!(ruby.integer_from_i64(-256).to_le_bytes(), vec![0, 255]);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn to_le_bytes(self) -> Vec<u8> {
+        self.pack_bytes(INTEGER_PACK_LITTLE_ENDIAN)
+    }
+
+    fn pack_bytes(self, endian: u32) -> Vec<u8> {
+        // the bit length doesn't include the sign bit, so always add 1
+        let mut bytes = vec![0; self.bit_length() / 8 + 1];
+        unsafe {
+            rb_integer_pack(
+                self.as_rb_value(),
+                bytes.as_mut_ptr() as *mut c_void,
+                bytes.len(),
+                1,
+                0,
+                (INTEGER_PACK_2COMP | endian) as c_int,
+            )
+        };
+        bytes
+    }
+
+    /// Returns `self` formatted as a Ruby string in `base`, as with Ruby's
+    /// `Integer#to_s(base)`.
+    ///
+    /// `base` must be between 2 and 36, inclusive. Digits above 9 are
+    /// represented by lowercase letters, and negative numbers are prefixed
+    /// with `-`.
+    ///
+    /// Returns `Err` if `base` is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Integer, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let i = ruby.integer_from_i64(255);
+    ///     assert_eq!(i.to_r_string_radix(16)?.to_string()?, "ff");
+    ///     assert_eq!(i.to_r_string_radix(2)?.to_string()?, "11111111");
+    ///
+    ///     let i = ruby.integer_from_i64(-255);
+    ///     assert_eq!(i.to_r_string_radix(16)?.to_string()?, "-ff");
+    ///
+    ///     let i: Integer = ruby.eval("2 ** 64")?;
+    ///     assert_eq!(i.to_r_string_radix(16)?.to_string()?, "10000000000000000");
+    ///
+    ///     assert!(i.to_r_string_radix(37).is_err());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn to_r_string_radix(self, base: u32) -> Result<RString, Error> {
+        if !(2..=36).contains(&base) {
+            return Err(Error::new(
+                Ruby::get_with(self).exception_arg_error(),
+                format!("invalid radix {}", base),
+            ));
+        }
+        let val = match self.integer_type() {
+            IntegerType::Fixnum(fix) => unsafe { rb_fix2str(fix.as_rb_value(), base as c_int) },
+            IntegerType::Bignum(big) => unsafe { rb_big2str(big.as_rb_value(), base as c_int) },
+        };
+        Ok(unsafe { RString::from_rb_value_unchecked(val) })
+    }
+
+    // callers must check `self` fits in 128 bits
+    fn pack_16_bytes(self) -> [u8; 16] {
+        let mut bytes = [0; 16];
+        unsafe {
+            rb_integer_pack(
+                self.as_rb_value(),
+                bytes.as_mut_ptr() as *mut c_void,
+                bytes.len(),
+                1,
+                0,
+                (INTEGER_PACK_2COMP | INTEGER_PACK_LITTLE_ENDIAN) as c_int,
+            )
+        };
+        bytes
+    }
+
     fn binary_operation_visit<T>(
         &self,
         other: &Self,
@@ -708,3 +1036,118 @@ impl DivAssign for Integer {
         *self = *self / other;
     }
 }
+
+impl BitAnd for Integer {
+    type Output = Self;
+
+    fn bitand(self, other: Self) -> Self {
+        self.binary_operation_visit(
+            &other,
+            // for Fixnums `(2a + 1) & (2b + 1) == 2(a & b) + 1`
+            |a, b| unsafe { Integer::from_rb_value_unchecked(a.as_rb_value() & b.as_rb_value()) },
+            |a, b| unsafe { Integer::from_rb_value_unchecked(rb_big_and(a, b)) },
+        )
+    }
+}
+
+impl BitAndAssign for Integer {
+    fn bitand_assign(&mut self, other: Self) {
+        *self = *self & other;
+    }
+}
+
+impl BitOr for Integer {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        self.binary_operation_visit(
+            &other,
+            // for Fixnums `(2a + 1) | (2b + 1) == 2(a | b) + 1`
+            |a, b| unsafe { Integer::from_rb_value_unchecked(a.as_rb_value() | b.as_rb_value()) },
+            |a, b| unsafe { Integer::from_rb_value_unchecked(rb_big_or(a, b)) },
+        )
+    }
+}
+
+impl BitOrAssign for Integer {
+    fn bitor_assign(&mut self, other: Self) {
+        *self = *self | other;
+    }
+}
+
+impl BitXor for Integer {
+    type Output = Self;
+
+    fn bitxor(self, other: Self) -> Self {
+        self.binary_operation_visit(
+            &other,
+            // for Fixnums `(2a + 1) ^ (2b + 1) == 2(a ^ b)`
+            |a, b| unsafe {
+                Integer::from_rb_value_unchecked((a.as_rb_value() ^ b.as_rb_value()) | 1)
+            },
+            |a, b| unsafe { Integer::from_rb_value_unchecked(rb_big_xor(a, b)) },
+        )
+    }
+}
+
+impl BitXorAssign for Integer {
+    fn bitxor_assign(&mut self, other: Self) {
+        *self = *self ^ other;
+    }
+}
+
+impl Shl<u32> for Integer {
+    type Output = Self;
+
+    fn shl(self, n: u32) -> Self {
+        let handle = Ruby::get_with(self);
+        let big = match self.integer_type() {
+            IntegerType::Fixnum(fix) => {
+                let i = fix.to_i64();
+                if n < i64::BITS && (i << n) >> n == i {
+                    return handle.integer_from_i64(i << n);
+                }
+                unsafe { rb_int2big(fix.to_isize()) }
+            }
+            IntegerType::Bignum(big) => big.as_rb_value(),
+        };
+        let n = handle.into_value(n);
+        unsafe { Integer::from_rb_value_unchecked(rb_big_lshift(big, n.as_rb_value())) }
+    }
+}
+
+impl ShlAssign<u32> for Integer {
+    fn shl_assign(&mut self, n: u32) {
+        *self = *self << n;
+    }
+}
+
+/// Arithmetic (sign-extending) right shift, rounding towards negative
+/// infinity, matching Ruby's `Integer#>>`.
+impl Shr<u32> for Integer {
+    type Output = Self;
+
+    fn shr(self, n: u32) -> Self {
+        let handle = Ruby::get_with(self);
+        match self.integer_type() {
+            IntegerType::Fixnum(fix) => {
+                handle.integer_from_i64(fix.to_i64() >> n.min(i64::BITS - 1))
+            }
+            IntegerType::Bignum(big) => {
+                let n = handle.into_value(n);
+                unsafe {
+                    Integer::from_rb_value_unchecked(rb_big_rshift(
+                        big.as_rb_value(),
+                        n.as_rb_value(),
+                    ))
+                }
+            }
+        }
+    }
+}
+
+impl ShrAssign<u32> for Integer {
+    fn shr_assign(&mut self, n: u32) {
+        *self = *self >> n;
+    }
+}
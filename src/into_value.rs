@@ -1,6 +1,7 @@
 use seq_macro::seq;
 
 use crate::{
+    error::Error,
     r_array::RArray,
     r_hash::RHash,
     value::{ReprValue, Value},
@@ -25,6 +26,10 @@ impl Ruby {
 }
 
 /// Conversions from Rust types into [`Value`].
+///
+/// `Option<T>` converts `None` to Ruby `nil`, and `Some(T)` to the
+/// conversion of `T`. See [`TryConvert`](crate::TryConvert#option) for the
+/// reverse conversion.
 pub trait IntoValue: Sized {
     /// Convert `self` into [`Value`].
     ///
@@ -56,6 +61,64 @@ pub trait IntoValue: Sized {
     fn into_value_with(self, handle: &Ruby) -> Value;
 }
 
+/// Fallible conversions from Rust types into [`Value`].
+///
+/// This is automatically implemented for all types implementing
+/// [`IntoValue`], and can be implemented for types where conversion to Ruby
+/// may fail. Functions and methods wrapped with [`function!`](crate::function)
+/// and [`method!`](crate::method) can return types implementing this trait,
+/// and any error will be raised as a Ruby exception.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{function, prelude::*, rb_assert, Error, Ruby, TryIntoValue, Value};
+///
+/// struct Percent(u32);
+///
+/// impl TryIntoValue for Percent {
+///     fn try_into_value_with(self, handle: &Ruby) -> Result<Value, Error> {
+///         if self.0 > 100 {
+///             return Err(Error::new(
+///                 handle.exception_range_error(),
+///                 format!("{}% is out of range", self.0),
+///             ));
+///         }
+///         Ok(handle.float_from_f64(self.0 as f64 / 100.0).as_value())
+///     }
+/// }
+///
+/// fn percent(n: u32) -> Percent {
+///     Percent(n)
+/// }
+///
+/// fn example(ruby: &Ruby) -> Result<(), Error> {
+///     ruby.define_global_function("percent", function!(percent, 1));
+///
+///     rb_assert!(ruby, "percent(50) == 0.5");
+///     rb_assert!(ruby, "begin; percent(150); false; rescue RangeError; true; end");
+///
+///     Ok(())
+/// }
+/// # Ruby::init(example).unwrap()
+/// ```
+pub trait TryIntoValue: Sized {
+    /// Convert `self` into [`Value`].
+    ///
+    /// Returns `Err` if the conversion fails.
+    fn try_into_value_with(self, handle: &Ruby) -> Result<Value, Error>;
+}
+
+impl<T> TryIntoValue for T
+where
+    T: IntoValue,
+{
+    #[inline]
+    fn try_into_value_with(self, handle: &Ruby) -> Result<Value, Error> {
+        Ok(self.into_value_with(handle))
+    }
+}
+
 /// Conversions from Rust types that do not contain [`Value`] into [`Value`].
 ///
 /// This trait is used as a bound in functions such as
@@ -160,10 +223,12 @@ where
     type Value = T;
     type Output = &'a [Self::Value];
 
+    #[inline]
     fn into_arg_list_with(self, _: &Ruby) -> Self::Output {
         self
     }
 
+    #[inline]
     fn contains_kw_args(&self) -> bool {
         false
     }
@@ -179,11 +244,13 @@ macro_rules! impl_arg_list {
                 type Value = Value;
                 type Output = [Self::Value; $n];
 
+                #[inline]
                 #[allow(unused_variables)]
                 fn into_arg_list_with(self, handle: &Ruby) -> Self::Output {
                     [#(handle.into_value(self.N),)*]
                 }
 
+                #[inline]
                 fn contains_kw_args(&self) -> bool {
                     false
                 }
@@ -206,11 +273,13 @@ macro_rules! impl_arg_list_kw {
                 type Value = Value;
                 type Output = [Self::Value; { $n + 1 }];
 
+                #[inline]
                 #[allow(unused_variables)]
                 fn into_arg_list_with(self, handle: &Ruby) -> Self::Output {
                     [#(handle.into_value(self.N),)* handle.into_value(self.$n.0)]
                 }
 
+                #[inline]
                 fn contains_kw_args(&self) -> bool {
                     true
                 }
@@ -230,10 +299,12 @@ where
     type Value = T;
     type Output = [Self::Value; N];
 
+    #[inline]
     fn into_arg_list_with(self, _: &Ruby) -> Self::Output {
         self
     }
 
+    #[inline]
     fn contains_kw_args(&self) -> bool {
         false
     }
@@ -0,0 +1,364 @@
+//! Conversions between [`serde_json`] types and Ruby objects.
+//!
+//! With the `serde_json` feature enabled [`serde_json::Value`] implements
+//! [`IntoValue`] and [`TryConvert`], mapping JSON to Ruby types as follows:
+//!
+//! | JSON           | Ruby                    |
+//! | -------------- | ----------------------- |
+//! | `null`         | `nil`                   |
+//! | `true`/`false` | `true`/`false`          |
+//! | integer number | `Integer`               |
+//! | other number   | `Float`                 |
+//! | string         | `String`                |
+//! | array          | `Array`                 |
+//! | object         | `Hash` with String keys |
+//!
+//! Integers too large for an `i64` are converted to bignums. When converting
+//! from Ruby, Symbols are converted to JSON strings, and Hash keys must be
+//! Strings or Symbols.
+//!
+//! Ruby Hashes preserve insertion order, so JSON objects keep the order of
+//! their [`serde_json::Map`], which is only the source order when
+//! `serde_json`'s `preserve_order` feature is enabled.
+//!
+//! To go straight from JSON text to Ruby objects, without building an
+//! intermediate [`serde_json::Value`], see [`Ruby::json_parse`].
+
+use std::{cell::Cell, fmt};
+
+use serde::de::{self, DeserializeSeed, MapAccess, SeqAccess, Visitor};
+use serde_json::{Map, Number};
+
+use crate::{
+    error::Error,
+    float::Float,
+    integer::Integer,
+    into_value::IntoValue,
+    module::Module,
+    r_array::RArray,
+    r_hash::{ForEach, RHash},
+    r_string::RString,
+    symbol::Symbol,
+    try_convert::TryConvert,
+    value::{Qfalse, Qtrue, ReprValue, Value},
+    Ruby,
+};
+
+/// The maximum depth of nested Arrays and Hashes that will be converted to
+/// JSON, and the default maximum depth for [`Ruby::json_parse`].
+///
+/// This matches `serde_json`'s default recursion limit when parsing.
+pub const MAX_NESTING: usize = 128;
+
+/// # JSON
+///
+/// Functions for parsing JSON directly into Ruby objects.
+///
+/// See also the [`json`](self) module.
+impl Ruby {
+    /// Parse JSON text directly into Ruby objects.
+    ///
+    /// Objects are converted as described in the [module docs](self), and
+    /// are built as the input is parsed, without an intermediate
+    /// [`serde_json::Value`]. Integers too large for a `u64` are parsed as
+    /// Floats.
+    ///
+    /// Errors with an `ArgumentError` if `json` is not valid JSON, or if
+    /// Arrays and objects are nested more than [`MAX_NESTING`] deep. See
+    /// [`Ruby::json_parse_with_max_nesting`] to set a different limit. Any
+    /// exception raised by Ruby while building the result is returned
+    /// unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{rb_assert, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let val = ruby.json_parse(br#"{"a": [1, 2.5, "three", null]}"#)?;
+    ///     rb_assert!(ruby, r#"val == {"a" => [1, 2.5, "three", nil]}"#, val);
+    ///
+    ///     assert!(ruby.json_parse(b"[1, 2").is_err());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn json_parse(&self, json: &[u8]) -> Result<Value, Error> {
+        self.json_parse_with_max_nesting(json, MAX_NESTING)
+    }
+
+    /// Parse JSON text directly into Ruby objects, with Arrays and objects
+    /// nested at most `max_nesting` deep.
+    ///
+    /// See [`Ruby::json_parse`] for details. The underlying parser has its
+    /// own limit of [`MAX_NESTING`], so values of `max_nesting` above this
+    /// have no effect.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     assert!(ruby.json_parse_with_max_nesting(b"[[1]]", 2).is_ok());
+    ///
+    ///     let err = ruby
+    ///         .json_parse_with_max_nesting(b"[[[1]]]", 2)
+    ///         .unwrap_err();
+    ///     assert!(err.is_kind_of(ruby.exception_arg_error()));
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn json_parse_with_max_nesting(
+        &self,
+        json: &[u8],
+        max_nesting: usize,
+    ) -> Result<Value, Error> {
+        let mut deserializer = serde_json::Deserializer::from_slice(json);
+        let error = Cell::new(None);
+        let seed = Seed {
+            handle: self,
+            depth: 0,
+            max_nesting,
+            error: &error,
+        };
+        seed.deserialize(&mut deserializer)
+            .and_then(|val| deserializer.end().map(|_| val))
+            .map_err(|e| {
+                error
+                    .take()
+                    .unwrap_or_else(|| Error::new(self.exception_arg_error(), e.to_string()))
+            })
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Seed<'a> {
+    handle: &'a Ruby,
+    depth: usize,
+    max_nesting: usize,
+    // an error raised by Ruby while building the result, returned as-is
+    // rather than as the stringified `serde_json::Error`
+    error: &'a Cell<Option<Error>>,
+}
+
+impl Seed<'_> {
+    fn nested<E: de::Error>(self) -> Result<Self, E> {
+        if self.depth >= self.max_nesting {
+            return Err(E::custom(format!(
+                "nesting of {} is too deep",
+                self.depth + 1
+            )));
+        }
+        Ok(Self {
+            depth: self.depth + 1,
+            ..self
+        })
+    }
+
+    fn stash<E: de::Error>(self, e: Error) -> E {
+        let msg = e.to_string();
+        self.error.set(Some(e));
+        E::custom(msg)
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for Seed<'_> {
+    type Value = Value;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for Seed<'_> {
+    type Value = Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("any valid JSON value")
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(self.handle.qnil().as_value())
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(v.into_value_with(self.handle))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(v.into_value_with(self.handle))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(v.into_value_with(self.handle))
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(v.into_value_with(self.handle))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(self.handle.str_new(v).as_value())
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let seed = self.nested::<A::Error>()?;
+        let ary = self.handle.ary_new();
+        while let Some(val) = seq.next_element_seed(seed)? {
+            ary.push(val).map_err(|e| self.stash::<A::Error>(e))?;
+        }
+        Ok(ary.as_value())
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let seed = self.nested::<A::Error>()?;
+        let hash = self.handle.hash_new();
+        while let Some(key) = map.next_key_seed(seed)? {
+            let val = map.next_value_seed(seed)?;
+            hash.aset(key, val).map_err(|e| self.stash::<A::Error>(e))?;
+        }
+        Ok(hash.as_value())
+    }
+}
+
+impl IntoValue for Number {
+    fn into_value_with(self, handle: &Ruby) -> Value {
+        if let Some(i) = self.as_i64() {
+            i.into_value_with(handle)
+        } else if let Some(u) = self.as_u64() {
+            u.into_value_with(handle)
+        } else {
+            let s = self.to_string();
+            // only reachable with serde_json's `arbitrary_precision` feature
+            if s.bytes().all(|b| b == b'-' || b.is_ascii_digit()) {
+                handle.module_kernel().funcall("Integer", (s,)).unwrap()
+            } else {
+                self.as_f64().unwrap().into_value_with(handle)
+            }
+        }
+    }
+}
+
+impl IntoValue for serde_json::Value {
+    fn into_value_with(self, handle: &Ruby) -> Value {
+        match self {
+            Self::Null => handle.qnil().as_value(),
+            Self::Bool(b) => b.into_value_with(handle),
+            Self::Number(n) => n.into_value_with(handle),
+            Self::String(s) => s.into_value_with(handle),
+            Self::Array(a) => handle.ary_from_iter(a).as_value(),
+            Self::Object(o) => handle.hash_from_iter(o).as_value(),
+        }
+    }
+}
+
+impl TryConvert for Number {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        let handle = Ruby::get_with(val);
+        if let Some(i) = Integer::from_value(val) {
+            i.to_i64()
+                .map(Number::from)
+                .or_else(|_| i.to_u64().map(Number::from))
+                .map_err(|_| {
+                    Error::new(
+                        handle.exception_range_error(),
+                        format!("integer {} too big to convert to JSON", i),
+                    )
+                })
+        } else if let Some(f) = Float::from_value(val) {
+            Number::from_f64(f.to_f64()).ok_or_else(|| {
+                Error::new(
+                    handle.exception_arg_error(),
+                    format!("{} not allowed in JSON", f),
+                )
+            })
+        } else {
+            Err(Error::type_error("JSON number", val))
+        }
+    }
+}
+
+impl TryConvert for serde_json::Value {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        to_json(val, 0)
+    }
+}
+
+fn to_json(val: Value, depth: usize) -> Result<serde_json::Value, Error> {
+    let handle = Ruby::get_with(val);
+    if depth > MAX_NESTING {
+        return Err(Error::new(
+            handle.exception_arg_error(),
+            format!("nesting of {} is too deep", depth),
+        ));
+    }
+    if val.is_nil() {
+        Ok(serde_json::Value::Null)
+    } else if Qtrue::from_value(val).is_some() {
+        Ok(serde_json::Value::Bool(true))
+    } else if Qfalse::from_value(val).is_some() {
+        Ok(serde_json::Value::Bool(false))
+    } else if Integer::from_value(val).is_some() || Float::from_value(val).is_some() {
+        Number::try_convert(val).map(serde_json::Value::Number)
+    } else if let Some(s) = RString::from_value(val) {
+        Ok(serde_json::Value::String(s.to_string()?))
+    } else if let Some(s) = Symbol::from_value(val) {
+        Ok(serde_json::Value::String(s.name()?.into_owned()))
+    } else if let Some(ary) = RArray::from_value(val) {
+        let mut vec = Vec::with_capacity(ary.len());
+        for i in 0..ary.len() {
+            vec.push(to_json(ary.entry(i as isize)?, depth + 1)?);
+        }
+        Ok(serde_json::Value::Array(vec))
+    } else if let Some(hash) = RHash::from_value(val) {
+        let mut map = Map::new();
+        hash.foreach(|key: Value, value: Value| {
+            let key = if let Some(s) = RString::from_value(key) {
+                s.to_string()?
+            } else if let Some(s) = Symbol::from_value(key) {
+                s.name()?.into_owned()
+            } else {
+                return Err(Error::new(
+                    handle.exception_type_error(),
+                    format!(
+                        "JSON object keys must be String or Symbol, not {}",
+                        unsafe { key.classname() }
+                    ),
+                ));
+            };
+            map.insert(key, to_json(value, depth + 1)?);
+            Ok(ForEach::Continue)
+        })?;
+        Ok(serde_json::Value::Object(map))
+    } else {
+        Err(Error::type_error("JSON", val))
+    }
+}
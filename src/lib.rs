@@ -217,8 +217,8 @@
 //!
 //! ## `rb_a`-`rb_arx`
 // * `rb_absint_numwords`:
-// * `rb_absint_singlebit_p`:
-// * `rb_absint_size`:
+//! * `rb_absint_singlebit_p`: See [`Integer::bit_length`].
+//! * `rb_absint_size`: See [`Integer::bit_length`].
 // * `rb_add_event_hook`:
 // * `rb_add_event_hook2`:
 //! * `rb_alias`: [`Module::define_alias`].
@@ -292,9 +292,9 @@
 // * `rb_assoc_new`:
 //! * `rb_attr`: [`Module::define_attr`].
 // * `rb_attr_get`:
-// * `rb_autoload`:
+//! * `rb_autoload`: [`Module::autoload`].
 // * `rb_autoload_load`:
-// * `rb_autoload_p`:
+//! * `rb_autoload_p`: [`Module::autoload_path`].
 //!
 //! ## `rb_b`
 //!
@@ -305,13 +305,13 @@
 // * `rb_big2int`:
 // * `rb_big2ll`:
 // * `rb_big2long`:
-// * `rb_big2str`:
+//! * `rb_big2str`: See [`Integer::to_r_string_radix`].
 // * `rb_big2uint`:
 // * `rb_big2ull`:
 // * `rb_big2ulong`:
 // * `rb_bigzero_p`:
 // * `rb_big_2comp`:
-// * `rb_big_and`:
+//! * `rb_big_and`: [`Integer`] implements [`BitAnd`](std::ops::BitAnd).
 // * `rb_big_clone`:
 // * `rb_big_cmp`:
 // * `rb_big_div`:
@@ -319,21 +319,21 @@
 // * `rb_big_eq`:
 // * `rb_big_eql`:
 // * `rb_big_idiv`:
-// * `rb_big_lshift`:
+//! * `rb_big_lshift`: [`Integer`] implements [`Shl`](std::ops::Shl).
 // * `rb_big_minus`:
 // * `rb_big_modulo`:
 // * `rb_big_mul`:
 // * `rb_big_new`:
 //! * `rb_big_norm`: [`Integer::norm`].
-// * `rb_big_or`:
+//! * `rb_big_or`: [`Integer`] implements [`BitOr`](std::ops::BitOr).
 // * `rb_big_pack`:
 // * `rb_big_plus`:
 // * `rb_big_pow`:
 // * `rb_big_resize`:
-// * `rb_big_rshift`:
-// * `rb_big_sign`:
+//! * `rb_big_rshift`: [`Integer`] implements [`Shr`](std::ops::Shr).
+//! * `rb_big_sign`: See [`Integer::is_positive`] and [`Integer::is_negative`].
 // * `rb_big_unpack`:
-// * `rb_big_xor`:
+//! * `rb_big_xor`: [`Integer`] implements [`BitXor`](std::ops::BitXor).
 //! * `rb_block_call`: See [`Value::block_call`].
 //! * `rb_block_call_kw`: [`Value::block_call`].
 //! * `rb_block_given_p`: [`block::block_given`].
@@ -350,23 +350,25 @@
 // * `rb_catch`:
 // * `rb_catch_obj`:
 // * `rb_category_compile_warn`:
-// * `rb_category_warn`:
+//! * `rb_category_warn`: See [`Ruby::warn_category`].
 // * `rb_category_warning`:
 // * `rb_char_to_option_kcode`:
 //! * `rb_check_arity`: [`scan_args::check_arity`].
-//! * `rb_check_array_type`:  See [`TryConvert`] and [`Value::try_convert`].
+//! * `rb_check_array_type`: [`Value::check_array_type`]. See also [`TryConvert`] and
+//!   [`Value::try_convert`].
 // * `rb_check_convert_type`:
 // * `rb_check_copyable`:
 // * `rb_check_frozen`:
 // * `rb_check_frozen_inline`:
 //! * `rb_check_funcall`: See [`Value::check_funcall`].
 //! * `rb_check_funcall_kw`:  [`Value::check_funcall`].
-//! * `rb_check_hash_type`: See [`TryConvert`] and [`Value::try_convert`].
+//! * `rb_check_hash_type`: [`Value::check_hash_type`]. See also [`TryConvert`] and
+//!   [`Value::try_convert`].
 //! * `rb_check_id`: Similar to [`Id::check`](value::Id::check).
 //! * `rb_check_id_cstr`: [`Id::check`](value::Id::check).
 // * `rb_check_inheritable`:
 // * `rb_check_safe_str`:
-// * `rb_check_string_type`:
+//! * `rb_check_string_type`: [`Value::check_string_type`].
 //! * `rb_check_symbol`: Similar to [`StaticSymbol::check`].
 //! * `rb_check_symbol_cstr`: [`StaticSymbol::check`].
 // * `rb_check_to_float`:
@@ -379,7 +381,7 @@
 // * `rb_class_descendants`:
 // * `rb_class_get_superclass`:
 // * `rb_class_inherited_p`: [`Module::is_inherited`].
-// * `rb_class_instance_methods`:
+//! * `rb_class_instance_methods`: [`Module::instance_methods`].
 //! * `rb_class_name`: Simmilar to [`Value::classname`].
 //! * `rb_class_new`: [`RClass::new`].
 //! * `rb_class_new_instance`: See [`RClass::new_instance`].
@@ -392,7 +394,7 @@
 // * `rb_class_protected_instance_methods`:
 // * `rb_class_public_instance_methods`:
 // * `rb_class_real`:
-// * `rb_class_subclasses`:
+//! * `rb_class_subclasses`: [`RClass::subclasses`].
 //! * `rb_class_superclass`: [`RClass::superclass`].
 // * `rb_clear_constant_cache`:
 // * `rb_clear_trace_func`:
@@ -450,8 +452,8 @@
 //! * `rb_current_receiver`: [`current_receiver`].
 // * `rb_cvar_defined`:
 // * `rb_cvar_find`:
-// * `rb_cvar_get`:
-// * `rb_cvar_set`:
+//! * `rb_cvar_get`: [`Module::cvar_get`].
+//! * `rb_cvar_set`: [`Module::cvar_set`].
 // * `rb_cv_get`:
 // * `rb_cv_set`:
 //!
@@ -491,7 +493,7 @@
 // * `rb_define_finalizer`:
 // * `rb_define_global_const`:
 //! * `rb_define_global_function`: [`define_global_function`].
-// * `rb_define_hooked_variable`:
+//! * `rb_define_hooked_variable`: [`Ruby::define_global_variable`].
 //! * `rb_define_method`: See [`Module::define_method`].
 //! * `rb_define_method_id`: [`Module::define_method`].
 //! * `rb_define_module`: [`define_module`].
@@ -501,10 +503,10 @@
 //! * `rb_define_module_under`: See [`Module::define_module`].
 //! * `rb_define_private_method`: [`Module::define_private_method`].
 //! * `rb_define_protected_method`: [`Module::define_protected_method`].
-// * `rb_define_readonly_variable`:
+//! * `rb_define_readonly_variable`: [`Ruby::define_readonly_variable`].
 //! * `rb_define_singleton_method`: [`Object::define_singleton_method`].
 //! * `rb_define_variable`: [`define_variable`].
-// * `rb_define_virtual_variable`:
+//! * `rb_define_virtual_variable`: Similar to [`Ruby::define_global_variable`].
 // * `rb_deprecate_constant`:
 // * `rb_detach_process`:
 // * `rb_dir_getwd`:
@@ -624,7 +626,7 @@
 // * `rb_enc_strlen`:
 // * `rb_enc_str_asciicompat_p`:
 // * `rb_enc_str_asciionly_p`:
-// * `rb_enc_str_buf_cat`:
+//! * `rb_enc_str_buf_cat`: [`RString::enc_cat`].
 //! * `rb_enc_str_coderange`: [`RString::enc_coderange_scan`].
 //! * `rb_enc_str_new`: [`RString::enc_new`].
 // * `rb_enc_str_new_cstr`:
@@ -752,7 +754,7 @@
 // * `rb_fix2long`:
 // * `RB_FIX2SHORT`:
 // * `rb_fix2short`:
-// * `rb_fix2str`:
+//! * `rb_fix2str`: See [`Integer::to_r_string_radix`].
 // * `RB_FIX2UINT`:
 // * `rb_fix2uint`:
 // * `RB_FIX2ULONG`:
@@ -875,7 +877,7 @@
 //! * `rb_hash_lookup2`: [`RHash::lookup2`].
 //! * `rb_hash_new`: [`RHash::new`].
 //! * `rb_hash_new_capa`: [`RHash::with_capacity`].
-// * `rb_hash_set_ifnone`:
+//! * `rb_hash_set_ifnone`: [`Ruby::hash_new_with_default`].
 //! * `rb_hash_size`: [`RHash::size`].
 //! * `rb_hash_size_num`: [`RHash::len`].
 // * `rb_hash_start`:
@@ -900,7 +902,7 @@
 // * `RB_INT2NUM`:
 // * `rb_int2num_inline`:
 // * `rb_Integer`:
-// * `rb_integer_pack`:
+//! * `rb_integer_pack`: [`Integer::to_be_bytes`], [`Integer::to_le_bytes`].
 // * `rb_integer_type_p`:
 // * `rb_integer_unpack`:
 //! * `rb_intern`: [`std::convert::From`].
@@ -988,7 +990,7 @@
 //! * `rb_iter_break`: See [`Error::iter_break`].
 //! * `rb_iter_break_value`: [`Error::iter_break`].
 // * `rb_ivar_count`:
-// * `rb_ivar_defined`:
+//! * `rb_ivar_defined`: [`Object::ivar_defined`].
 // * `rb_ivar_foreach`:
 //! * `rb_ivar_get`: [`Object::ivar_get`].
 //! * `rb_ivar_set`: [`Object::ivar_set`].
@@ -998,7 +1000,7 @@
 //! ## `rb_j`-`rb_k`
 //!
 //! * `rb_jump_tag`: Return [`Error`].
-// * `rb_keyword_given_p`:
+//! * `rb_keyword_given_p`: [`Ruby::keyword_given`].
 //!
 //! ## `rb_l`
 // * `rb_lastline_get`:
@@ -1034,8 +1036,8 @@
 // * `rb_mark_tbl`:
 // * `rb_mark_tbl_no_pin`:
 // * `rb_marshal_define_compat`:
-// * `rb_marshal_dump`:
-// * `rb_marshal_load`:
+//! * `rb_marshal_dump`: [`Ruby::marshal_dump`].
+//! * `rb_marshal_load`: [`Ruby::marshal_load`].
 // * `rb_match_busy`:
 // * `rb_memcicmp`:
 // * `rb_memerror`:
@@ -1061,7 +1063,7 @@
 // * `rb_method_basic_definition_p`:
 // * `rb_method_boundp`:
 // * `rb_method_call`:
-// * `rb_method_call_kw`:
+//! * `rb_method_call_kw`: [`BoundMethod::call`].
 // * `rb_method_call_with_block`:
 // * `rb_method_call_with_block_kw`:
 //! * `rb_module_new`: [`RModule::new`].
@@ -1123,7 +1125,7 @@
 // * `rb_notimplement`:
 // * `rb_num2char_inline`:
 // * `RB_NUM2CHR`:
-// * `rb_num2dbl`:
+//! * `rb_num2dbl`: [`RRational::to_f64`].
 // * `rb_num2fix`:
 // * `RB_NUM2INT`:
 // * `rb_num2int`:
@@ -1162,8 +1164,8 @@
 // * `rb_obj_call_init_kw`:
 // * `rb_obj_class`:
 //! * `rb_obj_classname`: [`Value::classname`].
-// * `rb_obj_clone`:
-// * `rb_obj_dup`:
+//! * `rb_obj_clone`: [`Value::clone_obj`].
+//! * `rb_obj_dup`: [`Value::dup`].
 // * `rb_obj_encoding`:
 // * `RB_OBJ_FREEZE`:
 //! * `rb_obj_freeze`: [`Value::freeze`].
@@ -1178,13 +1180,13 @@
 // * `rb_obj_init_copy`:
 // * `rb_obj_instance_eval`:
 // * `rb_obj_instance_exec`:
-// * `rb_obj_instance_variables`:
+//! * `rb_obj_instance_variables`: [`Object::instance_variables`].
 //! * `rb_obj_is_fiber`: [`Fiber::from_value`].
-// * `rb_obj_is_instance_of`:
+//! * `rb_obj_is_instance_of`: [`Value::is_instance_of`].
 //! * `rb_obj_is_kind_of`: [`Value::is_kind_of`].
 // * `rb_obj_is_method`:
 //! * `rb_obj_is_proc`: [`Proc::from_value`](block::Proc::from_value).
-// * `rb_obj_method`:
+//! * `rb_obj_method`: [`Value::method`].
 // * `rb_obj_method_arity`:
 // * `RB_OBJ_PROMOTED`:
 // * `RB_OBJ_PROMOTED_RAW`:
@@ -1255,7 +1257,7 @@
 // * `rb_ractor_stdout_set`:
 //! * `rb_raise`: Simmilar to returning [`Error`].
 // * `rb_random_base_init`:
-// * `rb_random_bytes`:
+//! * `rb_random_bytes`: [`Random::bytes`].
 // * `RB_RANDOM_DATA_INIT_PARENT`:
 // * `rb_random_int32`:
 // * `RB_RANDOM_INTERFACE_DECLARE`:
@@ -1264,8 +1266,8 @@
 // * `RB_RANDOM_INTERFACE_DEFINE_WITH_REAL`:
 // * `rb_random_mark`:
 // * `RB_RANDOM_PARENT`:
-// * `rb_random_real`:
-// * `rb_random_ulong_limited`:
+//! * `rb_random_real`: [`Random::rand_float`].
+//! * `rb_random_ulong_limited`: [`Random::rand_int`].
 // * `rb_rand_bytes_int32`:
 // * `rb_rand_if`:
 //! * `rb_range_beg_len`: [`Range::beg_len`].
@@ -1330,7 +1332,7 @@
 // * `rb_scan_args_length_mismatch`:
 // * `rb_set_class_path`:
 // * `rb_set_class_path_string`:
-// * `rb_set_end_proc`:
+//! * `rb_set_end_proc`: See [`Ruby::at_exit`].
 // * `rb_set_errinfo`:
 //! * `rb_singleton_class`: [`Object::singleton_class`].
 // * `rb_singleton_class_attached`:
@@ -1399,7 +1401,7 @@
 // * `rb_str_export`:
 // * `rb_str_export_locale`:
 // * `rb_str_export_to_enc`:
-// * `rb_str_format`:
+//! * `rb_str_format`: [`Ruby::str_format`].
 // * `rb_str_free`:
 // * `rb_str_freeze`:
 // * `rb_str_hash`:
@@ -1431,7 +1433,7 @@
 //! * `rb_str_strlen`: [`RString::length`].
 // * `rb_str_sublen`:
 // * `rb_str_subpos`:
-// * `rb_str_subseq`:
+//! * `rb_str_subseq`: Simmilar to [`RString::lines_iter`].
 // * `rb_str_substr`:
 // * `rb_str_succ`:
 //! * `rb_str_times`: [`RString::times`].
@@ -1492,6 +1494,7 @@
 // * `RB_SYMBOL_P`:
 // * `rb_symname_p`:
 // * `rb_sym_all_symbols`:
+//! * `rb_sym_to_proc`: [`Symbol::to_proc`].
 // * `rb_sym_to_s`:
 // * `rb_syserr_fail`:
 // * `rb_syserr_fail_str`:
@@ -1576,7 +1579,7 @@
 // * `rb_trap_exit`:
 // * `rb_type`:
 // * `rb_typeddata_inherited_p`:
-// * `rb_typeddata_is_kind_of`:
+//! * `rb_typeddata_is_kind_of`: [`typed_data::Obj::from_value`].
 // * `RB_TYPE_P`:
 // * `rb_type_p`:
 //!
@@ -1621,7 +1624,7 @@
 // * `rb_w32_fd_copy`:
 // * `rb_w32_fd_dup`:
 //! * `rb_waitpid`: [`Ruby::waitpid`].
-// * `rb_warn`:
+//! * `rb_warn`: See [`Ruby::warn`].
 //! * `rb_warning`: [`error::warning`].
 // * `rb_write_error`:
 // * `rb_write_error2`:
@@ -1792,13 +1795,18 @@
 mod macros;
 
 mod api;
+mod binding;
 pub mod block;
+mod bound_method;
 pub mod class;
+#[cfg(feature = "rust_decimal")]
+mod decimal;
 #[cfg(feature = "embed")]
 #[cfg_attr(docsrs, doc(cfg(feature = "embed")))]
 pub mod embed;
 pub mod encoding;
 mod enumerator;
+mod env;
 pub mod error;
 pub mod exception;
 #[cfg(any(ruby_gte_3_1, docsrs))]
@@ -1808,18 +1816,65 @@ mod float;
 pub mod gc;
 mod integer;
 mod into_value;
+#[cfg(feature = "serde_json")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde_json")))]
+pub mod json;
+pub mod marshal;
 pub mod method;
 pub mod module;
+#[cfg(feature = "msgpack")]
+#[cfg_attr(docsrs, doc(cfg(feature = "msgpack")))]
+pub mod msgpack;
 mod mutex;
 pub mod numeric;
 mod object;
+mod object_space;
 pub mod process;
 /// Traits that commonly should be in scope.
+///
+/// Many of Magnus' methods are defined on traits, which must be in scope for
+/// the methods to be available. Importing this module with
+/// `use magnus::prelude::*;` brings all the commonly needed traits in to
+/// scope.
+///
+/// The traits are imported anonymously (`as _`), so their methods are
+/// available without the trait names clashing with any names in your own
+/// code. Import traits by name from the crate root if you need to refer to
+/// them, e.g. in trait bounds.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{prelude::*, Error, RClass, Ruby};
+///
+/// fn example(ruby: &Ruby) -> Result<(), Error> {
+///     // `Module::define_method`, `Module::const_get`
+///     let class: RClass = ruby.class_object().const_get("String")?;
+///     // `ReprValue::funcall`
+///     let s: String = class.new_instance(("example",))?.funcall("upcase", ())?;
+///     assert_eq!(s, "EXAMPLE");
+///     // `IntoValue::into_value_with`, `IntoSymbol::into_symbol_with`
+///     let val = 42.into_value_with(ruby);
+///     let sym = "example".into_symbol_with(ruby);
+///     assert!(val.is_kind_of(ruby.class_integer()));
+///     assert_eq!(sym.name()?, "example");
+///
+///     Ok(())
+/// }
+/// # Ruby::init(example).unwrap()
+/// ```
 pub mod prelude {
     pub use crate::{
-        class::Class as _, encoding::EncodingCapable as _, module::Module as _,
-        numeric::Numeric as _, object::Object as _, try_convert::TryConvert as _,
-        value::ReprValue as _,
+        class::Class as _,
+        encoding::EncodingCapable as _,
+        into_value::{IntoValue as _, TryIntoValue as _},
+        module::Module as _,
+        numeric::Numeric as _,
+        object::Object as _,
+        r_string::IntoRString as _,
+        symbol::IntoSymbol as _,
+        try_convert::TryConvert as _,
+        value::{IntoId as _, ReprValue as _},
     };
 }
 pub mod r_array;
@@ -1832,9 +1887,11 @@ mod r_match;
 mod r_object;
 mod r_rational;
 pub mod r_regexp;
+mod r_set;
 pub mod r_string;
 pub mod r_struct;
 mod r_typed_data;
+mod random;
 mod range;
 #[cfg(feature = "rb-sys")]
 #[cfg_attr(docsrs, doc(cfg(feature = "rb-sys")))]
@@ -1847,14 +1904,49 @@ pub mod try_convert;
 pub mod typed_data;
 pub mod value;
 
-use std::{ffi::CString, mem::transmute, os::raw::c_int};
+// implementation details for proc macros, not part of the public API and not
+// covered by semver
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::value::private::ReprValue as ReprValueSealed;
+
+    #[inline]
+    pub fn assert_repr_value<T>()
+    where
+        T: crate::value::ReprValue,
+    {
+    }
+
+    /// Define a method generated by the `TypedData` derive macro, unless
+    /// `class` already defines a method `name` itself.
+    pub fn define_derived_method<M>(
+        class: crate::RClass,
+        name: &str,
+        func: M,
+    ) -> Result<(), crate::Error>
+    where
+        M: crate::method::Method,
+    {
+        use crate::{module::Module, value::ReprValue};
+
+        if class.funcall("method_defined?", (name, false))?
+            || class.funcall("private_method_defined?", (name, false))?
+        {
+            return Ok(());
+        }
+        class.define_method(name, func)
+    }
+}
+
+use std::{ffi::CString, mem::transmute, os::raw::c_int, panic::AssertUnwindSafe};
 
 use ::rb_sys::{
     rb_alias_variable, rb_backref_get, rb_call_super_kw, rb_current_receiver, rb_define_class,
-    rb_define_global_const, rb_define_global_function, rb_define_module, rb_define_variable,
-    rb_errinfo, rb_eval_string_protect, rb_require_string, rb_set_errinfo, VALUE,
+    rb_define_global_const, rb_define_global_function, rb_define_hooked_variable, rb_define_module,
+    rb_define_readonly_variable, rb_define_variable, rb_errinfo, rb_eval_string_protect,
+    rb_require_string, rb_set_errinfo, ID, VALUE,
 };
-pub use magnus_macros::{init, wrap, DataTypeFunctions, TypedData};
+pub use magnus_macros::{init, wrap, DataTypeFunctions, IvarView, Transparent, TypedData};
 
 #[cfg(any(ruby_gte_3_1, docsrs))]
 #[cfg_attr(docsrs, doc(cfg(ruby_gte_3_1)))]
@@ -1863,13 +1955,15 @@ pub use crate::fiber::Fiber;
 pub use crate::value::Flonum;
 pub use crate::{
     api::Ruby,
-    class::{Class, RClass},
-    enumerator::Enumerator,
+    binding::Binding,
+    bound_method::{BoundMethod, UnboundMethod},
+    class::{Class, ClassKey, RClass},
+    enumerator::{Enumerator, Yielder},
     error::Error,
     exception::{Exception, ExceptionClass},
     float::Float,
     integer::Integer,
-    into_value::{ArgList, IntoValue, IntoValueFromNative, KwArgs, RArrayArgList},
+    into_value::{ArgList, IntoValue, IntoValueFromNative, KwArgs, RArrayArgList, TryIntoValue},
     module::{Attr, Module, RModule},
     mutex::Mutex,
     numeric::Numeric,
@@ -1884,9 +1978,11 @@ pub use crate::{
     r_object::RObject,
     r_rational::RRational,
     r_regexp::RRegexp,
+    r_set::RSet,
     r_string::RString,
     r_struct::RStruct,
     r_typed_data::RTypedData,
+    random::Random,
     range::Range,
     symbol::Symbol,
     thread::Thread,
@@ -1896,7 +1992,7 @@ pub use crate::{
     value::{Fixnum, StaticSymbol, Value},
 };
 use crate::{
-    error::protect,
+    error::{protect, raise},
     method::Method,
     r_string::IntoRString,
     value::{private::ReprValue as _, IntoId, ReprValue},
@@ -2181,6 +2277,164 @@ impl Ruby {
         Ok(ptr)
     }
 
+    /// Define a global variable backed by Rust functions.
+    ///
+    /// `getter` is called each time the variable is read from Ruby, and
+    /// `setter` is called with the new value each time it is assigned. Errors
+    /// returned from either function are raised in Ruby.
+    ///
+    /// As with [`define_variable`](Ruby::define_variable) the preceeding `$`
+    /// of the global variable's name is optional.
+    ///
+    /// The functions are kept alive for the lifetime of the program. Ruby
+    /// values captured by the functions are not visible to the garbage
+    /// collector, so must be kept alive by other means, e.g. with
+    /// [`gc::register_mark_object`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    ///
+    /// use magnus::{prelude::*, rb_assert, Error, Ruby, Value};
+    ///
+    /// static LEVEL: AtomicUsize = AtomicUsize::new(1);
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     ruby.define_global_variable(
+    ///         "log_level",
+    ///         |_ruby| Ok(LEVEL.load(Ordering::Relaxed)),
+    ///         |ruby, val| {
+    ///             let level = usize::try_convert(val)?;
+    ///             if level > 5 {
+    ///                 return Err(Error::new(
+    ///                     ruby.exception_arg_error(),
+    ///                     "log level must be 0..=5",
+    ///                 ));
+    ///             }
+    ///             LEVEL.store(level, Ordering::Relaxed);
+    ///             Ok(())
+    ///         },
+    ///     )?;
+    ///
+    ///     rb_assert!(ruby, "$log_level == 1");
+    ///     ruby.eval::<Value>("$log_level = 3")?;
+    ///     assert_eq!(LEVEL.load(Ordering::Relaxed), 3);
+    ///     rb_assert!(ruby, "$log_level == 3");
+    ///     assert!(ruby.eval::<Value>("$log_level = 10").is_err());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn define_global_variable<G, S, T>(
+        &self,
+        name: &str,
+        getter: G,
+        setter: S,
+    ) -> Result<(), Error>
+    where
+        G: 'static + Send + Fn(&Ruby) -> Result<T, Error>,
+        S: 'static + Send + Fn(&Ruby, Value) -> Result<(), Error>,
+        T: IntoValue,
+    {
+        // Ruby passes a pointer to this as `data` to the getter/setter, and
+        // marks the first field as a `VALUE`.
+        #[repr(C)]
+        struct Hooks<G, S> {
+            marker: VALUE,
+            getter: G,
+            setter: S,
+        }
+
+        unsafe extern "C" fn get<G, S, T>(_id: ID, data: *mut VALUE) -> VALUE
+        where
+            G: Fn(&Ruby) -> Result<T, Error>,
+            T: IntoValue,
+        {
+            let ruby = Ruby::get_unchecked();
+            let hooks = &*(data as *const Hooks<G, S>);
+            let res = match std::panic::catch_unwind(AssertUnwindSafe(|| {
+                (hooks.getter)(&ruby).map(|v| ruby.into_value(v))
+            })) {
+                Ok(v) => v,
+                Err(e) => Err(Error::from_panic(e)),
+            };
+            match res {
+                Ok(v) => v.as_rb_value(),
+                Err(e) => raise(e),
+            }
+        }
+
+        unsafe extern "C" fn set<G, S>(val: VALUE, _id: ID, data: *mut VALUE)
+        where
+            S: Fn(&Ruby, Value) -> Result<(), Error>,
+        {
+            let ruby = Ruby::get_unchecked();
+            let hooks = &*(data as *const Hooks<G, S>);
+            let res = match std::panic::catch_unwind(AssertUnwindSafe(|| {
+                (hooks.setter)(&ruby, Value::new(val))
+            })) {
+                Ok(v) => v,
+                Err(e) => Err(Error::from_panic(e)),
+            };
+            if let Err(e) = res {
+                raise(e)
+            }
+        }
+
+        let name = CString::new(name).unwrap();
+        let hooks = Box::into_raw(Box::new(Hooks {
+            marker: self.qnil().as_rb_value(),
+            getter,
+            setter,
+        }));
+        unsafe {
+            rb_define_hooked_variable(
+                name.as_ptr(),
+                hooks as *mut VALUE,
+                Some(get::<G, S, T>),
+                Some(set::<G, S>),
+            );
+        }
+        Ok(())
+    }
+
+    /// Define a read-only global variable.
+    ///
+    /// `value` is registered with the garbage collector, so will never be
+    /// freed. Attempting to assign to the variable from Ruby raises a
+    /// `NameError`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{rb_assert, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     ruby.define_readonly_variable("example_version", ruby.str_new("1.0.0"))?;
+    ///     rb_assert!(ruby, r#"$example_version == "1.0.0""#);
+    ///     rb_assert!(ruby, "(($example_version = 1) rescue $!).is_a?(NameError)");
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn define_readonly_variable<T>(&self, name: &str, value: T) -> Result<(), Error>
+    where
+        T: IntoValue,
+    {
+        let value = self.into_value(value);
+        debug_assert_value!(value);
+        gc::register_mark_object(value);
+        let name = CString::new(name).unwrap();
+        let ptr = Box::into_raw(Box::new(value));
+        unsafe {
+            rb_define_readonly_variable(name.as_ptr(), ptr as *const VALUE);
+        }
+        Ok(())
+    }
+
     /// Alias the global variable `src` as `dst`.
     ///
     /// Unlike [`define_variable`](Ruby::define_variable), the preceeding `$`
@@ -2413,6 +2667,84 @@ impl Ruby {
             .and_then(TryConvert::try_convert)
     }
 
+    /// Finds and loads the given feature, relative to the Ruby file currently
+    /// being executed, if not already loaded.
+    ///
+    /// This calls Ruby's `Kernel#require_relative`. When called from an
+    /// extension's init function the path is relative to the Ruby file that
+    /// required the extension, allowing pure-Ruby code shipped alongside the
+    /// extension to be loaded. Errors with `LoadError` if the feature can
+    /// not be found, or there is no Ruby file to be relative to, e.g. when
+    /// Ruby is embedded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// #[magnus::init]
+    /// fn init(ruby: &Ruby) -> Result<(), Error> {
+    ///     // loads `helpers.rb` from the same directory as the file that
+    ///     // required this extension.
+    ///     ruby.require_relative("helpers")?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn require_relative<T>(&self, feature: T) -> Result<bool, Error>
+    where
+        T: IntoRString,
+    {
+        let feature = feature.into_r_string_with(self);
+        self.module_kernel().funcall("require_relative", (feature,))
+    }
+
+    /// Register `func` to be run when the Ruby VM exits.
+    ///
+    /// This registers `func` with Ruby's `Kernel#at_exit`, so it will be
+    /// run along with any `at_exit` blocks defined in Ruby code. These are run
+    /// in the reverse order they were registered, so `func` registered in
+    /// an extension's init function will generally be run after any
+    /// `at_exit` blocks defined by code using the extension.
+    ///
+    /// An error returned from `func` is reported by Ruby as an uncaught
+    /// exception, but does not prevent other `at_exit` blocks running.
+    ///
+    /// `func` is stored on the heap, where Ruby's garbage collector can not
+    /// see any Ruby values it captures. Any Ruby objects used by `func` must
+    /// be kept alive by other means, e.g. with
+    /// [`gc::register_mark_object`](crate::gc::register_mark_object), and
+    /// captured wrapped in [`Opaque`](value::Opaque).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     ruby.at_exit(|_ruby| {
+    ///         // flush caches, close native resources, etc.
+    ///         Ok(())
+    ///     })?;
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn at_exit<F>(&self, func: F) -> Result<(), Error>
+    where
+        F: 'static + Send + FnOnce(&Ruby) -> Result<(), Error>,
+    {
+        let mut func = Some(func);
+        let block = self.proc_from_fn(move |ruby, _args, _block| match func.take() {
+            Some(func) => func(ruby),
+            None => Ok(()),
+        });
+        let _: Value = self
+            .module_kernel()
+            .funcall_with_block("at_exit", (), block)?;
+        Ok(())
+    }
+
     /// Evaluate a string of Ruby code, converting the result to a `T`.
     ///
     /// Ruby will use the 'ASCII-8BIT' (aka binary) encoding for any Ruby
@@ -2459,6 +2791,44 @@ impl Ruby {
             other => Err(Error::from_tag(unsafe { transmute(other) })),
         }
     }
+
+    /// Evaluate a string of Ruby code, converting the result to a `T`, with
+    /// `filename` and `line` used as the location of the code in backtraces
+    /// and error messages.
+    ///
+    /// Unlike [`Ruby::eval`] the code is evaluated as a UTF-8 string, and may
+    /// contain null bytes.
+    ///
+    /// Errors if the conversion fails, or on an uncaught Ruby exception.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     assert_eq!(
+    ///         ruby.eval_with_location::<i64>("1 + 2", "generated.rb", 10)?,
+    ///         3
+    ///     );
+    ///
+    ///     let err = ruby
+    ///         .eval_with_location::<i64>("\n\nraise 'bang'", "generated.rb", 10)
+    ///         .unwrap_err();
+    ///     let backtrace: Vec<String> = err.value().unwrap().funcall("backtrace", ())?;
+    ///     assert!(backtrace[0].starts_with("generated.rb:12"));
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn eval_with_location<T>(&self, s: &str, filename: &str, line: usize) -> Result<T, Error>
+    where
+        T: TryConvert,
+    {
+        self.module_kernel()
+            .funcall("eval", (s, self.qnil(), filename, line))
+    }
 }
 
 /// Define a class in the root scope.
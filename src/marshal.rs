@@ -0,0 +1,100 @@
+//! Serialising Ruby objects with Ruby's `Marshal` format.
+//!
+//! See [`Ruby::marshal_dump`] and [`Ruby::marshal_load`].
+//!
+//! # Security
+//!
+//! Loading Marshal data can instantiate arbitrary classes and call methods
+//! on them, so must never be used with data from an untrusted source. For
+//! untrusted data use a format such as JSON instead.
+
+use rb_sys::{rb_marshal_dump, rb_marshal_load};
+
+use crate::{
+    error::{protect, Error},
+    into_value::IntoValue,
+    r_string::RString,
+    try_convert::TryConvert,
+    value::{private::ReprValue as _, Value},
+    Ruby,
+};
+
+/// # Marshal
+///
+/// Functions for serialising Ruby objects with Ruby's `Marshal` format.
+///
+/// See also the [`marshal`](self) module.
+impl Ruby {
+    /// Serialise `val` with Ruby's `Marshal.dump`, returning the result as a
+    /// binary String.
+    ///
+    /// Errors with `TypeError` if `val` is, or contains, an object that can
+    /// not be marshaled, such as a `Proc`, an `IO`, or an object with a
+    /// singleton class.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, rb_assert, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let dumped = ruby.marshal_dump(ruby.ary_from_vec(vec![1, 2, 3]))?;
+    ///     rb_assert!(ruby, "Marshal.load(dumped) == [1, 2, 3]", dumped);
+    ///
+    ///     let err = ruby
+    ///         .marshal_dump(ruby.proc_new(|_, _, _| ()))
+    ///         .unwrap_err();
+    ///     assert!(err.is_kind_of(ruby.exception_type_error()));
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn marshal_dump<T>(&self, val: T) -> Result<RString, Error>
+    where
+        T: IntoValue,
+    {
+        let val = self.into_value(val);
+        protect(|| unsafe {
+            RString::from_rb_value_unchecked(rb_marshal_dump(
+                val.as_rb_value(),
+                self.qnil().as_rb_value(),
+            ))
+        })
+    }
+
+    /// Deserialise `bytes` with Ruby's `Marshal.load`, converting the result
+    /// to `T`.
+    ///
+    /// **Warning:** never load Marshal data from an untrusted source, see
+    /// the [module docs](self#security).
+    ///
+    /// Errors if `bytes` is not valid Marshal data, references a class or
+    /// module that is not defined, or if the conversion to `T` fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let dumped = ruby.marshal_dump(("a", 1))?;
+    ///     let bytes = unsafe { dumped.as_slice() }.to_vec();
+    ///     let loaded: (String, i64) = ruby.marshal_load(&bytes)?;
+    ///     assert_eq!(loaded, (String::from("a"), 1));
+    ///
+    ///     assert!(ruby.marshal_load::<i64>(b"not marshal data").is_err());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn marshal_load<T>(&self, bytes: &[u8]) -> Result<T, Error>
+    where
+        T: TryConvert,
+    {
+        let s = self.str_from_slice(bytes);
+        protect(|| unsafe { Value::new(rb_marshal_load(s.as_rb_value())) })
+            .and_then(TryConvert::try_convert)
+    }
+}
@@ -14,9 +14,9 @@ use crate::{
         YieldValues,
     },
     error::{raise, Error},
-    into_value::{ArgList, IntoValue},
+    into_value::{ArgList, IntoValue, TryIntoValue},
     r_array::RArray,
-    try_convert::TryConvert,
+    try_convert::{with_path, TryConvert},
     value::{ReprValue, Value},
     Ruby,
 };
@@ -76,16 +76,16 @@ mod private {
 
     impl<T> ReturnValue for Result<T, Error>
     where
-        T: IntoValue,
+        T: TryIntoValue,
     {
         fn into_return_value(self) -> Result<Value, Error> {
-            self.map(|val| unsafe { val.into_value_unchecked() })
+            self.and_then(|val| val.try_into_value_with(&unsafe { Ruby::get_unchecked() }))
         }
     }
 
     impl<T> ReturnValue for T
     where
-        T: IntoValue,
+        T: TryIntoValue,
     {
         fn into_return_value(self) -> Result<Value, Error> {
             Ok(self).into_return_value()
@@ -235,6 +235,107 @@ pub trait Method: private::Method {}
 
 impl<T> Method for T where T: private::Method {}
 
+/// The signature of a method accepted by [`MethodSpec`].
+///
+/// This is the type returned by the [`method`](crate::method!) and
+/// [`function`](crate::function!) macros with an arity of `-1`.
+pub type CAryMethod = unsafe extern "C" fn(c_int, *const Value, Value) -> Value;
+
+#[derive(Clone, Copy, Debug)]
+enum Visibility {
+    Public,
+    Private,
+    Protected,
+}
+
+/// A description of a method, for defining many methods at once with
+/// [`Module::define_methods`](crate::Module::define_methods).
+///
+/// Every method is registered with the same [`CAryMethod`] signature, taking
+/// its arguments as a slice, no matter how many arguments it accepts. Code
+/// generating a large number of bindings can use this to avoid instantiating
+/// generic registration code for each arity, at the cost of checking the
+/// number of arguments at runtime (e.g. with
+/// [`scan_args`](crate::scan_args::scan_args)).
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{
+///     method, method::MethodSpec, prelude::*, rb_assert, scan_args::scan_args, Error, RClass,
+///     Ruby, Value,
+/// };
+///
+/// fn add(rb_self: i64, args: &[Value]) -> Result<i64, Error> {
+///     let args = scan_args::<(i64,), (), (), (), (), ()>(args)?;
+///     Ok(rb_self + args.required.0)
+/// }
+///
+/// fn sub(rb_self: i64, args: &[Value]) -> Result<i64, Error> {
+///     let args = scan_args::<(i64,), (), (), (), (), ()>(args)?;
+///     Ok(rb_self - args.required.0)
+/// }
+///
+/// fn example(ruby: &Ruby) -> Result<(), Error> {
+///     let class = RClass::new(ruby.class_integer())?;
+///     class.define_methods(&[
+///         MethodSpec::new("add", method!(add, -1)),
+///         MethodSpec::new("sub", method!(sub, -1)).private(),
+///     ])?;
+///
+///     rb_assert!(ruby, "class.public_method_defined?(:add)", class);
+///     rb_assert!(ruby, "class.private_method_defined?(:sub)", class);
+///
+///     Ok(())
+/// }
+/// # Ruby::init(example).unwrap()
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct MethodSpec {
+    name: &'static str,
+    func: CAryMethod,
+    visibility: Visibility,
+}
+
+impl MethodSpec {
+    /// Create a new `MethodSpec` for a public method `name`, implemented by
+    /// `func`.
+    pub const fn new(name: &'static str, func: CAryMethod) -> Self {
+        Self {
+            name,
+            func,
+            visibility: Visibility::Public,
+        }
+    }
+
+    /// Define the method as private.
+    pub const fn private(self) -> Self {
+        Self {
+            visibility: Visibility::Private,
+            ..self
+        }
+    }
+
+    /// Define the method as protected.
+    pub const fn protected(self) -> Self {
+        Self {
+            visibility: Visibility::Protected,
+            ..self
+        }
+    }
+
+    pub(crate) fn define<T>(&self, module: T) -> Result<(), Error>
+    where
+        T: crate::module::Module,
+    {
+        match self.visibility {
+            Visibility::Public => module.define_method(self.name, self.func),
+            Visibility::Private => module.define_private_method(self.name, self.func),
+            Visibility::Protected => module.define_protected_method(self.name, self.func),
+        }
+    }
+}
+
 /// Trait marking types that can be returned to Ruby.
 ///
 /// Implemented for the following types:
@@ -249,6 +350,9 @@ impl<T> Method for T where T: private::Method {}
 /// * `Result<YieldSplat<I>, magnus::Error>`
 ///
 /// where `I` implements `Iterator<Item = T>` and `T` implements [`IntoValue`].
+/// `T` and `Result<T, magnus::Error>` may also be any type implementing
+/// [`TryIntoValue`](crate::TryIntoValue), with conversion errors raised as
+/// Ruby exceptions.
 ///
 /// When is `Err(magnus::Error)` returned to Ruby it will be conveted to and
 /// raised as a Ruby exception.
@@ -257,6 +361,28 @@ impl<T> Method for T where T: private::Method {}
 /// [`Iterator`] to be bridged to Ruby method that calls a block with the
 /// elements of that [`Iterator`].
 ///
+/// Tuples of types implementing [`IntoValue`] also implement [`IntoValue`],
+/// converting to an Array, so can be used to return multiple values, like
+/// Ruby's `Integer#divmod`.
+///
+/// ```
+/// use magnus::{function, prelude::*, rb_assert, Error, Ruby};
+///
+/// fn divmod(a: i64, b: i64) -> (i64, i64) {
+///     (a.div_euclid(b), a.rem_euclid(b))
+/// }
+///
+/// fn example(ruby: &Ruby) -> Result<(), Error> {
+///     ruby.define_global_function("divmod", function!(divmod, 2));
+///
+///     rb_assert!(ruby, "divmod(7, 2) == [3, 1]");
+///     rb_assert!(ruby, "q, r = divmod(9, 4); q == 2 && r == 1");
+///
+///     Ok(())
+/// }
+/// # Ruby::init(example).unwrap()
+/// ```
+///
 /// Note: functions without a specified return value will return `()`. `()`
 /// implements [`IntoValue`] (converting to `nil`).
 pub trait ReturnValue: private::ReturnValue {}
@@ -488,7 +614,7 @@ where
     fn call_convert_value(self, rb_self: Value, args: RArray) -> Result<Value, Error> {
         (self)(
             TryConvert::try_convert(rb_self)?,
-            TryConvert::try_convert(args.as_value())?,
+            with_path(args.as_value())?,
         )
         .into_return_value()
     }
@@ -535,7 +661,7 @@ where
         (self)(
             &Ruby::get_with(rb_self),
             TryConvert::try_convert(rb_self)?,
-            TryConvert::try_convert(args.as_value())?,
+            with_path(args.as_value())?,
         )
         .into_return_value()
     }
@@ -779,9 +905,15 @@ seq!(N in 0..=16 {
 /// and [`get_kwargs`](crate::scan_args::get_kwargs) for more complex method
 /// signatures.
 ///
+/// With `-2` the arguments can also be taken as any type that can be
+/// converted from an Array, such as a `Vec<U>`, to convert each argument
+/// like Ruby's `*args` splat. Should an argument fail to convert, the error
+/// will include its index, e.g. `at [1]: no implicit conversion of String
+/// into Integer`.
+///
 /// | Arity | Signature                                                 |
 /// |-------|-----------------------------------------------------------|
-/// |    -2 | `fn(rb_self: T, arguments: RArray) -> Result<R, Error>`   |
+/// |    -2 | `fn(rb_self: T, arguments: A) -> Result<R, Error>`        |
 /// |    -1 | `fn(rb_self: T, arguments: &[Value]) -> Result<R, Error>` |
 /// |     0 | `fn(rb_self: T) -> Result<R, Error>`                      |
 /// |     1 | `fn(rb_self: T, arg1: U) -> Result<R, Error>`             |
@@ -790,6 +922,7 @@ seq!(N in 0..=16 {
 /// |    16 | ...                                                       |
 ///
 /// Where `T`, `U`, `V` and so on are any types that implement `TryConvert`,
+/// `A` is [`RArray`] or any type that implements `TryConvert` from an Array,
 /// and `R` implements [`IntoValue`]. It is also possible to return just `R`
 /// rather than a `Result` for functions that will never error, and omit the
 /// return value (i.e. return `()`) for a function that returns `nil` to Ruby.
@@ -1303,7 +1436,7 @@ where
 {
     #[inline]
     fn call_convert_value(self, args: RArray) -> Result<Value, Error> {
-        (self)(TryConvert::try_convert(args.as_value())?).into_return_value()
+        (self)(with_path(args.as_value())?).into_return_value()
     }
 
     #[inline]
@@ -1342,11 +1475,7 @@ where
 {
     #[inline]
     fn call_convert_value(self, args: RArray) -> Result<Value, Error> {
-        (self)(
-            &Ruby::get_with(args),
-            TryConvert::try_convert(args.as_value())?,
-        )
-        .into_return_value()
+        (self)(&Ruby::get_with(args), with_path(args.as_value())?).into_return_value()
     }
 
     #[inline]
@@ -1562,9 +1691,15 @@ seq!(N in 0..=16 {
 /// and [`get_kwargs`](crate::scan_args::get_kwargs) for more complex method
 /// signatures.
 ///
+/// With `-2` the arguments can also be taken as any type that can be
+/// converted from an Array, such as a `Vec<U>`, to convert each argument
+/// like Ruby's `*args` splat. Should an argument fail to convert, the error
+/// will include its index, e.g. `at [1]: no implicit conversion of String
+/// into Integer`.
+///
 /// | Arity | Signature                                     |
 /// |-------|-----------------------------------------------|
-/// |    -2 | `fn(arguments: RArray) -> Result<R, Error>`   |
+/// |    -2 | `fn(arguments: A) -> Result<R, Error>`        |
 /// |    -1 | `fn(arguments: &[Value]) -> Result<R, Error>` |
 /// |     0 | `fn()-> Result<R, Error>`                     |
 /// |     1 | `fn(arg1: T) -> Result<R, Error>`             |
@@ -1573,6 +1708,7 @@ seq!(N in 0..=16 {
 /// |    16 | ...                                           |
 ///
 /// Where `T`, `U`, and so on are any types that implement `TryConvert`,
+/// `A` is [`RArray`] or any type that implements `TryConvert` from an Array,
 /// and `R` implements [`IntoValue`]. It is also possible to return just `R`
 /// rather than a `Result` for functions that will never error, and omit the
 /// return value (i.e. return `()`) for a function that returns `nil` to Ruby.
@@ -1595,6 +1731,23 @@ seq!(N in 0..=16 {
 /// # let cleanup = unsafe { magnus::embed::init() };
 /// # init(&cleanup);
 /// ```
+///
+/// Taking any number of arguments, each converted to `i64`:
+///
+/// ```
+/// fn sum(nums: Vec<i64>) -> i64 {
+///     nums.iter().sum()
+/// }
+///
+/// #[magnus::init]
+/// fn init(ruby: &magnus::Ruby) {
+///     ruby.define_global_function("sum", magnus::function!(sum, -2));
+/// }
+/// # let cleanup = unsafe { magnus::embed::init() };
+/// # init(&cleanup);
+/// # magnus::rb_assert!("sum(1, 2, 3) == 6");
+/// # magnus::rb_assert!(r#"(sum(1, "2") rescue $!.message) == "at [1]: no implicit conversion of String into Integer""#);
+/// ```
 #[macro_export]
 macro_rules! function {
     ($name:expr, -2) => {{
@@ -5,7 +5,8 @@
 use std::{ffi::CString, fmt, mem::transmute, os::raw::c_int};
 
 use rb_sys::{
-    rb_alias, rb_attr, rb_class_inherited_p, rb_const_get, rb_const_set, rb_define_class_id_under,
+    rb_alias, rb_attr, rb_autoload, rb_autoload_p, rb_class_inherited_p, rb_class_instance_methods,
+    rb_const_get, rb_const_set, rb_cvar_get, rb_cvar_set, rb_define_class_id_under,
     rb_define_method_id, rb_define_module_function, rb_define_module_id_under,
     rb_define_private_method, rb_define_protected_method, rb_include_module, rb_mComparable,
     rb_mEnumerable, rb_mErrno, rb_mFileTest, rb_mGC, rb_mKernel, rb_mMath, rb_mProcess,
@@ -14,17 +15,20 @@ use rb_sys::{
 };
 
 use crate::{
+    bound_method::UnboundMethod,
     class::{Class, RClass},
     error::{protect, Error},
     exception::ExceptionClass,
     into_value::IntoValue,
-    method::Method,
+    method::{Method, MethodSpec},
     object::Object,
     r_array::RArray,
+    r_string::RString,
+    symbol::{IntoSymbol, Symbol},
     try_convert::TryConvert,
     value::{
         private::{self, ReprValue as _},
-        IntoId, NonZeroValue, ReprValue, Value,
+        IntoId, NonZeroValue, ReprValue, StaticSymbol, Value,
     },
     Ruby,
 };
@@ -216,6 +220,11 @@ impl TryConvert for RModule {
 pub trait Module: Object + ReprValue + Copy {
     /// Define a class in `self`'s scope.
     ///
+    /// If a class named `name` is already defined in `self`'s scope with the
+    /// same `superclass` the existing class is returned, allowing a class to
+    /// be re-opened. Returns `Err` if a constant `name` exists but is not a
+    /// class, or is a class with a different superclass.
+    ///
     /// # Examples
     ///
     /// ```
@@ -230,6 +239,30 @@ pub trait Module: Object + ReprValue + Copy {
     /// }
     /// # Ruby::init(example).unwrap()
     /// ```
+    ///
+    /// Deeply nested namespaces can be defined top-down:
+    ///
+    /// ```
+    /// use magnus::{prelude::*, rb_assert, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let class = ruby
+    ///         .define_module("MyGem")?
+    ///         .define_module("Parsers")?
+    ///         .define_class("Csv", ruby.class_object())?;
+    ///     rb_assert!(ruby, "MyGem::Parsers::Csv == class", class);
+    ///
+    ///     let reopened = ruby
+    ///         .class_object()
+    ///         .const_get::<_, magnus::RModule>("MyGem")?
+    ///         .define_module("Parsers")?
+    ///         .define_class("Csv", ruby.class_object())?;
+    ///     assert!(reopened.equal(class)?);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
     fn define_class<T>(self, name: T, superclass: RClass) -> Result<RClass, Error>
     where
         T: IntoId,
@@ -249,6 +282,10 @@ pub trait Module: Object + ReprValue + Copy {
 
     /// Define a module in `self`'s scope.
     ///
+    /// If a module named `name` is already defined in `self`'s scope the
+    /// existing module is returned. Returns `Err` if a constant `name` exists
+    /// but is not a module.
+    ///
     /// # Examples
     ///
     /// ```
@@ -383,6 +420,80 @@ pub trait Module: Object + ReprValue + Copy {
         Ok(())
     }
 
+    /// Include Ruby's `Enumerable` module into `self`.
+    ///
+    /// `Enumerable` requires an instance method `each` that yields each
+    /// element in turn. With `each` defined, methods like `map`, `select`,
+    /// `include?`, and `to_a` will be available. `min`, `max`, and `sort`
+    /// additionally require the elements to implement `<=>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{block::Yield, method, prelude::*, rb_assert, Error, RClass, Ruby, Value};
+    ///
+    /// fn each(ruby: &Ruby, rb_self: Value) -> Yield<impl Iterator<Item = u8>> {
+    ///     if ruby.block_given() {
+    ///         Yield::Iter(1..=3)
+    ///     } else {
+    ///         Yield::Enumerator(rb_self.enumeratorize("each", ()))
+    ///     }
+    /// }
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let class = RClass::new(ruby.class_object())?;
+    ///     class.define_method("each", method!(each, 0))?;
+    ///     class.include_enumerable()?;
+    ///
+    ///     let obj = class.new_instance(())?;
+    ///     rb_assert!(ruby, "obj.map { |i| i * 2 } == [2, 4, 6]", obj);
+    ///     rb_assert!(ruby, "obj.select(&:odd?) == [1, 3]", obj);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    fn include_enumerable(self) -> Result<(), Error> {
+        self.include_module(Ruby::get_with(self).module_enumerable())
+    }
+
+    /// Include Ruby's `Comparable` module into `self`.
+    ///
+    /// `Comparable` requires an instance method `<=>` that returns a negative
+    /// integer, `0`, or a positive integer when `self` is less than, equal
+    /// to, or greater than the argument, or `nil` when the two can not be
+    /// compared. With `<=>` defined, the methods `<`, `<=`, `==`, `>=`, `>`,
+    /// `between?`, and `clamp` will be available.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{method, prelude::*, rb_assert, Error, RClass, Ruby, Value};
+    ///
+    /// fn cmp(rb_self: Value, other: Value) -> Result<Option<i64>, Error> {
+    ///     let a: i64 = rb_self.funcall("value", ())?;
+    ///     let b: Option<i64> = other.check_funcall("value", ()).transpose()?;
+    ///     Ok(b.map(|b| a.cmp(&b) as i64))
+    /// }
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let class: RClass = ruby.eval("Struct.new(:value)")?;
+    ///     class.define_method("<=>", method!(cmp, 1))?;
+    ///     class.include_comparable()?;
+    ///
+    ///     let a = class.new_instance((1,))?;
+    ///     let b = class.new_instance((2,))?;
+    ///     rb_assert!(ruby, "a < b", a, b);
+    ///     rb_assert!(ruby, "b.between?(a, b)", a, b);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    fn include_comparable(self) -> Result<(), Error> {
+        self.include_module(Ruby::get_with(self).module_comparable())
+    }
+
     /// Set the value for the constant `name` within `self`'s scope.
     ///
     /// # Examples
@@ -449,6 +560,166 @@ pub trait Module: Object + ReprValue + Copy {
         res.and_then(TryConvert::try_convert)
     }
 
+    /// Register `path` to be loaded (with `require`) the first time the
+    /// constant `name` is referenced within `self`'s scope.
+    ///
+    /// This is equivalent to Ruby's `Module#autoload`. The file is not loaded
+    /// when this function is called, so a missing file will not be reported
+    /// here. Instead the `LoadError` will be raised when the constant is
+    /// first referenced, and returned as an `Err` from functions such as
+    /// [`Module::const_get`].
+    ///
+    /// Does nothing if the constant `name` is already defined.
+    ///
+    /// Returns `Err` with an `ArgumentError` if `path` is empty or contains
+    /// a null byte, or a `NameError` if `name` is not a valid constant name.
+    ///
+    /// See also [`Module::autoload_path`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, Error, Ruby, Value};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let module = ruby.define_module("Example")?;
+    ///     module.autoload("Missing", "example/missing")?;
+    ///
+    ///     let err = module.const_get::<_, Value>("Missing").unwrap_err();
+    ///     assert!(err.is_kind_of(ruby.exception_load_error()));
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    fn autoload<T>(self, name: T, path: &str) -> Result<(), Error>
+    where
+        T: IntoId,
+    {
+        debug_assert_value!(self);
+        let handle = Ruby::get_with(self);
+        let id = name.into_id_with(&handle);
+        let path = CString::new(path)
+            .map_err(|e| Error::new(handle.exception_arg_error(), e.to_string()))?;
+        protect(|| {
+            unsafe { rb_autoload(self.as_rb_value(), id.as_rb_id(), path.as_ptr()) };
+            handle.qnil()
+        })?;
+        Ok(())
+    }
+
+    /// Return the path registered to be autoloaded for the constant `name`
+    /// within `self`'s scope, or `None` if there is no autoload registered
+    /// for `name`, or the constant has already been loaded.
+    ///
+    /// This is equivalent to Ruby's `Module#autoload?`.
+    ///
+    /// See also [`Module::autoload`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let module = ruby.define_module("Example")?;
+    ///     module.autoload("Lazy", "example/lazy")?;
+    ///
+    ///     assert_eq!(
+    ///         module.autoload_path("Lazy")?.map(|s| s.to_string()).transpose()?,
+    ///         Some(String::from("example/lazy"))
+    ///     );
+    ///     assert!(module.autoload_path("Other")?.is_none());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    fn autoload_path<T>(self, name: T) -> Result<Option<RString>, Error>
+    where
+        T: IntoId,
+    {
+        debug_assert_value!(self);
+        let id = name.into_id_with(&Ruby::get_with(self));
+        protect(|| unsafe { Value::new(rb_autoload_p(self.as_rb_value(), id.as_rb_id())) })
+            .map(RString::from_value)
+    }
+
+    /// Get the value for the class variable `name` within `self`'s scope.
+    ///
+    /// Note, the `@@` is part of the name.
+    ///
+    /// Returns `Err` with a `NameError` if the class variable is
+    /// uninitialised.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, Error, RClass, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let class: RClass = ruby.eval(
+    ///         r#"
+    ///             class Example
+    ///               @@value = "foo"
+    ///             end
+    ///             Example
+    ///         "#,
+    ///     )?;
+    ///
+    ///     assert_eq!(class.cvar_get::<_, String>("@@value")?, "foo");
+    ///     assert!(class.cvar_get::<_, String>("@@missing").is_err());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    fn cvar_get<T, U>(self, name: T) -> Result<U, Error>
+    where
+        T: IntoId,
+        U: TryConvert,
+    {
+        debug_assert_value!(self);
+        let id = name.into_id_with(&Ruby::get_with(self));
+        let res = unsafe { protect(|| Value::new(rb_cvar_get(self.as_rb_value(), id.as_rb_id()))) };
+        res.and_then(TryConvert::try_convert)
+    }
+
+    /// Set the value for the class variable `name` within `self`'s scope.
+    ///
+    /// Note, the `@@` is part of the name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, rb_assert, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let class = ruby.define_class("Example", ruby.class_object())?;
+    ///     class.cvar_set("@@value", 42)?;
+    ///
+    ///     rb_assert!(ruby, "Example.class_variable_get(:@@value) == 42");
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    fn cvar_set<T, U>(self, name: T, value: U) -> Result<(), Error>
+    where
+        T: IntoId,
+        U: IntoValue,
+    {
+        debug_assert_value!(self);
+        let handle = Ruby::get_with(self);
+        let id = name.into_id_with(&handle);
+        let value = value.into_value_with(&handle);
+        protect(|| {
+            unsafe { rb_cvar_set(self.as_rb_value(), id.as_rb_id(), value.as_rb_value()) };
+            handle.qnil()
+        })?;
+        Ok(())
+    }
+
     /// Returns whether or not `self` inherits from `other`.
     ///
     /// Classes including a module are considered to inherit from that module.
@@ -675,6 +946,129 @@ pub trait Module: Object + ReprValue + Copy {
         Ok(())
     }
 
+    /// Define all the methods described by `specs` in `self`'s scope.
+    ///
+    /// See [`MethodSpec`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{method, method::MethodSpec, prelude::*, rb_assert, Error, Ruby, Value};
+    ///
+    /// fn greet(_rb_self: Value, args: &[Value]) -> String {
+    ///     format!("hello, {} args", args.len())
+    /// }
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let class = ruby.define_class("Greeter", ruby.class_object())?;
+    ///     class.define_methods(&[MethodSpec::new("greet", method!(greet, -1))])?;
+    ///     rb_assert!(ruby, r#"Greeter.new.greet(1, 2) == "hello, 2 args""#);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    fn define_methods(self, specs: &[MethodSpec]) -> Result<(), Error> {
+        for spec in specs {
+            spec.define(self)?;
+        }
+        Ok(())
+    }
+
+    /// Make the existing methods `names` in `self`'s scope private.
+    ///
+    /// This is equivalent to calling `private` with `names` in the body of a
+    /// Ruby class/module definition.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{function, prelude::*, Error, RClass, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let class = RClass::new(ruby.class_object())?;
+    ///     class.define_method("helper", function!(|| 1, 0))?;
+    ///     class.define_method("other_helper", function!(|| 2, 0))?;
+    ///     class.set_private(["helper", "other_helper"])?;
+    ///
+    ///     let obj = class.new_instance(())?;
+    ///     assert!(!obj.respond_to("helper", false)?);
+    ///     assert!(obj.respond_to("helper", true)?);
+    ///     assert!(!obj.respond_to("other_helper", false)?);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    fn set_private<I, T>(self, names: I) -> Result<(), Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: IntoSymbol,
+    {
+        set_visibility(self, "private", names)
+    }
+
+    /// Make the existing methods `names` in `self`'s scope protected.
+    ///
+    /// This is equivalent to calling `protected` with `names` in the body of
+    /// a Ruby class/module definition.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{function, prelude::*, Error, RClass, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let class = RClass::new(ruby.class_object())?;
+    ///     class.define_method("helper", function!(|| 1, 0))?;
+    ///     class.set_protected(["helper"])?;
+    ///
+    ///     let obj = class.new_instance(())?;
+    ///     assert!(!obj.respond_to("helper", false)?);
+    ///     assert!(obj.respond_to("helper", true)?);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    fn set_protected<I, T>(self, names: I) -> Result<(), Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: IntoSymbol,
+    {
+        set_visibility(self, "protected", names)
+    }
+
+    /// Make the existing methods `names` in `self`'s scope public.
+    ///
+    /// This is equivalent to calling `public` with `names` in the body of a
+    /// Ruby class/module definition.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{function, prelude::*, Error, RClass, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let class = RClass::new(ruby.class_object())?;
+    ///     class.define_private_method("helper", function!(|| 1, 0))?;
+    ///     class.set_public(["helper"])?;
+    ///
+    ///     let obj = class.new_instance(())?;
+    ///     assert!(obj.respond_to("helper", false)?);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    fn set_public<I, T>(self, names: I) -> Result<(), Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: IntoSymbol,
+    {
+        set_visibility(self, "public", names)
+    }
+
     /// Define public accessor methods for the attribute `name`.
     ///
     /// `name` should be **without** the preceding `@`.
@@ -754,6 +1148,164 @@ pub trait Module: Object + ReprValue + Copy {
         })?;
         Ok(())
     }
+
+    /// Return the instance method `name` of `self` as an [`UnboundMethod`].
+    ///
+    /// Returns `Err` if `self` has no instance method `name`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let unbound = ruby.class_string().instance_method("upcase")?;
+    ///     let res: String = unbound.bind(ruby.str_new("example"))?.call(())?;
+    ///     assert_eq!(res, "EXAMPLE");
+    ///
+    ///     assert!(ruby.class_string().instance_method("non_existant").is_err());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    fn instance_method<T>(self, name: T) -> Result<UnboundMethod, Error>
+    where
+        T: IntoId,
+    {
+        let handle = Ruby::get_with(self);
+        let id = name.into_id_with(&handle);
+        self.funcall("instance_method", (id,))
+    }
+
+    /// Return the names of the public and protected instance methods of
+    /// `self`, as with Ruby's `Module#instance_methods`.
+    ///
+    /// If `include_super` is `true` methods inherited from ancestors are
+    /// included, otherwise only the methods defined in `self` are returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, Error, RClass, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let class: RClass = ruby.eval(
+    ///         r#"
+    ///             Class.new do
+    ///               def foo; end
+    ///               def bar; end
+    ///               private def baz; end
+    ///             end
+    ///         "#,
+    ///     )?;
+    ///
+    ///     let mut names = class
+    ///         .instance_methods(false)
+    ///         .into_iter()
+    ///         .map(|name| name.name().map(|n| n.to_owned()))
+    ///         .collect::<Result<Vec<_>, _>>()?;
+    ///     names.sort();
+    ///     assert_eq!(names, vec!["bar", "foo"]);
+    ///
+    ///     assert!(class.instance_methods(true).len() > 2);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    fn instance_methods(self, include_super: bool) -> Vec<StaticSymbol> {
+        let handle = Ruby::get_with(self);
+        let args = [handle.into_value(include_super).as_rb_value()];
+        unsafe {
+            RArray::from_rb_value_unchecked(rb_class_instance_methods(
+                args.len() as c_int,
+                args.as_ptr(),
+                self.as_rb_value(),
+            ))
+            .to_vec()
+            .unwrap()
+        }
+    }
+
+    /// Define `method_missing` and `respond_to_missing?` for `self`.
+    ///
+    /// `func` is called with the name of the missing method as a [`Symbol`]
+    /// and the remaining arguments. `respond_to` is called with the name of a
+    /// method and should return `true` if `func` handles that method. This
+    /// makes `respond_to?` and `method` work for the dynamically handled
+    /// methods.
+    ///
+    /// Both methods are defined as private, matching Ruby's own
+    /// `method_missing` and `respond_to_missing?`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, rb_assert, Error, RClass, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let class = RClass::new(ruby.class_object())?;
+    ///     class.define_method_missing(
+    ///         |ruby, name, args| {
+    ///             if name.name()?.starts_with("echo_") {
+    ///                 Ok(ruby.ary_new_from_values(args).as_value())
+    ///             } else {
+    ///                 Err(Error::new(
+    ///                     ruby.exception_no_method_error(),
+    ///                     format!("undefined method `{}'", name),
+    ///                 ))
+    ///             }
+    ///         },
+    ///         |name| name.name().map_or(false, |n| n.starts_with("echo_")),
+    ///     )?;
+    ///
+    ///     let obj = class.new_instance(())?;
+    ///     rb_assert!(ruby, "obj.echo_foo(1, 2) == [1, 2]", obj);
+    ///     rb_assert!(ruby, "obj.respond_to?(:echo_bar)", obj);
+    ///     rb_assert!(ruby, "!obj.respond_to?(:baz)", obj);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    fn define_method_missing<F, P>(self, func: F, respond_to: P) -> Result<(), Error>
+    where
+        F: 'static + Send + Fn(&Ruby, Symbol, &[Value]) -> Result<Value, Error>,
+        P: 'static + Send + Fn(Symbol) -> bool,
+    {
+        fn method_name(ruby: &Ruby, args: &[Value]) -> Result<Symbol, Error> {
+            args.first()
+                .ok_or_else(|| Error::new(ruby.exception_arg_error(), "no method name given"))
+                .and_then(|v| Symbol::try_convert(*v))
+        }
+
+        let handle = Ruby::get_with(self);
+        let method_missing = handle.proc_from_fn(move |ruby, args, _block| {
+            let name = method_name(ruby, args)?;
+            func(ruby, name, &args[1..])
+        });
+        let respond_to_missing =
+            handle.proc_from_fn(move |ruby, args, _block| method_name(ruby, args).map(&respond_to));
+        let _: Value = self.funcall("define_method", ("method_missing", method_missing))?;
+        let _: Value =
+            self.funcall("define_method", ("respond_to_missing?", respond_to_missing))?;
+        let _: Value = self.funcall("private", ("method_missing", "respond_to_missing?"))?;
+        Ok(())
+    }
+}
+
+fn set_visibility<M, I, T>(module: M, visibility: &str, names: I) -> Result<(), Error>
+where
+    M: Module,
+    I: IntoIterator<Item = T>,
+    T: IntoSymbol,
+{
+    let handle = Ruby::get_with(module);
+    for name in names {
+        let _: Value = module.funcall(visibility, (name.into_symbol_with(&handle),))?;
+    }
+    Ok(())
 }
 
 /// Argument for [`define_attr`](Module::define_attr).
@@ -0,0 +1,393 @@
+//! Serialising Ruby objects to and from [MessagePack](https://msgpack.org).
+//!
+//! With the `msgpack` feature enabled, Ruby objects can be packed with
+//! [`Ruby::msgpack_pack`] and unpacked with [`Ruby::msgpack_unpack`],
+//! directly, without an intermediate Rust representation.
+//!
+//! | Ruby                       | MessagePack                  |
+//! | -------------------------- | ---------------------------- |
+//! | `nil`                      | nil                          |
+//! | `true`/`false`             | boolean                      |
+//! | `Integer`                  | int                          |
+//! | `Float`                    | float 64                     |
+//! | binary (ASCII-8BIT) String | bin                          |
+//! | other String               | str                          |
+//! | `Symbol`                   | ext type [`SYMBOL_EXT_TYPE`] |
+//! | `Array`                    | array                        |
+//! | `Hash`                     | map                          |
+//!
+//! Unpacked strings are tagged as UTF-8. Other types, and Integers outside
+//! the range of an `i64`/`u64`, can not be packed.
+
+use std::fmt;
+
+use rmp::{encode, Marker};
+
+use crate::{
+    encoding::EncodingCapable,
+    error::Error,
+    float::Float,
+    integer::Integer,
+    r_array::RArray,
+    r_hash::{ForEach, RHash},
+    r_string::RString,
+    symbol::Symbol,
+    value::{Qfalse, Qtrue, ReprValue, Value},
+    Ruby,
+};
+
+/// The MessagePack extension type used for Symbols.
+///
+/// Symbols are packed as their name, with this extension type, so that they
+/// are unpacked as Symbols rather than Strings. This matches the type
+/// commonly registered for Symbols with the `msgpack` gem.
+pub const SYMBOL_EXT_TYPE: i8 = 0;
+
+/// The maximum depth of nested Arrays and Hashes that will be packed or
+/// unpacked.
+pub const MAX_NESTING: usize = 128;
+
+/// # MessagePack
+///
+/// Functions for serialising Ruby objects to and from MessagePack.
+///
+/// See also the [`msgpack`](self) module.
+impl Ruby {
+    /// Serialise `val` to MessagePack, returning the result as a binary
+    /// String.
+    ///
+    /// See the [module docs](self) for the supported types. Errors if `val`
+    /// is, or contains, an unsupported type, or if Arrays and Hashes are
+    /// nested more than [`MAX_NESTING`] deep.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{rb_assert, Error, Ruby, Value};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let val: Value = ruby.eval(r#"{"a" => [1, 2.5, nil]}"#)?;
+    ///     let packed = ruby.msgpack_pack(val)?;
+    ///     rb_assert!(
+    ///         ruby,
+    ///         r#"packed == "\x81\xA1a\x93\x01\xCB\x40\x04\x00\x00\x00\x00\x00\x00\xC0".b"#,
+    ///         packed
+    ///     );
+    ///
+    ///     assert!(ruby.msgpack_pack(ruby.eval::<Value>("Object.new")?).is_err());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn msgpack_pack<T>(&self, val: T) -> Result<RString, Error>
+    where
+        T: ReprValue,
+    {
+        let mut buf = Vec::new();
+        pack(self, &mut buf, val.as_value(), 0)?;
+        Ok(self.enc_str_new(buf, self.ascii8bit_encoding()))
+    }
+
+    /// Deserialise MessagePack `bytes` into Ruby objects.
+    ///
+    /// See the [module docs](self) for how MessagePack types are converted.
+    /// Errors if `bytes` isn't valid MessagePack, contains an extension type
+    /// other than [`SYMBOL_EXT_TYPE`], contains trailing data, or has Arrays and
+    /// maps nested more than [`MAX_NESTING`] deep.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, rb_assert, Error, Ruby, Value};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let val: Value = ruby.eval(r#"{a: ["b", 1, -1, 1.5, true]}"#)?;
+    ///     let packed = ruby.msgpack_pack(val)?;
+    ///     let res = ruby.msgpack_unpack(unsafe { packed.as_slice() })?;
+    ///     assert!(res.eql(val)?);
+    ///
+    ///     assert!(ruby.msgpack_unpack(b"\x92\x01").is_err());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn msgpack_unpack(&self, bytes: &[u8]) -> Result<Value, Error> {
+        let mut unpacker = Unpacker {
+            handle: self,
+            bytes,
+        };
+        let val = unpacker.unpack(0)?;
+        if !unpacker.bytes.is_empty() {
+            return Err(unpacker.error("trailing data after MessagePack value"));
+        }
+        Ok(val)
+    }
+}
+
+fn pack(handle: &Ruby, buf: &mut Vec<u8>, val: Value, depth: usize) -> Result<(), Error> {
+    if val.is_nil() {
+        encode::write_nil(buf).map_err(write_err(handle))?;
+    } else if Qtrue::from_value(val).is_some() {
+        encode::write_bool(buf, true).map_err(write_err(handle))?;
+    } else if Qfalse::from_value(val).is_some() {
+        encode::write_bool(buf, false).map_err(write_err(handle))?;
+    } else if let Some(i) = Integer::from_value(val) {
+        if let Ok(i) = i.to_i64() {
+            encode::write_sint(buf, i).map_err(write_err(handle))?;
+        } else if let Ok(u) = i.to_u64() {
+            encode::write_uint(buf, u).map_err(write_err(handle))?;
+        } else {
+            return Err(Error::new(
+                handle.exception_range_error(),
+                format!("integer {} too big to pack as MessagePack", i),
+            ));
+        }
+    } else if let Some(f) = Float::from_value(val) {
+        encode::write_f64(buf, f.to_f64()).map_err(write_err(handle))?;
+    } else if let Some(s) = RString::from_value(val) {
+        let bytes = unsafe { s.as_slice() };
+        if s.enc_get() == handle.ascii8bit_encindex() {
+            encode::write_bin_len(buf, len(handle, bytes.len())?).map_err(write_err(handle))?;
+        } else {
+            encode::write_str_len(buf, len(handle, bytes.len())?).map_err(write_err(handle))?;
+        }
+        buf.extend_from_slice(bytes);
+    } else if let Some(sym) = Symbol::from_value(val) {
+        let name = sym.name()?;
+        encode::write_ext_meta(buf, len(handle, name.len())?, SYMBOL_EXT_TYPE)
+            .map_err(write_err(handle))?;
+        buf.extend_from_slice(name.as_bytes());
+    } else if let Some(ary) = RArray::from_value(val) {
+        check_depth(handle, depth)?;
+        encode::write_array_len(buf, len(handle, ary.len())?).map_err(write_err(handle))?;
+        for i in 0..ary.len() {
+            pack(handle, buf, ary.entry(i as isize)?, depth + 1)?;
+        }
+    } else if let Some(hash) = RHash::from_value(val) {
+        check_depth(handle, depth)?;
+        encode::write_map_len(buf, len(handle, hash.len())?).map_err(write_err(handle))?;
+        hash.foreach(|key: Value, value: Value| {
+            pack(handle, buf, key, depth + 1)?;
+            pack(handle, buf, value, depth + 1)?;
+            Ok(ForEach::Continue)
+        })?;
+    } else {
+        return Err(Error::new(
+            handle.exception_type_error(),
+            format!("can't pack {} as MessagePack", unsafe { val.classname() }),
+        ));
+    }
+    Ok(())
+}
+
+fn write_err<E>(handle: &Ruby) -> impl Fn(E) -> Error + '_
+where
+    E: fmt::Display,
+{
+    |e| Error::new(handle.exception_runtime_error(), e.to_string())
+}
+
+fn check_depth(handle: &Ruby, depth: usize) -> Result<(), Error> {
+    if depth >= MAX_NESTING {
+        return Err(Error::new(
+            handle.exception_arg_error(),
+            format!("nesting of {} is too deep", depth + 1),
+        ));
+    }
+    Ok(())
+}
+
+fn len(handle: &Ruby, len: usize) -> Result<u32, Error> {
+    u32::try_from(len).map_err(|_| {
+        Error::new(
+            handle.exception_range_error(),
+            "length too long to pack as MessagePack",
+        )
+    })
+}
+
+struct Unpacker<'a, 'b> {
+    handle: &'a Ruby,
+    bytes: &'b [u8],
+}
+
+impl<'b> Unpacker<'_, 'b> {
+    fn error(&self, msg: &'static str) -> Error {
+        Error::new(self.handle.exception_arg_error(), msg)
+    }
+
+    fn take(&mut self, n: usize) -> Result<&'b [u8], Error> {
+        if self.bytes.len() < n {
+            return Err(self.error("unexpected end of MessagePack data"));
+        }
+        let (head, tail) = self.bytes.split_at(n);
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    fn take_array<const N: usize>(&mut self) -> Result<[u8; N], Error> {
+        Ok(self.take(N)?.try_into().unwrap())
+    }
+
+    fn take_len(&mut self, size: usize) -> Result<usize, Error> {
+        Ok(match size {
+            1 => self.take_array::<1>()?[0] as usize,
+            2 => u16::from_be_bytes(self.take_array()?) as usize,
+            _ => u32::from_be_bytes(self.take_array()?) as usize,
+        })
+    }
+
+    fn unpack(&mut self, depth: usize) -> Result<Value, Error> {
+        let handle = self.handle;
+        let marker = Marker::from_u8(self.take_array::<1>()?[0]);
+        let val = match marker {
+            Marker::Null => handle.qnil().as_value(),
+            Marker::True => handle.qtrue().as_value(),
+            Marker::False => handle.qfalse().as_value(),
+            Marker::FixPos(n) => handle.integer_from_u64(n.into()).as_value(),
+            Marker::FixNeg(n) => handle.integer_from_i64(n.into()).as_value(),
+            Marker::U8 => handle
+                .integer_from_u64(self.take_array::<1>()?[0].into())
+                .as_value(),
+            Marker::U16 => handle
+                .integer_from_u64(u16::from_be_bytes(self.take_array()?).into())
+                .as_value(),
+            Marker::U32 => handle
+                .integer_from_u64(u32::from_be_bytes(self.take_array()?).into())
+                .as_value(),
+            Marker::U64 => handle
+                .integer_from_u64(u64::from_be_bytes(self.take_array()?))
+                .as_value(),
+            Marker::I8 => handle
+                .integer_from_i64(i8::from_be_bytes(self.take_array()?).into())
+                .as_value(),
+            Marker::I16 => handle
+                .integer_from_i64(i16::from_be_bytes(self.take_array()?).into())
+                .as_value(),
+            Marker::I32 => handle
+                .integer_from_i64(i32::from_be_bytes(self.take_array()?).into())
+                .as_value(),
+            Marker::I64 => handle
+                .integer_from_i64(i64::from_be_bytes(self.take_array()?))
+                .as_value(),
+            Marker::F32 => handle
+                .float_from_f64(f32::from_be_bytes(self.take_array()?).into())
+                .as_value(),
+            Marker::F64 => handle
+                .float_from_f64(f64::from_be_bytes(self.take_array()?))
+                .as_value(),
+            Marker::FixStr(n) => self.unpack_str(n.into())?,
+            Marker::Str8 => {
+                let n = self.take_len(1)?;
+                self.unpack_str(n)?
+            }
+            Marker::Str16 => {
+                let n = self.take_len(2)?;
+                self.unpack_str(n)?
+            }
+            Marker::Str32 => {
+                let n = self.take_len(4)?;
+                self.unpack_str(n)?
+            }
+            Marker::Bin8 => {
+                let n = self.take_len(1)?;
+                self.unpack_bin(n)?
+            }
+            Marker::Bin16 => {
+                let n = self.take_len(2)?;
+                self.unpack_bin(n)?
+            }
+            Marker::Bin32 => {
+                let n = self.take_len(4)?;
+                self.unpack_bin(n)?
+            }
+            Marker::FixArray(n) => self.unpack_array(n.into(), depth)?,
+            Marker::Array16 => {
+                let n = self.take_len(2)?;
+                self.unpack_array(n, depth)?
+            }
+            Marker::Array32 => {
+                let n = self.take_len(4)?;
+                self.unpack_array(n, depth)?
+            }
+            Marker::FixMap(n) => self.unpack_map(n.into(), depth)?,
+            Marker::Map16 => {
+                let n = self.take_len(2)?;
+                self.unpack_map(n, depth)?
+            }
+            Marker::Map32 => {
+                let n = self.take_len(4)?;
+                self.unpack_map(n, depth)?
+            }
+            Marker::FixExt1 => self.unpack_ext(1)?,
+            Marker::FixExt2 => self.unpack_ext(2)?,
+            Marker::FixExt4 => self.unpack_ext(4)?,
+            Marker::FixExt8 => self.unpack_ext(8)?,
+            Marker::FixExt16 => self.unpack_ext(16)?,
+            Marker::Ext8 => {
+                let n = self.take_len(1)?;
+                self.unpack_ext(n)?
+            }
+            Marker::Ext16 => {
+                let n = self.take_len(2)?;
+                self.unpack_ext(n)?
+            }
+            Marker::Ext32 => {
+                let n = self.take_len(4)?;
+                self.unpack_ext(n)?
+            }
+            Marker::Reserved => return Err(self.error("invalid MessagePack marker")),
+        };
+        Ok(val)
+    }
+
+    fn unpack_str(&mut self, n: usize) -> Result<Value, Error> {
+        let bytes = self.take(n)?;
+        Ok(self
+            .handle
+            .enc_str_new(bytes, self.handle.utf8_encoding())
+            .as_value())
+    }
+
+    fn unpack_bin(&mut self, n: usize) -> Result<Value, Error> {
+        let bytes = self.take(n)?;
+        Ok(self
+            .handle
+            .enc_str_new(bytes, self.handle.ascii8bit_encoding())
+            .as_value())
+    }
+
+    fn unpack_array(&mut self, n: usize, depth: usize) -> Result<Value, Error> {
+        check_depth(self.handle, depth)?;
+        // don't trust `n` for the capacity, it may be larger than the input
+        let ary = self.handle.ary_new_capa(n.min(self.bytes.len()));
+        for _ in 0..n {
+            ary.push(self.unpack(depth + 1)?)?;
+        }
+        Ok(ary.as_value())
+    }
+
+    fn unpack_map(&mut self, n: usize, depth: usize) -> Result<Value, Error> {
+        check_depth(self.handle, depth)?;
+        let hash = self.handle.hash_new();
+        for _ in 0..n {
+            let key = self.unpack(depth + 1)?;
+            let val = self.unpack(depth + 1)?;
+            hash.aset(key, val)?;
+        }
+        Ok(hash.as_value())
+    }
+
+    fn unpack_ext(&mut self, n: usize) -> Result<Value, Error> {
+        let ty = i8::from_be_bytes(self.take_array()?);
+        let bytes = self.take(n)?;
+        if ty != SYMBOL_EXT_TYPE {
+            return Err(self.error("unknown MessagePack extension type"));
+        }
+        let name = std::str::from_utf8(bytes)
+            .map_err(|_| self.error("invalid UTF-8 in MessagePack Symbol"))?;
+        Ok(self.handle.to_symbol(name).as_value())
+    }
+}
@@ -6,6 +6,8 @@ use rb_sys::{rb_num_coerce_bin, rb_num_coerce_bit, rb_num_coerce_cmp, rb_num_coe
 
 use crate::{
     error::{protect, Error},
+    float::Float,
+    integer::Integer,
     into_value::IntoValue,
     try_convert::TryConvert,
     value::{
@@ -222,6 +224,129 @@ pub trait Numeric: ReprValue + Copy {
         })
         .and_then(TryConvert::try_convert)
     }
+
+    /// Returns whether `self` is zero.
+    ///
+    /// For `Integer` and `Float` this is checked directly, for other types
+    /// this calls Ruby's `zero?` method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroI64;
+    ///
+    /// use magnus::{Error, Numeric, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     assert!(Numeric::is_zero(ruby.integer_from_i64(0))?);
+    ///     assert!(!ruby.rational_new(1, NonZeroI64::new(2).unwrap()).is_zero()?);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    fn is_zero(self) -> Result<bool, Error> {
+        let val = self.as_value();
+        if let Some(i) = Integer::from_value(val) {
+            Ok(i.is_zero())
+        } else if let Some(f) = Float::from_value(val) {
+            Ok(f.is_zero())
+        } else {
+            val.funcall("zero?", ())
+        }
+    }
+
+    /// Returns whether `self` is greater than zero.
+    ///
+    /// For `Integer` and `Float` this is checked directly, for other types
+    /// this calls Ruby's `positive?` method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroI64;
+    ///
+    /// use magnus::{Error, Numeric, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     assert!(Numeric::is_positive(ruby.float_from_f64(1.5))?);
+    ///     assert!(!ruby.rational_new(-1, NonZeroI64::new(2).unwrap()).is_positive()?);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    fn is_positive(self) -> Result<bool, Error> {
+        let val = self.as_value();
+        if let Some(i) = Integer::from_value(val) {
+            Ok(i.is_positive())
+        } else if let Some(f) = Float::from_value(val) {
+            Ok(f.is_positive())
+        } else {
+            val.funcall("positive?", ())
+        }
+    }
+
+    /// Returns whether `self` is less than zero.
+    ///
+    /// For `Integer` and `Float` this is checked directly, for other types
+    /// this calls Ruby's `negative?` method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroI64;
+    ///
+    /// use magnus::{Error, Numeric, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     assert!(Numeric::is_negative(ruby.integer_from_i64(-3))?);
+    ///     assert!(ruby.rational_new(-1, NonZeroI64::new(2).unwrap()).is_negative()?);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    fn is_negative(self) -> Result<bool, Error> {
+        let val = self.as_value();
+        if let Some(i) = Integer::from_value(val) {
+            Ok(i.is_negative())
+        } else if let Some(f) = Float::from_value(val) {
+            Ok(f.is_negative())
+        } else {
+            val.funcall("negative?", ())
+        }
+    }
+
+    /// Returns whether `self` is an integer, as with Ruby's `integer?`
+    /// method.
+    ///
+    /// This is `true` for `Integer` and `false` for `Float`, both checked
+    /// directly. For other types this calls Ruby's `integer?` method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Numeric, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     assert!(ruby.integer_from_i64(1).is_integer()?);
+    ///     assert!(!ruby.float_from_f64(1.0).is_integer()?);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    fn is_integer(self) -> Result<bool, Error> {
+        let val = self.as_value();
+        if Integer::from_value(val).is_some() {
+            Ok(true)
+        } else if Float::from_value(val).is_some() {
+            Ok(false)
+        } else {
+            val.funcall("integer?", ())
+        }
+    }
 }
 
 /// Wrapper type for a Value known to be an instance of Ruby’s Numeric class.
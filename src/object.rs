@@ -1,7 +1,8 @@
 use std::{ffi::CString, mem::transmute};
 
 use rb_sys::{
-    rb_define_singleton_method, rb_extend_object, rb_ivar_get, rb_ivar_set, rb_singleton_class,
+    rb_define_singleton_method, rb_extend_object, rb_ivar_defined, rb_ivar_get, rb_ivar_set,
+    rb_obj_instance_variables, rb_singleton_class,
 };
 
 use crate::{
@@ -10,8 +11,9 @@ use crate::{
     into_value::IntoValue,
     method::Method,
     module::RModule,
+    r_array::RArray,
     try_convert::TryConvert,
-    value::{private::ReprValue as _, IntoId, ReprValue, Value},
+    value::{private::ReprValue as _, IntoId, ReprValue, StaticSymbol, Value},
     Ruby,
 };
 
@@ -127,6 +129,80 @@ pub trait Object: ReprValue + Copy {
         res.and_then(TryConvert::try_convert)
     }
 
+    /// Returns whether the instance variable `name` has been set within
+    /// `self`'s scope, as with Ruby's `Object#instance_variable_defined?`.
+    ///
+    /// Unlike [`ivar_get`](Object::ivar_get), this distinguishes an instance
+    /// variable that has been set to `nil` from one that has never been set.
+    ///
+    /// Note, the `@` is part of the name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, Error, RObject, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let obj: RObject = ruby.eval("Object.new")?;
+    ///     assert!(!obj.ivar_defined("@cache"));
+    ///
+    ///     obj.ivar_set("@cache", ruby.qnil())?;
+    ///     assert!(obj.ivar_defined("@cache"));
+    ///     assert!(obj.ivar_get::<_, Option<String>>("@cache")?.is_none());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    fn ivar_defined<T>(self, name: T) -> bool
+    where
+        T: IntoId,
+    {
+        debug_assert_value!(self);
+        let id = name.into_id_with(&Ruby::get_with(self));
+        unsafe { Value::new(rb_ivar_defined(self.as_rb_value(), id.as_rb_id())).to_bool() }
+    }
+
+    /// Return the names of the instance variables set within `self`'s scope,
+    /// as with Ruby's `Object#instance_variables`.
+    ///
+    /// The names include the `@`, and can be passed to
+    /// [`ivar_get`](Object::ivar_get).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, Error, RObject, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let obj: RObject = ruby.eval("Object.new")?;
+    ///     obj.ivar_set("@name", "example")?;
+    ///     obj.ivar_set("@count", 3)?;
+    ///
+    ///     let names = obj
+    ///         .instance_variables()
+    ///         .into_iter()
+    ///         .map(|name| name.name().map(|n| n.to_owned()))
+    ///         .collect::<Result<Vec<_>, _>>()?;
+    ///     assert_eq!(names, vec!["@name", "@count"]);
+    ///
+    ///     for name in obj.instance_variables() {
+    ///         let _: magnus::Value = obj.ivar_get(name)?;
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    fn instance_variables(self) -> Vec<StaticSymbol> {
+        debug_assert_value!(self);
+        unsafe {
+            RArray::from_rb_value_unchecked(rb_obj_instance_variables(self.as_rb_value()))
+                .to_vec()
+                .unwrap()
+        }
+    }
+
     /// Set the value for the instance variable `name` within `self`'s scope.
     ///
     /// Note, the `@` is part of the name. Setting an instance variable without
@@ -184,16 +260,29 @@ pub trait Object: ReprValue + Copy {
 
     /// Finds or creates the singleton class of `self`.
     ///
-    /// Returns `Err` if `self` can not have a singleton class.
+    /// Methods and constants defined on the returned class apply only to
+    /// `self`. See also [`define_singleton_method`](Object::define_singleton_method).
+    ///
+    /// Returns `Err` with a `TypeError` if `self` can not have a singleton
+    /// class. This is rare, as the immediate values that can't have a
+    /// singleton class, such as `Integer`s and `Symbol`s, do not implement
+    /// `Object`.
     ///
     /// # Examples
     ///
     /// ```
-    /// use magnus::{prelude::*, Error, Ruby};
+    /// use magnus::{function, prelude::*, rb_assert, Error, RObject, Ruby};
     ///
     /// fn example(ruby: &Ruby) -> Result<(), Error> {
     ///     assert!(ruby.str_new("example").singleton_class().is_ok());
     ///
+    ///     let obj: RObject = ruby.eval("Object.new")?;
+    ///     let other: RObject = ruby.eval("Object.new")?;
+    ///     let singleton = obj.singleton_class()?;
+    ///     singleton.define_method("greet", function!(|| "hello", 0))?;
+    ///     rb_assert!(ruby, r#"obj.greet == "hello""#, obj);
+    ///     rb_assert!(ruby, "!other.respond_to?(:greet)", other);
+    ///
     ///     Ok(())
     /// }
     /// # Ruby::init(example).unwrap()
@@ -0,0 +1,87 @@
+//! Functions for inspecting objects in the Ruby heap.
+
+use std::slice;
+
+use crate::{
+    block::block_call_closure,
+    class::RClass,
+    error::Error,
+    module::{Module, RModule},
+    value::{IntoId, ReprValue, Value},
+    Ruby,
+};
+
+/// # ObjectSpace
+///
+/// Functions for inspecting the objects in Ruby's heap.
+impl Ruby {
+    /// Call `func` with each live object in the Ruby heap that is an
+    /// instance of `class` (or one of its subclasses), or every object if
+    /// `class` is `None`.
+    ///
+    /// This is equivalent to Ruby's `ObjectSpace.each_object`. Immediate
+    /// values (such as `nil`, `true`, Symbols, and small Integers) and
+    /// objects internal to the interpreter are never passed to `func`.
+    ///
+    /// To stop iterating early return the error from
+    /// [`Ruby::iter_break_value`] from `func`. Any other error returned by
+    /// `func` will stop iteration and be returned from this function.
+    ///
+    /// # Performance
+    ///
+    /// This walks the entire Ruby heap, which is slow and scales with the
+    /// total number of objects, not the number of matching objects. It is
+    /// intended for debugging and introspection, not for use in hot code.
+    /// Objects that are unreachable but not yet collected by the GC may be
+    /// included.
+    ///
+    /// `ObjectSpace.each_object` is always available in CRuby, but may be
+    /// disabled or restricted in other Ruby implementations (e.g. JRuby
+    /// requires `ObjectSpace` support to be enabled with `-X+O`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, Error, RClass, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let class: RClass = ruby.eval("class Example; end; Example")?;
+    ///     let _a = class.new_instance(())?;
+    ///     let _b = class.new_instance(())?;
+    ///
+    ///     let mut count = 0;
+    ///     ruby.each_object(Some(class), |_| {
+    ///         count += 1;
+    ///         Ok(())
+    ///     })?;
+    ///     assert!(count >= 2);
+    ///
+    ///     let mut seen = 0;
+    ///     ruby.each_object(Some(class), |_| {
+    ///         seen += 1;
+    ///         Err(ruby.iter_break_value(()))
+    ///     })?;
+    ///     assert_eq!(seen, 1);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn each_object<F>(&self, class: Option<RClass>, func: F) -> Result<(), Error>
+    where
+        F: FnMut(Value) -> Result<(), Error>,
+    {
+        let object_space = self.class_object().const_get::<_, RModule>("ObjectSpace")?;
+        let class = class.map(|c| c.as_value());
+        let args = match class {
+            Some(ref class) => slice::from_ref(class),
+            None => &[],
+        };
+        block_call_closure(
+            object_space.as_value(),
+            "each_object".into_id_with(self),
+            args,
+            func,
+        )
+    }
+}
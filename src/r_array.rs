@@ -21,13 +21,15 @@ use crate::{
     enumerator::Enumerator,
     error::{protect, Error},
     gc,
+    integer::Integer,
     into_value::{IntoValue, IntoValueFromNative},
     object::Object,
+    r_hash::{ForEach, RHash},
     r_string::{IntoRString, RString},
-    try_convert::{TryConvert, TryConvertOwned},
+    try_convert::{annotate_path, TryConvert, TryConvertOwned},
     value::{
         private::{self, ReprValue as _},
-        NonZeroValue, ReprValue, Value,
+        Lazy, NonZeroValue, ReprValue, Value,
     },
     Ruby,
 };
@@ -57,9 +59,43 @@ impl Ruby {
         unsafe { RArray::from_rb_value_unchecked(rb_ary_new()) }
     }
 
+    /// Return a shared, frozen, empty `RArray`.
+    ///
+    /// The same instance is returned on every call, so this avoids allocating
+    /// a new Array when returning `[]`, e.g. as a "no results" value from a
+    /// frequently called method. As the Array is frozen any attempt to modify
+    /// it will raise `FrozenError`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, rb_assert, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let a = ruby.ary_empty_frozen();
+    ///     let b = ruby.ary_empty_frozen();
+    ///     assert!(a.is_empty());
+    ///     assert!(a.is_frozen());
+    ///     rb_assert!(ruby, "a.equal?(b)", a, b);
+    ///     assert!(a.push(1).is_err());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn ary_empty_frozen(&self) -> RArray {
+        static EMPTY: Lazy<RArray> = Lazy::new(|ruby| ruby.ary_new().freeze());
+        self.get_inner(&EMPTY)
+    }
+
     /// Create a new empty `RArray` with capacity for `n` elements
     /// pre-allocated.
     ///
+    /// This is the equivalent of [`Vec::with_capacity`]. Ruby's C API doesn't
+    /// provide a way to reserve additional capacity for an existing array, to
+    /// add many elements to an existing array with a single allocation see
+    /// [`RArray::append_all`].
+    ///
     /// # Examples
     ///
     /// ```
@@ -69,6 +105,12 @@ impl Ruby {
     ///     let ary = ruby.ary_new_capa(16);
     ///     assert!(ary.is_empty());
     ///
+    ///     for i in 0..16 {
+    ///         // doesn't reallocate
+    ///         ary.push(i)?;
+    ///     }
+    ///     assert_eq!(ary.len(), 16);
+    ///
     ///     Ok(())
     /// }
     /// # Ruby::init(example).unwrap()
@@ -101,6 +143,10 @@ impl Ruby {
 
     /// Create a new `RArray` containing the elements in `slice`.
     ///
+    /// The elements are copied into the new array in a single call to Ruby,
+    /// so this is faster than creating an array with
+    /// [`ary_new_capa`](Ruby::ary_new_capa) and pushing each element.
+    ///
     /// # Examples
     ///
     /// ```
@@ -563,6 +609,45 @@ impl RArray {
         unsafe { Value::new(rb_ary_includes(self.as_rb_value(), val.as_rb_value())).to_bool() }
     }
 
+    /// Returns the index of the first element in `self` that is `==` to
+    /// `val`, or `None` if there is no such element, as with Ruby's
+    /// `Array#index`.
+    ///
+    /// Errors if any call to `==` raises.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, RArray, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let ary: RArray = ruby.eval(r#"[:foo, "bar", 2, "bar"]"#)?;
+    ///     assert_eq!(ary.index_of("bar")?, Some(1));
+    ///     // 2.0 == 2 in Ruby
+    ///     assert_eq!(ary.index_of(2.0)?, Some(2));
+    ///     assert_eq!(ary.index_of("foo")?, None);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn index_of<T>(self, val: T) -> Result<Option<usize>, Error>
+    where
+        T: IntoValue,
+    {
+        let val = Ruby::get_with(self).into_value(val);
+        let mut i = 0;
+        // `==` may modify `self`, so re-check the length every iteration
+        while i < self.len() {
+            let entry = unsafe { Value::new(rb_ary_entry(self.as_rb_value(), i as c_long)) };
+            if entry.equal(val)? {
+                return Ok(Some(i));
+            }
+            i += 1;
+        }
+        Ok(None)
+    }
+
     /// Concatenate elements from the slice `s` to `self`.
     ///
     /// Returns `Err` if `self` is frozen.
@@ -636,6 +721,62 @@ impl RArray {
         Ok(())
     }
 
+    /// Add all the items from the iterator `items` to the end of `self`.
+    ///
+    /// Space for the number of items given by the lower bound of the
+    /// iterator's [`size_hint`](Iterator::size_hint) is allocated before any
+    /// items are added, so only a single allocation is required for
+    /// iterators of a known size. Until all items have been added, any
+    /// elements not yet set will be `nil`.
+    ///
+    /// Returns `Err` if `self` is frozen.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{rb_assert, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let ary = ruby.ary_from_vec(vec![1, 2]);
+    ///     ary.append_all(3..=1000)?;
+    ///     rb_assert!(ruby, "ary == (1..1000).to_a", ary);
+    ///
+    ///     ary.append_all((0..5).filter(|i| i % 2 == 0))?;
+    ///     rb_assert!(ruby, "ary.last(3) == [0, 2, 4]", ary);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn append_all<I, T>(self, items: I) -> Result<(), Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: IntoValue,
+    {
+        let handle = Ruby::get_with(self);
+        let iter = items.into_iter();
+        let start = self.len();
+        let reserved = start + iter.size_hint().0;
+        if reserved > start {
+            self.resize(reserved)?;
+        }
+        let mut i = start;
+        for item in iter {
+            if i < reserved {
+                self.store(i as isize, handle.into_value(item))?;
+            } else {
+                self.push(handle.into_value(item))?;
+            }
+            i += 1;
+        }
+        // a misbehaving iterator may have returned fewer items than its
+        // lower bound
+        if i < reserved {
+            self.resize(i)?;
+        }
+        Ok(())
+    }
+
     /// Create a new `RArray` containing the both the elements in `self` and
     /// `other`.
     ///
@@ -1145,7 +1286,63 @@ impl RArray {
     where
         T: TryConvertOwned,
     {
-        unsafe { self.as_slice().iter().map(|v| T::try_convert(*v)).collect() }
+        unsafe {
+            self.as_slice()
+                .iter()
+                .enumerate()
+                .map(|(i, v)| annotate_path(T::try_convert(*v), || format!("[{}]", i)))
+                .collect()
+        }
+    }
+
+    /// Convert `self`, an Array of Integers in the range `0..=255`, to a Rust
+    /// vector of bytes.
+    ///
+    /// Errors with a `RangeError` giving the index of the offending element
+    /// if any element is not an Integer, or is out of the range of a byte.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, RArray, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let ary: RArray = ruby.eval("[104, 105, 0, 255]")?;
+    ///     assert_eq!(ary.to_bytes()?, vec![104, 105, 0, 255]);
+    ///
+    ///     let ary: RArray = ruby.eval("[1, 2, 256]")?;
+    ///     let err = ary.to_bytes().unwrap_err();
+    ///     assert!(err.is_kind_of(ruby.exception_range_error()));
+    ///     assert_eq!(
+    ///         err.to_string(),
+    ///         "RangeError: element at index 2 is not a byte (0..=255), got 256"
+    ///     );
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn to_bytes(self) -> Result<Vec<u8>, Error> {
+        unsafe {
+            self.as_slice()
+                .iter()
+                .enumerate()
+                .map(|(i, v)| {
+                    Integer::from_value(*v)
+                        .and_then(|int| int.to_u8().ok())
+                        .ok_or_else(|| {
+                            Error::new(
+                                Ruby::get_with(self).exception_range_error(),
+                                format!(
+                                    "element at index {} is not a byte (0..=255), got {}",
+                                    i,
+                                    v.inspect()
+                                ),
+                            )
+                        })
+                })
+                .collect()
+        }
     }
 
     /// Convert `self` to a Rust array of [`Value`]s, of length `N`.
@@ -1169,10 +1366,11 @@ impl RArray {
     /// ```
     pub fn to_value_array<const N: usize>(self) -> Result<[Value; N], Error> {
         unsafe {
-            self.as_slice().try_into().map_err(|_| {
+            let slice = self.as_slice();
+            slice.try_into().map_err(|_| {
                 Error::new(
                     Ruby::get_with(self).exception_type_error(),
-                    format!("expected Array of length {}", N),
+                    format!("expected Array of length {}, got {}", N, slice.len()),
                 )
             })
         }
@@ -1192,7 +1390,9 @@ impl RArray {
     ///     let ary: RArray = ruby.eval("[1, 2, 3]")?;
     ///     assert_eq!(ary.to_array::<i64, 3>()?, [1, 2, 3]);
     ///     assert!(ary.to_array::<i64, 2>().is_err());
-    ///     assert!(ary.to_array::<i64, 4>().is_err());
+    ///
+    ///     let err = ary.to_array::<i64, 4>().unwrap_err();
+    ///     assert!(err.to_string().contains("expected Array of length 4, got 3"));
     ///
     ///     Ok(())
     /// }
@@ -1207,15 +1407,15 @@ impl RArray {
             if slice.len() != N {
                 return Err(Error::new(
                     Ruby::get_with(self).exception_type_error(),
-                    format!("expected Array of length {}", N),
+                    format!("expected Array of length {}, got {}", N, slice.len()),
                 ));
             }
             // one day might be able to collect direct into an array, but for
             // now need to go via Vec
             slice
                 .iter()
-                .copied()
-                .map(TryConvert::try_convert)
+                .enumerate()
+                .map(|(i, v)| annotate_path(T::try_convert(*v), || format!("[{}]", i)))
                 .collect::<Result<Vec<T>, Error>>()
                 .map(|v| v.try_into().ok().unwrap())
         }
@@ -1290,6 +1490,34 @@ impl RArray {
         }
     }
 
+    /// Return the value found by repeatedly indexing into nested Arrays and
+    /// Hashes with `keys`, like Ruby's `Array#dig`.
+    ///
+    /// Returns `None` if any intermediate value is `nil`, or the final value
+    /// is `nil`. Returns `Err` if `keys` is empty, or if an intermediate value
+    /// does not respond to `dig`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, Error, RArray, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let ary: RArray = ruby.eval(r#"[{"a" => [1, 2]}, nil, 3]"#)?;
+    ///
+    ///     let val = ary.dig(&[ruby.into_value(0), ruby.into_value("a"), ruby.into_value(1)])?;
+    ///     assert_eq!(i64::try_convert(val.unwrap())?, 2);
+    ///     assert!(ary.dig(&[ruby.into_value(1), ruby.into_value(0)])?.is_none());
+    ///     assert!(ary.dig(&[ruby.into_value(2), ruby.into_value(0)]).is_err());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn dig(self, keys: &[Value]) -> Result<Option<Value>, Error> {
+        self.funcall("dig", keys)
+    }
+
     /// Set the element at `offset`.
     ///
     /// If `offset` is beyond the current size of the array the array will be
@@ -1355,6 +1583,546 @@ impl RArray {
         self.enumeratorize("each", ())
     }
 
+    /// Call `func` for each element of `self`, along with its index.
+    ///
+    /// Elements are converted to `T` with [`TryConvert`]. `func` returning
+    /// [`ForEach::Stop`] ends the iteration early, and [`ForEach::Delete`]
+    /// removes the element just passed to `func` from `self`, with the
+    /// following elements (and their indices) shifting down by one. Errors
+    /// from converting elements, or returned by `func`, stop the iteration
+    /// and are returned.
+    ///
+    /// `self` may be modified during iteration; the length is checked before
+    /// every element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{r_hash::ForEach, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let ary = ruby.ary_from_vec(vec!["a", "b", "c", "d"]);
+    ///     let mut rows = Vec::new();
+    ///     ary.each_indexed(|i, s: String| {
+    ///         if s == "c" {
+    ///             return Ok(ForEach::Stop);
+    ///         }
+    ///         rows.push(format!("{}: {}", i, s));
+    ///         Ok(ForEach::Continue)
+    ///     })?;
+    ///     assert_eq!(rows, vec!["0: a", "1: b"]);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn each_indexed<F, T>(self, mut func: F) -> Result<(), Error>
+    where
+        F: FnMut(usize, T) -> Result<ForEach, Error>,
+        T: TryConvert,
+    {
+        let mut i = 0;
+        while i < self.len() {
+            match func(i, self.entry(i as isize)?)? {
+                ForEach::Continue => i += 1,
+                ForEach::Stop => break,
+                ForEach::Delete => {
+                    let _: Value = self.delete_at(i as isize)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns a new hash of arrays of the elements of `self` grouped by the
+    /// key returned by `func`.
+    ///
+    /// The hash's keys are in the order each key was first returned by
+    /// `func`, and each array in the order of elements in `self`, matching
+    /// Ruby's `Enumerable#group_by`. Errors returned by `func` stop the
+    /// iteration and are returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, rb_assert, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let ary = ruby.ary_from_vec(vec![1, 2, 3, 4, 5]);
+    ///     let res = ary.group_by(|v| Ok(i64::try_convert(v)? % 2 == 0))?;
+    ///     rb_assert!(ruby, "res == {false => [1, 3, 5], true => [2, 4]}", res);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn group_by<F, K>(self, mut func: F) -> Result<RHash, Error>
+    where
+        F: FnMut(Value) -> Result<K, Error>,
+        K: IntoValue,
+    {
+        let handle = Ruby::get_with(self);
+        let res = handle.hash_new();
+        let mut i = 0;
+        while i < self.len() {
+            let val: Value = self.entry(i as isize)?;
+            let key = handle.into_value(func(val)?);
+            let group = match res.get(key) {
+                Some(group) => RArray::from_value(group).unwrap(),
+                None => {
+                    let group = handle.ary_new();
+                    res.aset(key, group)?;
+                    group
+                }
+            };
+            group.push(val)?;
+            i += 1;
+        }
+        Ok(res)
+    }
+
+    /// Returns a new hash of each distinct element of `self` to the number of
+    /// times it occurs, like Ruby's `Enumerable#tally`.
+    ///
+    /// Elements are compared with `eql?` and `hash`, as with Hash keys. The
+    /// hash's keys are in the order each element first occurs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{rb_assert, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let ary = ruby.ary_from_vec(vec!["a", "b", "a", "c", "a", "b"]);
+    ///     let res = ary.tally()?;
+    ///     rb_assert!(ruby, r#"res == {"a" => 3, "b" => 2, "c" => 1}"#, res);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn tally(self) -> Result<RHash, Error> {
+        self.tally_by(Ok)
+    }
+
+    /// Returns a new hash of each distinct key returned by `func` to the
+    /// number of elements of `self` for which `func` returned that key.
+    ///
+    /// Keys are compared with `eql?` and `hash`, as with Hash keys. The hash's
+    /// keys are in the order each key was first returned by `func`. Errors
+    /// returned by `func` stop the iteration and are returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, rb_assert, Error, RString, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let ary = ruby.ary_from_vec(vec!["apple", "fig", "kiwi", "pear"]);
+    ///     let res = ary.tally_by(|v| RString::try_convert(v).map(|s| s.len()))?;
+    ///     rb_assert!(ruby, "res == {5 => 1, 3 => 1, 4 => 2}", res);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn tally_by<F, K>(self, mut func: F) -> Result<RHash, Error>
+    where
+        F: FnMut(Value) -> Result<K, Error>,
+        K: IntoValue,
+    {
+        let handle = Ruby::get_with(self);
+        let res = handle.hash_new();
+        let mut i = 0;
+        while i < self.len() {
+            let key = handle.into_value(func(self.entry(i as isize)?)?);
+            let count = match res.get(key) {
+                Some(count) => usize::try_convert(count)? + 1,
+                None => 1,
+            };
+            res.aset(key, count)?;
+            i += 1;
+        }
+        Ok(res)
+    }
+
+    /// Returns two new arrays, the first containing the elements of `self`
+    /// for which `func` returns `true`, the second those for which `func`
+    /// returns `false`.
+    ///
+    /// Errors returned by `func` stop the iteration and are returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, rb_assert, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let ary = ruby.ary_from_vec(vec![1, 2, 3, 4, 5]);
+    ///     let (even, odd) = ary.partition(|v| Ok(i64::try_convert(v)? % 2 == 0))?;
+    ///     rb_assert!(ruby, "even == [2, 4]", even);
+    ///     rb_assert!(ruby, "odd == [1, 3, 5]", odd);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn partition<F>(self, mut func: F) -> Result<(RArray, RArray), Error>
+    where
+        F: FnMut(Value) -> Result<bool, Error>,
+    {
+        let handle = Ruby::get_with(self);
+        let selected = handle.ary_new();
+        let rejected = handle.ary_new();
+        let mut i = 0;
+        while i < self.len() {
+            let val: Value = self.entry(i as isize)?;
+            if func(val)? {
+                selected.push(val)?;
+            } else {
+                rejected.push(val)?;
+            }
+            i += 1;
+        }
+        Ok((selected, rejected))
+    }
+
+    /// Returns a new array of the concatenated arrays returned by calling
+    /// `func` with each element of `self`.
+    ///
+    /// Unlike Ruby's `Enumerable#flat_map`, `func` must always return an
+    /// array, so there's no ambiguity about whether a single value is
+    /// wrapped. Errors returned by `func` stop the iteration and are
+    /// returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, rb_assert, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let ary = ruby.ary_from_vec(vec![1, 2, 3]);
+    ///     let res = ary.flat_map(|v| {
+    ///         let i = i64::try_convert(v)?;
+    ///         Ok(ruby.ary_from_vec(vec![i; i as usize]))
+    ///     })?;
+    ///     rb_assert!(ruby, "res == [1, 2, 2, 3, 3, 3]", res);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn flat_map<F>(self, mut func: F) -> Result<RArray, Error>
+    where
+        F: FnMut(Value) -> Result<RArray, Error>,
+    {
+        let res = Ruby::get_with(self).ary_new_capa(self.len());
+        let mut i = 0;
+        while i < self.len() {
+            res.concat(func(self.entry(i as isize)?)?)?;
+            i += 1;
+        }
+        Ok(res)
+    }
+
+    /// Call `func` with consecutive sub-arrays of `n` elements of `self`,
+    /// like Ruby's `Enumerable#each_slice`.
+    ///
+    /// The last sub-array will contain fewer than `n` elements if the length
+    /// of `self` is not a multiple of `n`. `func` returning [`ForEach::Stop`]
+    /// ends the iteration early, [`ForEach::Delete`] is treated the same as
+    /// [`ForEach::Continue`]. Errors returned by `func` stop the iteration
+    /// and are returned.
+    ///
+    /// Returns `Err` if `n` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{r_hash::ForEach, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let ary = ruby.ary_from_vec(vec![1, 2, 3, 4, 5]);
+    ///     let mut batches = Vec::new();
+    ///     ary.each_slice(2, |batch| {
+    ///         batches.push(batch.to_vec::<i64>()?);
+    ///         Ok(ForEach::Continue)
+    ///     })?;
+    ///     assert_eq!(batches, vec![vec![1, 2], vec![3, 4], vec![5]]);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn each_slice<F>(self, n: usize, mut func: F) -> Result<(), Error>
+    where
+        F: FnMut(RArray) -> Result<ForEach, Error>,
+    {
+        if n == 0 {
+            return Err(Error::new(
+                Ruby::get_with(self).exception_arg_error(),
+                "invalid slice size",
+            ));
+        }
+        let mut i = 0;
+        while i < self.len() {
+            let slice = self.subseq(i, n).unwrap();
+            if let ForEach::Stop = func(slice)? {
+                break;
+            }
+            i += n;
+        }
+        Ok(())
+    }
+
+    /// Call `func` with each sub-array of `n` consecutive elements of
+    /// `self`, like Ruby's `Enumerable#each_cons`.
+    ///
+    /// `func` is not called if `n` is greater than the length of `self`.
+    /// `func` returning [`ForEach::Stop`] ends the iteration early,
+    /// [`ForEach::Delete`] is treated the same as [`ForEach::Continue`].
+    /// Errors returned by `func` stop the iteration and are returned.
+    ///
+    /// Returns `Err` if `n` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{r_hash::ForEach, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let ary = ruby.ary_from_vec(vec![1, 2, 3, 4]);
+    ///     let mut windows = Vec::new();
+    ///     ary.each_cons(3, |window| {
+    ///         windows.push(window.to_vec::<i64>()?);
+    ///         Ok(ForEach::Continue)
+    ///     })?;
+    ///     assert_eq!(windows, vec![vec![1, 2, 3], vec![2, 3, 4]]);
+    ///
+    ///     let mut called = false;
+    ///     ary.each_cons(5, |_| {
+    ///         called = true;
+    ///         Ok(ForEach::Continue)
+    ///     })?;
+    ///     assert!(!called);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn each_cons<F>(self, n: usize, mut func: F) -> Result<(), Error>
+    where
+        F: FnMut(RArray) -> Result<ForEach, Error>,
+    {
+        if n == 0 {
+            return Err(Error::new(
+                Ruby::get_with(self).exception_arg_error(),
+                "invalid size",
+            ));
+        }
+        let mut i = 0;
+        while i + n <= self.len() {
+            let window = self.subseq(i, n).unwrap();
+            if let ForEach::Stop = func(window)? {
+                break;
+            }
+            i += 1;
+        }
+        Ok(())
+    }
+
+    /// Combine the elements of `self` into a single Rust value, by calling
+    /// `func` with the accumulator and each element in turn.
+    ///
+    /// Elements are converted to `T` with [`TryConvert`]. Errors from
+    /// converting elements, or returned by `func`, stop the iteration and are
+    /// returned.
+    ///
+    /// The accumulator is not visible to Ruby's garbage collector, so should
+    /// not store Ruby values in a heap allocated data structure, such as a
+    /// `Vec`. See [the crate-level safety docs](crate#safety).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let ary = ruby.ary_from_vec(vec![1, 2, 3, 4]);
+    ///     let sum = ary.fold(0, |acc, i: i64| Ok(acc + i))?;
+    ///     assert_eq!(sum, 10);
+    ///
+    ///     let ary = ruby.ary_from_vec(vec!["a", "b", "c"]);
+    ///     let s = ary.fold(String::new(), |mut acc, s: String| {
+    ///         acc.push_str(&s);
+    ///         Ok(acc)
+    ///     })?;
+    ///     assert_eq!(s, "abc");
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn fold<A, F, T>(self, init: A, mut func: F) -> Result<A, Error>
+    where
+        F: FnMut(A, T) -> Result<A, Error>,
+        T: TryConvert,
+    {
+        let mut acc = init;
+        let mut i = 0;
+        while i < self.len() {
+            acc = func(acc, self.entry(i as isize)?)?;
+            i += 1;
+        }
+        Ok(acc)
+    }
+
+    /// Returns the element of `self` for which `func` returns the smallest
+    /// key.
+    ///
+    /// Returns `None` if `self` is empty. When several elements share the
+    /// smallest key the first is returned. Errors if two keys can not be
+    /// compared (such as a `NaN` float), or with the first error returned by
+    /// `func`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, rb_assert, Error, RString, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let ary = ruby.ary_from_vec(vec!["apple", "fig", "banana"]);
+    ///     let shortest = ary.min_by(|v| Ok(RString::try_convert(v)?.len()))?;
+    ///     rb_assert!(ruby, r#"shortest == "fig""#, shortest);
+    ///
+    ///     assert!(ruby.ary_new().min_by(|_| Ok(0))?.is_none());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn min_by<F, K>(self, func: F) -> Result<Option<Value>, Error>
+    where
+        F: FnMut(Value) -> Result<K, Error>,
+        K: PartialOrd,
+    {
+        self.extremum_by(func, Ordering::Less)
+    }
+
+    /// Returns the element of `self` for which `func` returns the largest
+    /// key.
+    ///
+    /// Returns `None` if `self` is empty. When several elements share the
+    /// largest key the first is returned. Errors if two keys can not be
+    /// compared (such as a `NaN` float), or with the first error returned by
+    /// `func`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, rb_assert, Error, RString, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let ary = ruby.ary_from_vec(vec!["apple", "fig", "banana"]);
+    ///     let longest = ary.max_by(|v| Ok(RString::try_convert(v)?.len()))?;
+    ///     rb_assert!(ruby, r#"longest == "banana""#, longest);
+    ///
+    ///     assert!(ruby.ary_new().max_by(|_| Ok(0))?.is_none());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn max_by<F, K>(self, func: F) -> Result<Option<Value>, Error>
+    where
+        F: FnMut(Value) -> Result<K, Error>,
+        K: PartialOrd,
+    {
+        self.extremum_by(func, Ordering::Greater)
+    }
+
+    fn extremum_by<F, K>(self, mut func: F, want: Ordering) -> Result<Option<Value>, Error>
+    where
+        F: FnMut(Value) -> Result<K, Error>,
+        K: PartialOrd,
+    {
+        let mut res: Option<(Value, K)> = None;
+        let mut i = 0;
+        while i < self.len() {
+            let val: Value = self.entry(i as isize)?;
+            let key = func(val)?;
+            res = match res {
+                None => Some((val, key)),
+                Some((cur, cur_key)) => match key.partial_cmp(&cur_key) {
+                    Some(ord) if ord == want => Some((val, key)),
+                    Some(_) => Some((cur, cur_key)),
+                    None => {
+                        return Err(Error::new(
+                            Ruby::get_with(self).exception_arg_error(),
+                            "comparison of keys failed",
+                        ))
+                    }
+                },
+            };
+            i += 1;
+        }
+        Ok(res.map(|(val, _)| val))
+    }
+
+    /// Returns the sum of the keys returned by calling `func` with each
+    /// element of `self`.
+    ///
+    /// Returns the [`Default`] for `K` (`0` for numbers) if `self` is empty.
+    /// Keys are summed with [`CheckedAdd`], so rather than promoting to a
+    /// Bignum as Ruby would, integer overflow is an error. Errors are the
+    /// first error returned by `func`, or a `RangeError` on overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, Error, RArray, RHash, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let ary: RArray = ruby.eval("[{price: 1.5}, {price: 2.25}]")?;
+    ///     let total = ary.sum_by(|v| {
+    ///         let hash = RHash::try_convert(v)?;
+    ///         hash.fetch::<_, f64>(ruby.to_symbol("price"))
+    ///     })?;
+    ///     assert_eq!(total, 3.75);
+    ///
+    ///     let ary = ruby.ary_from_vec(vec![1, 2, 3]);
+    ///     assert_eq!(ary.sum_by(|v| i64::try_convert(v))?, 6);
+    ///     assert_eq!(ruby.ary_new().sum_by(|v| i64::try_convert(v))?, 0);
+    ///
+    ///     let ary = ruby.ary_from_vec(vec![i64::MAX, 1]);
+    ///     let err = ary.sum_by(|v| i64::try_convert(v)).unwrap_err();
+    ///     assert!(err.is_kind_of(ruby.exception_range_error()));
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn sum_by<F, K>(self, mut func: F) -> Result<K, Error>
+    where
+        F: FnMut(Value) -> Result<K, Error>,
+        K: Default + CheckedAdd,
+    {
+        let mut sum = K::default();
+        let mut i = 0;
+        while i < self.len() {
+            sum = sum
+                .checked_add(func(self.entry(i as isize)?)?)
+                .ok_or_else(|| {
+                    Error::new(
+                        Ruby::get_with(self).exception_range_error(),
+                        "sum overflowed",
+                    )
+                })?;
+            i += 1;
+        }
+        Ok(sum)
+    }
+
     /// Returns true if both `self` and `other` share the same backing storage.
     ///
     /// It is possible for two Ruby Arrays to share the same backing storage,
@@ -1571,6 +2339,139 @@ impl RArray {
             .and_then(<Option<i64>>::try_convert)
             .map(|opt| opt.map(|i| i.cmp(&0)))
     }
+
+    /// Returns a new array of arrays, where the `n`th array contains the
+    /// `n`th element of `self` followed by the `n`th element of each of
+    /// `others`.
+    ///
+    /// This matches Ruby's `Array#zip`. The result has the same length as
+    /// `self`, with `nil` used in place of missing elements when any of
+    /// `others` are shorter than `self`. Elements of `others` beyond the
+    /// length of `self` are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{rb_assert, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let a = ruby.ary_from_vec(vec![1, 2, 3]);
+    ///     let b = ruby.ary_from_vec(vec!["a", "b", "c", "d"]);
+    ///     let c = ruby.ary_from_vec(vec![true, false]);
+    ///
+    ///     let res = a.zip(&[b, c]);
+    ///     rb_assert!(
+    ///         ruby,
+    ///         r#"res == [[1, "a", true], [2, "b", false], [3, "c", nil]]"#,
+    ///         res
+    ///     );
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn zip(self, others: &[Self]) -> Self {
+        let ruby = Ruby::get_with(self);
+        let entry = |ary: Self, i: usize| unsafe {
+            Value::new(rb_ary_entry(ary.as_rb_value(), i as c_long))
+        };
+        ruby.ary_from_iter((0..self.len()).map(|i| {
+            ruby.ary_from_iter(
+                std::iter::once(entry(self, i)).chain(others.iter().map(|o| entry(*o, i))),
+            )
+        }))
+    }
+
+    /// Returns a new array with the elements of `self`, with any nested
+    /// arrays recursively replaced by their elements.
+    ///
+    /// `depth` limits how many levels of nesting are flattened, `None`
+    /// flattens fully. `Some(0)` returns a copy of `self` with no flattening.
+    ///
+    /// This matches Ruby's `Array#flatten`, so as well as `Array`s, elements
+    /// that respond to `to_ary` are converted with `to_ary` and flattened.
+    /// Errors if `to_ary` raises or returns something other than an `Array`,
+    /// or if `self` contains itself (directly or nested) within the flattened
+    /// depth.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, rb_assert, Error, RArray, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let ary: RArray = ruby.eval("[1, [2, [3, [4]]], 5]")?;
+    ///
+    ///     let res = ary.flatten(None)?;
+    ///     rb_assert!(ruby, "res == [1, 2, 3, 4, 5]", res);
+    ///
+    ///     let res = ary.flatten(Some(1))?;
+    ///     rb_assert!(ruby, "res == [1, 2, [3, [4]], 5]", res);
+    ///
+    ///     let ary: RArray = ruby.eval(
+    ///         r#"
+    ///             o = Object.new
+    ///             def o.to_ary
+    ///               [2, 3]
+    ///             end
+    ///             [1, o]
+    ///         "#,
+    ///     )?;
+    ///     let res = ary.flatten(None)?;
+    ///     rb_assert!(ruby, "res == [1, 2, 3]", res);
+    ///
+    ///     let ary: RArray = ruby.eval("a = [1]; a << a; a")?;
+    ///     assert!(ary.flatten(None).is_err());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn flatten(self, depth: Option<usize>) -> Result<Self, Error> {
+        let ruby = Ruby::get_with(self);
+        let res = ruby.ary_new_capa(self.len());
+        let mut seen = Vec::new();
+        self.flatten_into(res, depth, &mut seen)?;
+        Ok(res)
+    }
+
+    fn flatten_into(
+        self,
+        res: Self,
+        depth: Option<usize>,
+        seen: &mut Vec<Self>,
+    ) -> Result<(), Error> {
+        // `self` is always on the Rust stack in one of the recursive calls
+        // while it is in `seen`, so it can't be collected by the GC.
+        seen.push(self);
+        let mut i = 0;
+        // calling `to_ary` may run arbitrary code that modifies `self`, so
+        // re-check the length every time round.
+        while i < self.len() {
+            let val: Value = self.entry(i as isize)?;
+            i += 1;
+            if depth == Some(0) {
+                res.push(val)?;
+                continue;
+            }
+            let checked =
+                protect(|| unsafe { Value::new(rb_check_array_type(val.as_rb_value())) })?;
+            match Self::from_value(checked) {
+                Some(ary) => {
+                    if seen.iter().any(|s| s.as_rb_value() == ary.as_rb_value()) {
+                        return Err(Error::new(
+                            Ruby::get_with(self).exception_arg_error(),
+                            "tried to flatten recursive array",
+                        ));
+                    }
+                    ary.flatten_into(res, depth.map(|d| d - 1), seen)?;
+                }
+                None => res.push(val)?,
+            }
+        }
+        seen.pop();
+        Ok(())
+    }
 }
 
 impl fmt::Display for RArray {
@@ -2011,3 +2912,40 @@ impl<T> Iter<T> {
         }
     }
 }
+
+/// Addition that reports overflow, used by [`RArray::sum_by`].
+pub trait CheckedAdd: Sized {
+    /// Add `self` and `other`, returning `None` on overflow.
+    fn checked_add(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_checked_add_int {
+    ($($t:ty),*) => {
+        $(
+            impl CheckedAdd for $t {
+                #[inline]
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$t>::checked_add(self, other)
+                }
+            }
+        )*
+    };
+}
+
+impl_checked_add_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+macro_rules! impl_checked_add_float {
+    ($($t:ty),*) => {
+        $(
+            impl CheckedAdd for $t {
+                // floats go to infinity rather than overflowing
+                #[inline]
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    Some(self + other)
+                }
+            }
+        )*
+    };
+}
+
+impl_checked_add_float!(f32, f64);
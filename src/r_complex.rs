@@ -243,13 +243,35 @@ impl ReprValue for RComplex {}
 
 impl TryConvert for RComplex {
     fn try_convert(val: Value) -> Result<Self, Error> {
-        Self::from_value(val).ok_or_else(|| {
-            Error::new(
-                Ruby::get_with(val).exception_type_error(),
-                format!("no implicit conversion of {} into Complex", unsafe {
-                    val.classname()
-                },),
-            )
-        })
+        Self::from_value(val).ok_or_else(|| Error::type_error("Complex", val))
+    }
+}
+
+/// Converts to a Ruby `Complex` with `Float` real and imaginary parts.
+#[cfg_attr(docsrs, doc(cfg(feature = "num-complex")))]
+#[cfg(feature = "num-complex")]
+impl IntoValue for num_complex::Complex<f64> {
+    fn into_value_with(self, handle: &Ruby) -> Value {
+        RComplex::new(
+            handle.float_from_f64(self.re),
+            handle.float_from_f64(self.im),
+        )
+        .as_value()
+    }
+}
+
+/// Converts from a Ruby `Complex`, or a real number such as `Integer` or
+/// `Float`.
+#[cfg_attr(docsrs, doc(cfg(feature = "num-complex")))]
+#[cfg(feature = "num-complex")]
+impl TryConvert for num_complex::Complex<f64> {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        if let Some(complex) = RComplex::from_value(val) {
+            return Ok(Self::new(complex.real()?, complex.imag()?));
+        }
+        if val.is_kind_of(Ruby::get_with(val).class_numeric()) {
+            return Ok(Self::new(f64::try_convert(val)?, 0.0));
+        }
+        Err(Error::type_error("Complex", val))
     }
 }
@@ -14,17 +14,22 @@ use rb_sys::rb_hash_new_capa;
 use rb_sys::{
     rb_check_hash_type, rb_hash_aref, rb_hash_aset, rb_hash_bulk_insert, rb_hash_clear,
     rb_hash_delete, rb_hash_fetch, rb_hash_foreach, rb_hash_lookup, rb_hash_lookup2, rb_hash_new,
-    rb_hash_size, rb_hash_size_num, rb_hash_update_by, ruby_value_type, VALUE,
+    rb_hash_set_ifnone, rb_hash_size, rb_hash_size_num, rb_hash_update_by, rb_str_intern,
+    rb_sym2str, ruby_value_type, VALUE,
 };
 
 use crate::{
+    block::Proc,
     error::{protect, raise, Error},
     into_value::{IntoValue, IntoValueFromNative},
     object::Object,
-    try_convert::{TryConvert, TryConvertOwned},
+    r_array::RArray,
+    r_string::RString,
+    symbol::Symbol,
+    try_convert::{annotate_path, TryConvert, TryConvertOwned},
     value::{
         private::{self, ReprValue as _},
-        Fixnum, NonZeroValue, ReprValue, Value, QUNDEF,
+        Fixnum, Lazy, NonZeroValue, ReprValue, StaticSymbol, Value, QUNDEF,
     },
     Ruby,
 };
@@ -104,6 +109,35 @@ impl Ruby {
         unsafe { RHash::from_rb_value_unchecked(rb_hash_new()) }
     }
 
+    /// Return a shared, frozen, empty `RHash`.
+    ///
+    /// The same instance is returned on every call, so this avoids allocating
+    /// a new Hash when returning `{}`, e.g. as a "no results" value from a
+    /// frequently called method. As the Hash is frozen any attempt to modify
+    /// it will raise `FrozenError`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, rb_assert, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let a = ruby.hash_empty_frozen();
+    ///     let b = ruby.hash_empty_frozen();
+    ///     assert!(a.is_empty());
+    ///     assert!(a.is_frozen());
+    ///     rb_assert!(ruby, "a.equal?(b)", a, b);
+    ///     assert!(a.aset("key", 1).is_err());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn hash_empty_frozen(&self) -> RHash {
+        static EMPTY: Lazy<RHash> = Lazy::new(|ruby| ruby.hash_new().freeze());
+        self.get_inner(&EMPTY)
+    }
+
     /// Create a new empty `RHash` with capacity for `n` elements pre-allocated.
     ///
     /// # Examples
@@ -125,6 +159,87 @@ impl Ruby {
         unsafe { RHash::from_rb_value_unchecked(rb_hash_new_capa(n as c_long)) }
     }
 
+    /// Create a new empty `RHash` that returns `default` when a missing key
+    /// is accessed.
+    ///
+    /// The default is returned by [`aref`](RHash::aref) and Ruby's `Hash#[]`,
+    /// but not by [`get`](RHash::get), [`lookup`](RHash::lookup), or
+    /// [`fetch`](RHash::fetch). Accessing a missing key does not store the
+    /// default in the hash, so `default` should usually be immutable, for
+    /// example a number.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{rb_assert, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let counts = ruby.hash_new_with_default(0);
+    ///     for word in ["a", "b", "a"] {
+    ///         let count: i64 = counts.aref(word)?;
+    ///         counts.aset(word, count + 1)?;
+    ///     }
+    ///     rb_assert!(ruby, r#"counts == {"a" => 2, "b" => 1}"#, counts);
+    ///     rb_assert!(ruby, r#"counts["c"] == 0"#, counts);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn hash_new_with_default<T>(&self, default: T) -> RHash
+    where
+        T: IntoValue,
+    {
+        let default = self.into_value(default);
+        unsafe {
+            let hash = rb_hash_new();
+            rb_hash_set_ifnone(hash, default.as_rb_value());
+            RHash::from_rb_value_unchecked(hash)
+        }
+    }
+
+    /// Create a new empty `RHash` that calls `default_proc` with the hash and
+    /// key when a missing key is accessed.
+    ///
+    /// As with [`hash_new_with_default`](Ruby::hash_new_with_default), the
+    /// default proc is only used by [`aref`](RHash::aref) and Ruby's
+    /// `Hash#[]`.
+    ///
+    /// Errors if `default_proc` is a lambda that does not take exactly 2
+    /// arguments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{rb_assert, Error, RArray, RHash, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let groups = ruby.hash_new_with_default_proc(ruby.proc_new(|ruby, args, _| {
+    ///         let hash = RHash::from_value(args[0]).unwrap();
+    ///         let ary = ruby.ary_new();
+    ///         hash.aset(args[1], ary)?;
+    ///         Ok(ary)
+    ///     }))?;
+    ///     for word in ["apple", "avocado", "banana"] {
+    ///         let group: RArray = groups.aref(&word[..1])?;
+    ///         group.push(word)?;
+    ///     }
+    ///     rb_assert!(
+    ///         ruby,
+    ///         r#"groups == {"a" => ["apple", "avocado"], "b" => ["banana"]}"#,
+    ///         groups
+    ///     );
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn hash_new_with_default_proc(&self, default_proc: Proc) -> Result<RHash, Error> {
+        let hash = self.hash_new();
+        let _: Value = hash.funcall("default_proc=", (default_proc,))?;
+        Ok(hash)
+    }
+
     /// Create a new `RHash` from a Rust iterator.
     ///
     /// # Examples
@@ -615,6 +730,35 @@ impl RHash {
         .and_then(|v| (!v.is_undef()).then(|| v))
     }
 
+    /// Return the value found by repeatedly indexing into nested Hashes and
+    /// Arrays with `keys`, like Ruby's `Hash#dig`.
+    ///
+    /// Returns `None` if any intermediate value is `nil`, or the final value
+    /// is `nil`. Returns `Err` if `keys` is empty, or if an intermediate value
+    /// does not respond to `dig`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, Error, RHash, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let hash: RHash = ruby.eval(r#"{"user" => {"emails" => ["a@example.com"]}, "n" => 1}"#)?;
+    ///
+    ///     let keys = [ruby.into_value("user"), ruby.into_value("emails"), ruby.into_value(0)];
+    ///     let val = hash.dig(&keys)?;
+    ///     assert_eq!(String::try_convert(val.unwrap())?, "a@example.com");
+    ///     assert!(hash.dig(&[ruby.into_value("missing"), ruby.into_value(0)])?.is_none());
+    ///     assert!(hash.dig(&[ruby.into_value("n"), ruby.into_value(0)]).is_err());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn dig(self, keys: &[Value]) -> Result<Option<Value>, Error> {
+        self.funcall("dig", keys)
+    }
+
     /// Return the value for `key`, converting it to `U`.
     ///
     /// Returns `Err` if `key` is missing. See also [`aref`](RHash::aref),
@@ -706,6 +850,60 @@ impl RHash {
         Ok(())
     }
 
+    /// Makes `self` compare keys by identity, rather than by `eql?`/`hash`,
+    /// as with Ruby's `Hash#compare_by_identity`.
+    ///
+    /// Two keys are then considered the same only if they are the same
+    /// object. This is useful for tracking visited objects. Returns `self`,
+    /// for chaining.
+    ///
+    /// Errors if `self` is frozen, or is being iterated over.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let hash = ruby.hash_new().compare_by_identity()?;
+    ///     assert!(hash.is_compare_by_identity()?);
+    ///
+    ///     hash.aset(ruby.str_new("key"), 1)?;
+    ///     hash.aset(ruby.str_new("key"), 2)?;
+    ///     assert_eq!(hash.len(), 2);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn compare_by_identity(self) -> Result<Self, Error> {
+        self.funcall("compare_by_identity", ())
+    }
+
+    /// Returns whether `self` compares keys by identity, as with Ruby's
+    /// `Hash#compare_by_identity?`.
+    ///
+    /// See [`compare_by_identity`](RHash::compare_by_identity).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, RHash, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     assert!(!ruby.hash_new().is_compare_by_identity()?);
+    ///
+    ///     let hash: RHash = ruby.eval("{}.compare_by_identity")?;
+    ///     assert!(hash.is_compare_by_identity()?);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn is_compare_by_identity(self) -> Result<bool, Error> {
+        self.funcall("compare_by_identity?", ())
+    }
+
     /// Run `func` for each key/value pair in `self`.
     ///
     /// The result of `func` is checked on each call, when it is
@@ -772,6 +970,10 @@ impl RHash {
     /// of Ruby's garbage collector, and thus may be prematurely garbage
     /// collected in the following sweep phase.
     ///
+    /// A [`HashMap`] does not preserve the insertion order of the Ruby hash,
+    /// see [`RHash::to_vec`] (or `RHash::to_index_map` with the `indexmap`
+    /// feature) where order matters.
+    ///
     /// Errors if the conversion of any key or value fails.
     ///
     /// # Examples
@@ -798,6 +1000,7 @@ impl RHash {
     {
         let mut map = HashMap::new();
         self.foreach(|key, value| {
+            let (key, value) = convert_pair(key, value)?;
             map.insert(key, value);
             Ok(ForEach::Continue)
         })?;
@@ -812,6 +1015,9 @@ impl RHash {
     /// of Ruby's garbage collector, and thus may be prematurely garbage
     /// collected in the following sweep phase.
     ///
+    /// Ruby hashes are ordered, the pairs are returned in the hash's insertion
+    /// order.
+    ///
     /// Errors if the conversion of any key or value fails.
     ///
     /// # Examples
@@ -834,12 +1040,173 @@ impl RHash {
     {
         let mut vec = Vec::with_capacity(self.len());
         self.foreach(|key, value| {
-            vec.push((key, value));
+            vec.push(convert_pair(key, value)?);
             Ok(ForEach::Continue)
         })?;
         Ok(vec)
     }
 
+    /// Convert `self` to a Rust vector of key/value pairs, sorted by key.
+    ///
+    /// This will only convert to a map of 'owned' Rust native types. The types
+    /// representing Ruby objects can not be stored in a heap-allocated
+    /// datastructure like a [`Vec`] as they are hidden from the mark phase
+    /// of Ruby's garbage collector, and thus may be prematurely garbage
+    /// collected in the following sweep phase.
+    ///
+    /// The pairs are sorted after conversion, using the [`Ord`]
+    /// implementation of the Rust key type, so the result does not depend on
+    /// the hash's insertion order. This is useful when deterministic output is
+    /// required, such as for canonical serialisation.
+    ///
+    /// Errors if the conversion of any key or value fails, the error message
+    /// will include the offending key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, RHash, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let r_hash: RHash = ruby.eval(r#"{"c" => 3, "a" => 1, "b" => 2}"#)?;
+    ///     assert_eq!(
+    ///         r_hash.to_sorted_vec()?,
+    ///         vec![
+    ///             (String::from("a"), 1),
+    ///             (String::from("b"), 2),
+    ///             (String::from("c"), 3)
+    ///         ]
+    ///     );
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn to_sorted_vec<K, V>(self) -> Result<Vec<(K, V)>, Error>
+    where
+        K: TryConvertOwned + Ord,
+        V: TryConvertOwned,
+    {
+        let mut vec = self.to_vec::<K, V>()?;
+        vec.sort_by(|(a, _), (b, _)| a.cmp(b));
+        Ok(vec)
+    }
+
+    /// Return `self` converted to an [`IndexMap`](indexmap::IndexMap),
+    /// preserving the insertion order of the Ruby hash.
+    ///
+    /// This will only convert to a map of 'owned' Rust native types. The types
+    /// representing Ruby objects can not be stored in a heap-allocated
+    /// datastructure like an `IndexMap` as they are hidden from the mark
+    /// phase of Ruby's garbage collector, and thus may be prematurely garbage
+    /// collected in the following sweep phase.
+    ///
+    /// Errors if the conversion of any key or value fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, RHash, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let r_hash: RHash = ruby.eval(r#"{"b" => 2, "a" => 1, "c" => 3}"#)?;
+    ///     let map = r_hash.to_index_map::<String, i64>()?;
+    ///     assert_eq!(map.keys().collect::<Vec<_>>(), ["b", "a", "c"]);
+    ///     assert_eq!(map["a"], 1);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    #[cfg(feature = "indexmap")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "indexmap")))]
+    pub fn to_index_map<K, V>(self) -> Result<indexmap::IndexMap<K, V>, Error>
+    where
+        K: TryConvertOwned + Eq + Hash,
+        V: TryConvertOwned,
+    {
+        let mut map = indexmap::IndexMap::with_capacity(self.len());
+        self.foreach(|key, value| {
+            let (key, value) = convert_pair(key, value)?;
+            map.insert(key, value);
+            Ok(ForEach::Continue)
+        })?;
+        Ok(map)
+    }
+
+    /// Convert `self`, a hash of arrays (e.g. `{a: [1, 2], b: [3, 4]}`), to
+    /// a Rust [`HashMap`] of column name to [`Vec`] of values.
+    ///
+    /// Keys must be Symbols or Strings, and are converted to
+    /// [`StaticSymbol`]s. This will only convert to 'owned' Rust native types,
+    /// for the same reasons as [`RHash::to_hash_map`].
+    ///
+    /// Errors if any value is not an array, if the conversion of any element
+    /// fails, or if the columns are not all the same length. Length mismatch
+    /// errors name the offending key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, RHash, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let hash: RHash = ruby.eval(r#"{a: [1, 2, 3], "b" => [4, 5, 6]}"#)?;
+    ///     let columns = hash.columns::<i64>()?;
+    ///     assert_eq!(columns[&ruby.sym_new("a")], vec![1, 2, 3]);
+    ///     assert_eq!(columns[&ruby.sym_new("b")], vec![4, 5, 6]);
+    ///
+    ///     let hash: RHash = ruby.eval("{a: [1, 2, 3], b: [4, 5]}")?;
+    ///     let err = hash.columns::<i64>().unwrap_err();
+    ///     assert!(err.to_string().contains("`b`"));
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn columns<T>(self) -> Result<HashMap<StaticSymbol, Vec<T>>, Error>
+    where
+        T: TryConvertOwned,
+    {
+        let handle = Ruby::get_with(self);
+        let mut columns = HashMap::with_capacity(self.len());
+        let mut expected: Option<(StaticSymbol, usize)> = None;
+        self.foreach(|key: Value, value: Value| {
+            let name = match Symbol::from_value(key) {
+                Some(sym) => sym,
+                None => handle.to_symbol(String::try_convert(key)?),
+            }
+            .to_static();
+            let ary = RArray::from_value(value).ok_or_else(|| {
+                Error::new(
+                    handle.exception_type_error(),
+                    format!("column `{}` must be an Array, not {}", name, unsafe {
+                        value.classname()
+                    }),
+                )
+            })?;
+            match expected {
+                Some((first, len)) if len != ary.len() => {
+                    return Err(Error::new(
+                        handle.exception_arg_error(),
+                        format!(
+                            "column `{}` has length {}, expected {} (the length of column `{}`)",
+                            name,
+                            ary.len(),
+                            len,
+                            first
+                        ),
+                    ));
+                }
+                Some(_) => (),
+                None => expected = Some((name, ary.len())),
+            }
+            columns.insert(name, ary.to_vec()?);
+            Ok(ForEach::Continue)
+        })?;
+        Ok(columns)
+    }
+
     /// Return the number of entries in `self` as a Ruby [`Fixnum`].
     ///
     /// # Examples
@@ -900,6 +1267,18 @@ impl RHash {
     }
 }
 
+fn convert_pair<K, V>(key: Value, value: Value) -> Result<(K, V), Error>
+where
+    K: TryConvert,
+    V: TryConvert,
+{
+    let segment = || format!("[{}]", key.inspect());
+    Ok((
+        annotate_path(K::try_convert(key), segment)?,
+        annotate_path(V::try_convert(value), segment)?,
+    ))
+}
+
 impl fmt::Display for RHash {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", unsafe { self.to_s_infallible() })
@@ -933,6 +1312,21 @@ where
     }
 }
 
+#[cfg(feature = "indexmap")]
+impl<K, V> IntoValue for indexmap::IndexMap<K, V>
+where
+    K: IntoValueFromNative,
+    V: IntoValueFromNative,
+{
+    fn into_value_with(self, handle: &Ruby) -> Value {
+        let hash = handle.hash_new();
+        for (k, v) in self {
+            let _ = hash.aset(k, v);
+        }
+        hash.into_value_with(handle)
+    }
+}
+
 #[cfg(feature = "old-api")]
 impl<K, V> FromIterator<(K, V)> for RHash
 where
@@ -971,3 +1365,54 @@ impl TryConvert for RHash {
         }
     }
 }
+
+/// Create a [`RHash`] with Symbol keys from Rust key-value mappings. Keys must
+/// be string literals, while values can be anything that implements
+/// [`IntoValue`].
+///
+/// This is useful for returning keyword-like results (e.g. Ruby's
+/// `{status: 200, body: "..."}`) from methods. See also [`kwargs!`](crate::kwargs!) for
+/// passing keyword arguments to Ruby methods.
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread, when the handle to Ruby is not
+/// supplied as the first argument.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{function, prelude::*, rb_assert, sym_hash, Error, RHash, Ruby};
+///
+/// fn response(ruby: &Ruby, body: String) -> RHash {
+///     sym_hash!(ruby, "status" => 200, "body" => body)
+/// }
+///
+/// fn example(ruby: &Ruby) -> Result<(), Error> {
+///     ruby.define_global_function("response", function!(response, 1));
+///
+///     rb_assert!(ruby, r#"response("ok") == {status: 200, body: "ok"}"#);
+///
+///     let empty = sym_hash!(ruby);
+///     assert!(empty.is_empty());
+///
+///     Ok(())
+/// }
+/// # Ruby::init(example).unwrap()
+/// ```
+#[macro_export]
+macro_rules! sym_hash {
+    ($ruby:expr $(, $k:literal => $v:expr)* $(,)?) => {{
+        let h = $ruby.hash_new();
+        $(
+            h.aset(
+                $ruby.to_symbol($k),
+                $crate::IntoValue::into_value_with($v, $ruby),
+            ).unwrap();
+        )*
+        h
+    }};
+    ($($k:literal => $v:expr),+ $(,)?) => {{
+        $crate::sym_hash!(&$crate::Ruby::get().unwrap(), $($k => $v),+)
+    }};
+}
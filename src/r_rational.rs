@@ -1,9 +1,11 @@
 use std::{fmt, num::NonZeroI64};
 
-use rb_sys::{rb_rational_den, rb_rational_new, rb_rational_num, ruby_value_type, VALUE};
+use rb_sys::{
+    rb_num2dbl, rb_rational_den, rb_rational_new, rb_rational_num, ruby_value_type, VALUE,
+};
 
 use crate::{
-    error::Error,
+    error::{protect, Error},
     integer::Integer,
     into_value::IntoValue,
     numeric::Numeric,
@@ -154,6 +156,70 @@ impl RRational {
     pub fn den(self) -> Integer {
         unsafe { Integer::from_rb_value_unchecked(rb_rational_den(self.as_rb_value())) }
     }
+
+    /// Convert `self` to an `f64`.
+    ///
+    /// The conversion is lossy, rounding to the nearest representable `f64`.
+    /// Numerators and denominators too large for an `i64` are handled without
+    /// error, but the result will be infinite if the value of `self` is
+    /// outside the range of an `f64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroI64;
+    ///
+    /// use magnus::{Error, RRational, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let rational = ruby.rational_new(3, NonZeroI64::new(4).unwrap());
+    ///     assert_eq!(rational.to_f64()?, 0.75);
+    ///
+    ///     let rational: RRational = ruby.eval("(2**100 + 1) / 2r**101")?;
+    ///     assert_eq!(rational.to_f64()?, 0.5);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn to_f64(self) -> Result<f64, Error> {
+        let mut res = 0.0;
+        protect(|| {
+            unsafe { res = rb_num2dbl(self.as_rb_value()) };
+            Ruby::get_with(self).qnil()
+        })?;
+        Ok(res)
+    }
+
+    /// Returns `self`'s numerator and denominator as a pair of `i64`s.
+    ///
+    /// The rational is always in its reduced form, with a positive
+    /// denominator.
+    ///
+    /// Errors with a `RangeError` if either the numerator or denominator is
+    /// too large to fit in an `i64`, rather than losing precision.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroI64;
+    ///
+    /// use magnus::{Error, RRational, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let rational = ruby.rational_new(6, NonZeroI64::new(-9).unwrap());
+    ///     assert_eq!(rational.to_pair()?, (-2, 3));
+    ///
+    ///     let rational: RRational = ruby.eval("1/2r**64")?;
+    ///     assert!(rational.to_pair().is_err());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn to_pair(self) -> Result<(i64, i64), Error> {
+        Ok((self.num().to_i64()?, self.den().to_i64()?))
+    }
 }
 
 impl fmt::Display for RRational {
@@ -193,3 +259,38 @@ impl TryConvert for RRational {
         })
     }
 }
+
+/// Converts to a Ruby `Rational`.
+///
+/// Errors with a `ZeroDivisionError` if the denominator is zero, which is only
+/// possible with a `Ratio` created with `Ratio::new_raw`.
+///
+/// A tuple such as `(i64, i64)` converts to an `Array`, see
+/// [`Ruby::rational_new`] to create a `Rational` from a numerator and
+/// denominator.
+#[cfg_attr(docsrs, doc(cfg(feature = "num-rational")))]
+#[cfg(feature = "num-rational")]
+impl crate::into_value::TryIntoValue for num_rational::Ratio<i64> {
+    fn try_into_value_with(self, handle: &Ruby) -> Result<Value, Error> {
+        let num = handle.into_value(*self.numer());
+        let den = handle.into_value(*self.denom());
+        protect(|| unsafe { Value::new(rb_rational_new(num.as_rb_value(), den.as_rb_value())) })
+    }
+}
+
+/// Converts from a Ruby `Rational` or `Integer`.
+///
+/// Errors with a `RangeError` if the numerator or denominator don't fit in an
+/// `i64`.
+#[cfg_attr(docsrs, doc(cfg(feature = "num-rational")))]
+#[cfg(feature = "num-rational")]
+impl TryConvert for num_rational::Ratio<i64> {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        if let Some(i) = Integer::from_value(val) {
+            return Ok(Self::from_integer(i.to_i64()?));
+        }
+        let rational = RRational::try_convert(val)?;
+        let (num, den) = rational.to_pair()?;
+        Ok(Self::new_raw(num, den))
+    }
+}
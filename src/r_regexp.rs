@@ -1,6 +1,8 @@
 //! Types for working with Ruby’s Regexp class.
 
 use std::{
+    cell::UnsafeCell,
+    collections::HashMap,
     fmt,
     os::raw::{c_char, c_int, c_long, c_uint},
 };
@@ -13,11 +15,12 @@ use crate::{
     encoding::EncodingCapable,
     error::{protect, Error},
     into_value::IntoValue,
+    r_array::RArray,
     r_string::{IntoRString, RString},
     try_convert::TryConvert,
     value::{
         private::{self, ReprValue as _},
-        NonZeroValue, ReprValue, Value,
+        Lazy, NonZeroValue, Opaque, ReprValue, Value,
     },
     Ruby,
 };
@@ -55,6 +58,102 @@ impl Ruby {
             ))
         })
     }
+
+    /// Return a `Regexp` for the Rust string `pattern`, reusing a previously
+    /// compiled `Regexp` if one exists.
+    ///
+    /// Compiled regexps are cached by `pattern` and `opts`, so calling this
+    /// repeatedly with the same arguments (such as in a loop) only compiles
+    /// the regexp once. The cache holds at most [`REG_CACHE_MAX_LEN`]
+    /// regexps, once full it is emptied before a new regexp is added.
+    ///
+    /// As the same object may be returned from multiple calls, the returned
+    /// regexp should not be modified (e.g. by setting instance variables).
+    ///
+    /// The encoding of the Ruby regexp will be UTF-8.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, r_regexp::Opts, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let a = ruby.reg_new_cached("fo+", Opts::new())?;
+    ///     let b = ruby.reg_new_cached("fo+", Opts::new())?;
+    ///     let c = ruby.reg_new_cached("fo+", Opts::new().ignorecase())?;
+    ///     // the exact same object is returned for the same arguments
+    ///     assert!(a.funcall::<_, _, bool>("equal?", (b,))?);
+    ///     assert!(!a.funcall::<_, _, bool>("equal?", (c,))?);
+    ///
+    ///     assert!(ruby.reg_new_cached("(", Opts::new()).is_err());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn reg_new_cached(&self, pattern: &str, opts: Opts) -> Result<RRegexp, Error> {
+        if let Some(regexp) = unsafe { reg_cache() }
+            .as_ref()
+            .and_then(|c| c.regexps.get(&opts.0))
+            .and_then(|m| m.get(pattern))
+        {
+            return Ok(self.get_inner(*regexp));
+        }
+        let regexp = self.reg_new(pattern, opts)?;
+        let full = unsafe { reg_cache() }
+            .as_ref()
+            .map_or(false, |c| c.len >= REG_CACHE_MAX_LEN);
+        let mark = self.get_inner(&REG_CACHE_MARK);
+        if full {
+            mark.clear()?;
+        }
+        mark.push(regexp)?;
+        let cache = unsafe { reg_cache() }.get_or_insert_with(|| RegCache {
+            regexps: HashMap::new(),
+            len: 0,
+        });
+        if full {
+            cache.regexps.clear();
+            cache.len = 0;
+        }
+        if cache
+            .regexps
+            .entry(opts.0)
+            .or_default()
+            .insert(pattern.to_owned(), regexp.into())
+            .is_none()
+        {
+            cache.len += 1;
+        }
+        Ok(regexp)
+    }
+}
+
+/// The maximum number of regexps held by the cache used by
+/// [`Ruby::reg_new_cached`].
+pub const REG_CACHE_MAX_LEN: usize = 256;
+
+struct RegCache {
+    regexps: HashMap<c_uint, HashMap<String, Opaque<RRegexp>>>,
+    len: usize,
+}
+
+struct RegCacheCell(UnsafeCell<Option<RegCache>>);
+
+// Only accessed from Ruby threads, which are serialised by the GVL.
+unsafe impl Sync for RegCacheCell {}
+
+static REG_CACHE: RegCacheCell = RegCacheCell(UnsafeCell::new(None));
+
+// keeps the cached regexps alive, as Rust-side storage isn't seen by the GC
+static REG_CACHE_MARK: Lazy<RArray> = Lazy::new(|ruby| ruby.ary_new());
+
+/// # Safety
+///
+/// Must only be called from a Ruby thread, and the returned reference must
+/// not be held over a call back in to Ruby.
+unsafe fn reg_cache() -> &'static mut Option<RegCache> {
+    &mut *REG_CACHE.0.get()
 }
 
 /// A Value pointer to a RRegexp struct, Ruby's internal representation of
@@ -0,0 +1,486 @@
+use std::{
+    collections::{BTreeSet, HashSet},
+    fmt,
+    hash::Hash,
+};
+
+use rb_sys::rb_const_defined;
+
+use crate::{
+    class::{Class, RClass},
+    error::Error,
+    into_value::{IntoValue, IntoValueFromNative, TryIntoValue},
+    module::Module,
+    object::Object,
+    r_array::RArray,
+    r_hash::ForEach,
+    try_convert::{nested, TryConvert, TryConvertOwned},
+    value::{
+        private::{self, ReprValue as _},
+        Lazy, NonZeroValue, ReprValue, Value,
+    },
+    Ruby,
+};
+
+// only initialised once `Set` is defined, after which it doesn't need to be
+// looked up again
+static SET_CLASS: Lazy<RClass> = Lazy::new(|ruby| ruby.class_object().const_get("Set").unwrap());
+
+fn defined_set_class(ruby: &Ruby) -> Option<RClass> {
+    if Lazy::try_get_inner(&SET_CLASS).is_none() {
+        let id = ruby.intern("Set");
+        let defined =
+            unsafe { rb_const_defined(ruby.class_object().as_rb_value(), id.as_rb_id()) != 0 };
+        if !defined {
+            return None;
+        }
+    }
+    Some(ruby.get_inner(&SET_CLASS))
+}
+
+fn set_class(ruby: &Ruby) -> Result<RClass, Error> {
+    if let Some(class) = defined_set_class(ruby) {
+        return Ok(class);
+    }
+    ruby.require("set")?;
+    defined_set_class(ruby)
+        .ok_or_else(|| Error::new(ruby.exception_name_error(), "uninitialized constant Set"))
+}
+
+/// # `RSet`
+///
+/// Functions that can be used to create instances of Ruby's `Set` class.
+///
+/// See also the [`RSet`] type.
+impl Ruby {
+    /// Create a new empty `RSet`.
+    ///
+    /// The `set` library will be required if `Set` is not already defined.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{rb_assert, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let set = ruby.set_new()?;
+    ///     assert!(set.is_empty()?);
+    ///     rb_assert!(ruby, "set == Set.new", set);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn set_new(&self) -> Result<RSet, Error> {
+        set_class(self)?
+            .new_instance(())
+            .map(|set| unsafe { RSet::from_value_unchecked(set) })
+    }
+
+    /// Create a new `RSet` containing the elements from `iter`.
+    ///
+    /// The `set` library will be required if `Set` is not already defined.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{rb_assert, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let set = ruby.set_from_iter([1, 2, 3, 2, 1])?;
+    ///     rb_assert!(ruby, "set == Set[1, 2, 3]", set);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn set_from_iter<I, T>(&self, iter: I) -> Result<RSet, Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: IntoValue,
+    {
+        let set = self.set_new()?;
+        for item in iter {
+            set.add(item)?;
+        }
+        Ok(set)
+    }
+}
+
+/// A Value known to be an instance of Ruby's `Set` class.
+///
+/// `Set` is implemented in Ruby rather than C, so most functions on this
+/// type call Ruby methods, and can fail.
+///
+/// As with Ruby's `Set`, elements are kept in insertion order.
+///
+/// See the [`ReprValue`] and [`Object`] traits for additional methods
+/// available on this type. See [`Ruby`](Ruby#rset) for methods to create an
+/// `RSet`.
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct RSet(NonZeroValue);
+
+impl RSet {
+    /// Return `Some(RSet)` if `val` is a `Set`, `None` otherwise.
+    ///
+    /// This will return `None` if the `set` library has not been loaded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, RSet, Ruby, Value};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     ruby.require("set")?;
+    ///     assert!(RSet::from_value(ruby.eval("Set[1, 2]")?).is_some());
+    ///     assert!(RSet::from_value(ruby.eval("[1, 2]")?).is_none());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn from_value(val: Value) -> Option<Self> {
+        // if `Set` isn't defined `val` can't be a `Set`
+        let class = defined_set_class(&Ruby::get_with(val))?;
+        val.is_kind_of(class)
+            .then(|| unsafe { Self::from_value_unchecked(val) })
+    }
+
+    #[inline]
+    unsafe fn from_value_unchecked(val: Value) -> Self {
+        Self(NonZeroValue::new_unchecked(val))
+    }
+
+    /// Return the number of elements in `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let set = ruby.set_from_iter(["a", "b", "a"])?;
+    ///     assert_eq!(set.len()?, 2);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn len(self) -> Result<usize, Error> {
+        self.funcall("size", ())
+    }
+
+    /// Return whether `self` contains no elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let set = ruby.set_new()?;
+    ///     assert!(set.is_empty()?);
+    ///     set.add(1)?;
+    ///     assert!(!set.is_empty()?);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn is_empty(self) -> Result<bool, Error> {
+        self.funcall("empty?", ())
+    }
+
+    /// Add `val` to `self`.
+    ///
+    /// Returns `Err` if `self` is frozen.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{rb_assert, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let set = ruby.set_new()?;
+    ///     set.add("a")?;
+    ///     set.add("b")?;
+    ///     set.add("a")?;
+    ///     rb_assert!(ruby, r#"set == Set["a", "b"]"#, set);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn add<T>(self, val: T) -> Result<(), Error>
+    where
+        T: IntoValue,
+    {
+        let val = Ruby::get_with(self).into_value(val);
+        self.funcall::<_, _, Value>("add", (val,))?;
+        Ok(())
+    }
+
+    /// Return whether `self` contains `val`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let set = ruby.set_from_iter([1, 2, 3])?;
+    ///     assert!(set.includes(2)?);
+    ///     assert!(!set.includes(4)?);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn includes<T>(self, val: T) -> Result<bool, Error>
+    where
+        T: IntoValue,
+    {
+        let val = Ruby::get_with(self).into_value(val);
+        self.funcall("include?", (val,))
+    }
+
+    /// Remove `val` from `self`.
+    ///
+    /// Returns `Ok(true)` if `val` was present, `Ok(false)` otherwise.
+    /// Returns `Err` if `self` is frozen.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{rb_assert, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let set = ruby.set_from_iter([1, 2, 3])?;
+    ///     assert!(set.delete(2)?);
+    ///     assert!(!set.delete(4)?);
+    ///     rb_assert!(ruby, "set == Set[1, 3]", set);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn delete<T>(self, val: T) -> Result<bool, Error>
+    where
+        T: IntoValue,
+    {
+        let val = Ruby::get_with(self).into_value(val);
+        self.funcall::<_, _, Option<Value>>("delete?", (val,))
+            .map(|res| res.is_some())
+    }
+
+    /// Return a new `RArray` containing the elements of `self`, in insertion
+    /// order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{rb_assert, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let set = ruby.set_from_iter(["b", "a", "b"])?;
+    ///     rb_assert!(ruby, r#"ary == ["b", "a"]"#, ary = set.to_r_array()?);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn to_r_array(self) -> Result<RArray, Error> {
+        self.funcall("to_a", ())
+    }
+
+    /// Run `func` for each element in `self`, in insertion order.
+    ///
+    /// Elements are converted to `T` with [`TryConvert`]. `func` returning
+    /// [`ForEach::Stop`] ends the iteration early, and [`ForEach::Delete`]
+    /// removes the element from `self`.
+    ///
+    /// `func` is called with a snapshot of the elements of `self`, so it is
+    /// safe to modify `self` from `func`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{r_hash::ForEach, rb_assert, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let set = ruby.set_from_iter([1, 2, 3, 4])?;
+    ///     let mut sum = 0;
+    ///     set.foreach(|i: i64| {
+    ///         sum += i;
+    ///         if i % 2 == 0 {
+    ///             Ok(ForEach::Delete)
+    ///         } else {
+    ///             Ok(ForEach::Continue)
+    ///         }
+    ///     })?;
+    ///     assert_eq!(sum, 10);
+    ///     rb_assert!(ruby, "set == Set[1, 3]", set);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn foreach<F, T>(self, mut func: F) -> Result<(), Error>
+    where
+        F: FnMut(T) -> Result<ForEach, Error>,
+        T: TryConvert,
+    {
+        let ary = self.to_r_array()?;
+        for i in 0..ary.len() {
+            let val: Value = ary.entry(i as isize)?;
+            match func(T::try_convert(val)?)? {
+                ForEach::Continue => (),
+                ForEach::Stop => break,
+                ForEach::Delete => {
+                    self.delete(val)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Return `self` converted to a Rust [`HashSet`].
+    ///
+    /// This will only convert to a set of 'owned' Rust native types. The
+    /// types representing Ruby objects can not be stored in a heap-allocated
+    /// datastructure like a [`HashSet`] as they are hidden from the mark phase
+    /// of Ruby's garbage collector, and thus may be prematurely garbage
+    /// collected in the following sweep phase.
+    ///
+    /// Errors if the conversion of any element fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    ///
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let set = ruby.set_from_iter(["a", "b"])?;
+    ///     assert_eq!(
+    ///         set.to_hash_set::<String>()?,
+    ///         HashSet::from([String::from("a"), String::from("b")])
+    ///     );
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn to_hash_set<T>(self) -> Result<HashSet<T>, Error>
+    where
+        T: TryConvertOwned + Eq + Hash,
+    {
+        let mut set = HashSet::new();
+        self.foreach(|val| {
+            set.insert(val);
+            Ok(ForEach::Continue)
+        })?;
+        Ok(set)
+    }
+}
+
+impl fmt::Display for RSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", unsafe { self.to_s_infallible() })
+    }
+}
+
+impl fmt::Debug for RSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.inspect())
+    }
+}
+
+impl IntoValue for RSet {
+    #[inline]
+    fn into_value_with(self, _: &Ruby) -> Value {
+        self.0.get()
+    }
+}
+
+/// Converts to a Ruby `Set`.
+///
+/// Errors if the `set` library can not be loaded.
+impl<T> TryIntoValue for HashSet<T>
+where
+    T: IntoValueFromNative,
+{
+    fn try_into_value_with(self, handle: &Ruby) -> Result<Value, Error> {
+        handle.set_from_iter(self).map(|set| set.as_value())
+    }
+}
+
+/// Converts to a Ruby `Set`, with elements in sorted order.
+///
+/// Errors if the `set` library can not be loaded.
+impl<T> TryIntoValue for BTreeSet<T>
+where
+    T: IntoValueFromNative,
+{
+    fn try_into_value_with(self, handle: &Ruby) -> Result<Value, Error> {
+        handle.set_from_iter(self).map(|set| set.as_value())
+    }
+}
+
+impl Object for RSet {}
+
+unsafe impl private::ReprValue for RSet {}
+
+impl ReprValue for RSet {}
+
+impl TryConvert for RSet {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        Self::from_value(val).ok_or_else(|| Error::type_error("Set", val))
+    }
+}
+
+fn try_convert_set<T, C>(val: Value) -> Result<C, Error>
+where
+    T: TryConvertOwned,
+    C: Default + Extend<T>,
+{
+    let mut res = C::default();
+    if let Some(set) = RSet::from_value(val) {
+        res.extend(set.to_r_array()?.to_vec::<T>()?);
+    } else if let Some(ary) = RArray::from_value(val) {
+        res.extend(ary.to_vec::<T>()?);
+    } else {
+        return Err(Error::type_error("Set", val));
+    }
+    Ok(res)
+}
+
+/// Converts from a Ruby `Set` or `Array`.
+///
+/// Duplicate elements in an `Array` are removed.
+impl<T> TryConvert for HashSet<T>
+where
+    T: TryConvertOwned + Eq + Hash,
+{
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        nested(val, || try_convert_set(val))
+    }
+}
+
+unsafe impl<T> TryConvertOwned for HashSet<T> where T: TryConvertOwned + Eq + Hash {}
+
+/// Converts from a Ruby `Set` or `Array`.
+///
+/// Duplicate elements in an `Array` are removed.
+impl<T> TryConvert for BTreeSet<T>
+where
+    T: TryConvertOwned + Ord,
+{
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        nested(val, || try_convert_set(val))
+    }
+}
+
+unsafe impl<T> TryConvertOwned for BTreeSet<T> where T: TryConvertOwned + Ord {}
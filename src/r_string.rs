@@ -7,7 +7,7 @@ use std::{
     fmt, io,
     iter::Iterator,
     mem::transmute,
-    os::raw::{c_char, c_long},
+    os::raw::{c_char, c_int, c_long},
     path::{Path, PathBuf},
     ptr, slice, str,
 };
@@ -15,24 +15,26 @@ use std::{
 #[cfg(ruby_gte_3_0)]
 use rb_sys::rb_str_to_interned_str;
 use rb_sys::{
-    self, rb_enc_str_coderange, rb_enc_str_new, rb_str_buf_append, rb_str_buf_new, rb_str_capacity,
-    rb_str_cat, rb_str_cmp, rb_str_comparable, rb_str_conv_enc, rb_str_drop_bytes, rb_str_dump,
-    rb_str_ellipsize, rb_str_new, rb_str_new_frozen, rb_str_new_shared, rb_str_offset, rb_str_plus,
-    rb_str_replace, rb_str_scrub, rb_str_shared_replace, rb_str_split, rb_str_strlen, rb_str_times,
-    rb_str_to_str, rb_str_update, rb_utf8_str_new, rb_utf8_str_new_static, ruby_coderange_type,
+    self, rb_enc_dummy_p, rb_enc_str_buf_cat, rb_enc_str_coderange, rb_enc_str_new,
+    rb_str_buf_append, rb_str_buf_new, rb_str_capacity, rb_str_cat, rb_str_cmp, rb_str_comparable,
+    rb_str_conv_enc, rb_str_drop_bytes, rb_str_dump, rb_str_ellipsize, rb_str_format, rb_str_new,
+    rb_str_new_frozen, rb_str_new_shared, rb_str_offset, rb_str_plus, rb_str_replace, rb_str_scrub,
+    rb_str_shared_replace, rb_str_split, rb_str_strlen, rb_str_subseq, rb_str_times, rb_str_to_str,
+    rb_str_update, rb_utf8_str_new, rb_utf8_str_new_static, ruby_coderange_type,
     ruby_rstring_flags, ruby_value_type, RSTRING_LEN, RSTRING_PTR, VALUE,
 };
 
 use crate::{
-    encoding::{Coderange, EncodingCapable, RbEncoding},
+    block::block_call_closure,
+    encoding::{Coderange, EncodingCapable, Index, MbcLen, RbEncoding},
     error::{protect, Error},
-    into_value::{IntoValue, IntoValueFromNative},
+    into_value::{ArgList, IntoValue, IntoValueFromNative},
     object::Object,
     r_array::RArray,
     try_convert::TryConvert,
     value::{
         private::{self, ReprValue as _},
-        NonZeroValue, ReprValue, Value,
+        IntoId, NonZeroValue, ReprValue, Value,
     },
     Ruby,
 };
@@ -75,6 +77,17 @@ impl Ruby {
         RString::from_rb_value_unchecked(rb_utf8_str_new_static(ptr, len))
     }
 
+    /// Implementation detail of [`frozen_str`].
+    #[doc(hidden)]
+    pub unsafe fn str_new_frozen_lit(&self, ptr: *const c_char, len: c_long) -> RString {
+        let s = self.str_new_lit(ptr, len);
+        #[cfg(ruby_gte_3_0)]
+        let s = s.to_interned_str().as_r_string();
+        #[cfg(ruby_lt_3_0)]
+        let s = s.freeze();
+        s
+    }
+
     /// Create a new Ruby string with capacity `n`.
     ///
     /// The encoding will be set to ASCII-8BIT (aka BINARY). See also
@@ -125,6 +138,46 @@ impl Ruby {
         s
     }
 
+    /// Create a new [`RStringBuilder`] with capacity `n`, building a string
+    /// of the encoding `enc`.
+    ///
+    /// Errors if `enc` is a dummy encoding, such as UTF-16.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{rb_assert, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let mut builder = ruby.str_builder(4096, ruby.utf8_encindex())?;
+    ///     builder.push_str("foo")?;
+    ///     builder.push_str("bar")?;
+    ///     let s = builder.finish();
+    ///     rb_assert!(ruby, r#"s == "foobar""#, s);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn str_builder<T>(&self, n: usize, enc: T) -> Result<RStringBuilder, Error>
+    where
+        T: Into<Index>,
+    {
+        let enc = enc.into();
+        if unsafe { rb_enc_dummy_p(RbEncoding::from(enc).as_ptr()) } != 0 {
+            return Err(Error::new(
+                self.exception_arg_error(),
+                format!(
+                    "can't build a string of dummy encoding {}",
+                    RbEncoding::from(enc).name()
+                ),
+            ));
+        }
+        let s = self.str_buf_new(n);
+        s.enc_associate(enc)?;
+        Ok(RStringBuilder { s, enc })
+    }
+
     /// Create a new Ruby string from the Rust slice `s`.
     ///
     /// The encoding of the Ruby string will be set to ASCII-8BIT (aka BINARY).
@@ -148,6 +201,36 @@ impl Ruby {
         unsafe { RString::from_rb_value_unchecked(rb_str_new(ptr as *const c_char, len as c_long)) }
     }
 
+    /// Create a new frozen Ruby string from the Rust slice `s`.
+    ///
+    /// The encoding of the Ruby string will be set to ASCII-8BIT (aka BINARY).
+    ///
+    /// This is useful for returning the contents of an internal buffer to
+    /// Ruby. The bytes are copied, so Ruby can't alias the buffer, and the
+    /// string is frozen, so can't be mutated by the caller in the expectation
+    /// the changes will be reflected back. To return an existing Ruby object
+    /// held internally see [`ReprValue::frozen_copy`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, rb_assert, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let buf = vec![104, 101, 108, 108, 111];
+    ///     let s = ruby.str_frozen_from_slice(&buf);
+    ///     assert!(s.is_frozen());
+    ///     rb_assert!(ruby, r#"s == "hello".b"#, s);
+    ///     rb_assert!(ruby, "(s << 'x' rescue $!).is_a?(FrozenError)", s);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn str_frozen_from_slice(&self, s: &[u8]) -> RString {
+        self.str_from_slice(s).freeze()
+    }
+
     /// Create a new Ruby string from the value `s` with the encoding `enc`.
     ///
     /// # Examples
@@ -266,6 +349,54 @@ impl Ruby {
     {
         enc.into().chr(code)
     }
+
+    /// Create a new Ruby string by formatting `args` according to the format
+    /// string `fmt`.
+    ///
+    /// This uses the same formatting rules as Ruby's `Kernel#format` /
+    /// `String#%`, e.g. `%s` calls `to_s` on its argument, and `%p` calls
+    /// `inspect`. Named references like `%{name}` can be used by passing a
+    /// hash as the only argument.
+    ///
+    /// Returns `Err` with an `ArgumentError` if `fmt` is malformed or the
+    /// number of arguments doesn't match. Note that extra arguments are only
+    /// an error when Ruby is run in debug mode (`$DEBUG = true`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{rb_assert, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let s = ruby.str_format("%s: %05.1f %p", ("pi", 3.14159, "raw"))?;
+    ///     rb_assert!(ruby, r#"s == "pi: 003.1 \"raw\"""#, s);
+    ///
+    ///     let hash = ruby.hash_new();
+    ///     hash.aset(ruby.to_symbol("name"), "world")?;
+    ///     let s = ruby.str_format("hello %{name}", (hash,))?;
+    ///     rb_assert!(ruby, r#"s == "hello world""#, s);
+    ///
+    ///     assert!(ruby.str_format("%s %s", ("only one",)).is_err());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn str_format<A>(&self, fmt: &str, args: A) -> Result<RString, Error>
+    where
+        A: ArgList,
+    {
+        let fmt = self.str_new(fmt);
+        let args = args.into_arg_list_with(self);
+        let slice = args.as_ref();
+        protect(|| unsafe {
+            RString::from_rb_value_unchecked(rb_str_format(
+                slice.len() as c_int,
+                slice.as_ptr() as *const VALUE,
+                fmt.as_rb_value(),
+            ))
+        })
+    }
 }
 
 /// A Value pointer to a RString struct, Ruby's internal representation of
@@ -613,6 +744,46 @@ impl RString {
         unsafe { Self::from_rb_value_unchecked(rb_str_new_frozen(s.as_rb_value())) }
     }
 
+    /// Returns `self` if it is not frozen, otherwise returns a new mutable
+    /// string with the same contents.
+    ///
+    /// Similar to [`Cow::to_mut`](std::borrow::Cow::to_mut), this allows
+    /// modifying a string without raising a `FrozenError`, while only paying
+    /// for a copy when required. The returned string shares its backing data
+    /// with `self` (see [`RString::new_shared`]) so the contents are only
+    /// duplicated once modified.
+    ///
+    /// Note that when `self` is not frozen, modifying the returned string
+    /// modifies `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, rb_assert, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let frozen = ruby.str_new("example").freeze();
+    ///     let s = frozen.to_mut();
+    ///     s.cat(" modified");
+    ///     rb_assert!(ruby, r#"s == "example modified""#, s);
+    ///     rb_assert!(ruby, r#"frozen == "example""#, frozen);
+    ///
+    ///     let unfrozen = ruby.str_new("example");
+    ///     let s = unfrozen.to_mut();
+    ///     assert!(s.is_same_object(unfrozen));
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn to_mut(self) -> Self {
+        if self.is_frozen() {
+            Self::new_shared(self)
+        } else {
+            self
+        }
+    }
+
     /// Return `self` as a slice of bytes.
     ///
     /// # Safety
@@ -1198,8 +1369,11 @@ impl RString {
         vec.into()
     }
 
-    /// Converts `self` to a [`char`]. Errors if the string is more than one
-    /// character or can not be encoded as UTF-8.
+    /// Converts `self` to a [`char`].
+    ///
+    /// Errors with an `ArgumentError` if the string is empty or contains more
+    /// than one codepoint, or with an `EncodingError` if it can not be
+    /// encoded as UTF-8.
     ///
     /// # Examples
     ///
@@ -1210,6 +1384,12 @@ impl RString {
     ///     let s = ruby.str_new("a");
     ///     assert_eq!(s.to_char()?, 'a');
     ///
+    ///     let s = ruby.str_new("🦀");
+    ///     assert_eq!(s.to_char()?, '🦀');
+    ///
+    ///     assert!(ruby.str_new("").to_char().is_err());
+    ///     assert!(ruby.str_new("ab").to_char().is_err());
+    ///
     ///     Ok(())
     /// }
     /// # Ruby::init(example).unwrap()
@@ -1221,16 +1401,17 @@ impl RString {
         } else {
             self.conv_enc(handle.utf8_encoding())?
         };
-        unsafe {
+        let mut chars = unsafe {
             str::from_utf8(utf8.as_slice())
                 .map_err(|e| Error::new(handle.exception_encoding_error(), format!("{}", e)))?
-                .parse()
-                .map_err(|e| {
-                    Error::new(
-                        handle.exception_type_error(),
-                        format!("could not convert string to char, {}", e),
-                    )
-                })
+                .chars()
+        };
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(Error::new(
+                handle.exception_arg_error(),
+                format!("expected a single character string, got {}", self.inspect()),
+            )),
         }
     }
 
@@ -1429,6 +1610,58 @@ impl RString {
         }
     }
 
+    /// Mutate `self`, adding `buf`, encoded as `enc`, to the end.
+    ///
+    /// Unlike [`cat`](RString::cat), this checks `enc` is compatible with the
+    /// encoding of `self`, returning `Err` (an `Encoding::CompatibilityError`)
+    /// if it is not. If `self` contains only ASCII characters its encoding may
+    /// be changed to `enc`, the same as when appending strings in Ruby.
+    ///
+    /// Combined with [`Ruby::str_with_capacity`] or [`Ruby::str_buf_new`] and
+    /// [`enc_associate`](EncodingCapable::enc_associate) this can be used to
+    /// efficiently build a large string. See [`RStringBuilder`] to build a
+    /// string whose encoding can't change.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{encoding::EncodingCapable, rb_assert, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let buf = ruby.str_with_capacity(4096);
+    ///     buf.enc_cat("café", ruby.utf8_encoding())?;
+    ///     buf.enc_cat(" au lait", ruby.usascii_encoding())?;
+    ///     rb_assert!(ruby, r#"buf == "café au lait""#, buf);
+    ///
+    ///     let buf = ruby.str_buf_new(4096);
+    ///     buf.enc_associate(ruby.find_encindex("EUC-JP")?)?;
+    ///     buf.enc_cat("abc", ruby.usascii_encoding())?;
+    ///     assert!(buf.enc_cat("café", ruby.utf8_encoding()).is_err());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn enc_cat<T, E>(self, buf: T, enc: E) -> Result<(), Error>
+    where
+        T: AsRef<[u8]>,
+        E: Into<RbEncoding>,
+    {
+        let buf = buf.as_ref();
+        let len = buf.len();
+        let ptr = buf.as_ptr();
+        let enc = enc.into();
+        protect(|| unsafe {
+            Value::new(rb_enc_str_buf_cat(
+                self.as_rb_value(),
+                ptr as *const c_char,
+                len as c_long,
+                enc.as_ptr(),
+            ))
+        })?;
+        Ok(())
+    }
+
     /// Replace the contents and encoding of `self` with those of `other`.
     ///
     /// # Examples
@@ -1685,8 +1918,98 @@ impl RString {
         self.len() == 0
     }
 
+    /// Returns whether `self` starts with any of `prefixes`.
+    ///
+    /// This matches Ruby's `String#start_with?` with String arguments, but
+    /// operates directly on the string's buffer rather than calling the Ruby
+    /// method. Bytes are compared exactly, and a prefix only matches if it
+    /// ends on a character boundary in `self`'s encoding.
+    ///
+    /// `prefixes` are UTF-8, so non-ASCII prefixes will only match strings
+    /// with a UTF-8 (or binary) encoding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let s = ruby.str_new("foobar");
+    ///     assert!(s.start_with(&["foo"]));
+    ///     assert!(s.start_with(&["baz", "foo"]));
+    ///     assert!(!s.start_with(&["bar", "baz"]));
+    ///     assert!(s.start_with(&[""]));
+    ///     assert!(!s.start_with(&[]));
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn start_with(self, prefixes: &[&str]) -> bool {
+        let bytes = unsafe { self.as_slice() };
+        prefixes.iter().any(|prefix| {
+            bytes.starts_with(prefix.as_bytes()) && self.is_char_boundary(bytes, prefix.len())
+        })
+    }
+
+    /// Returns whether `self` ends with any of `suffixes`.
+    ///
+    /// This matches Ruby's `String#end_with?`, but operates directly on the
+    /// string's buffer rather than calling the Ruby method. Bytes are
+    /// compared exactly, and a suffix only matches if it starts on a
+    /// character boundary in `self`'s encoding.
+    ///
+    /// `suffixes` are UTF-8, so non-ASCII suffixes will only match strings
+    /// with a UTF-8 (or binary) encoding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let s = ruby.str_new("foobar");
+    ///     assert!(s.end_with(&["bar"]));
+    ///     assert!(s.end_with(&["baz", "bar"]));
+    ///     assert!(!s.end_with(&["foo", "baz"]));
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn end_with(self, suffixes: &[&str]) -> bool {
+        let bytes = unsafe { self.as_slice() };
+        suffixes.iter().any(|suffix| {
+            bytes.ends_with(suffix.as_bytes())
+                && self.is_char_boundary(bytes, bytes.len() - suffix.len())
+        })
+    }
+
+    fn is_char_boundary(self, bytes: &[u8], pos: usize) -> bool {
+        if pos == 0 || pos == bytes.len() {
+            return true;
+        }
+        let handle = Ruby::get_with(self);
+        let enc = self.enc_get();
+        // UTF-8 is self-synchronising, so a complete UTF-8 match always falls
+        // on a character boundary. Binary strings have 1 byte characters.
+        if self.is_utf8_compatible_encoding() || enc == handle.ascii8bit_encindex() {
+            return true;
+        }
+        let enc = RbEncoding::from(enc);
+        let mut i = 0;
+        while i < pos {
+            i += enc.mbclen(&bytes[i..]);
+        }
+        i == pos
+    }
+
     /// Compares `self` with `other` to establish an ordering.
     ///
+    /// Strings are compared byte by byte, matching Ruby's `String#<=>`. If
+    /// the bytes are equal but the encodings differ, the ordering is decided
+    /// by the encodings, as with `String#<=>`.
+    ///
     /// # Examples
     ///
     /// ```
@@ -1786,74 +2109,518 @@ impl RString {
         let delim = CString::new(delim).unwrap();
         unsafe { RArray::from_rb_value_unchecked(rb_str_split(self.as_rb_value(), delim.as_ptr())) }
     }
-}
-
-impl fmt::Display for RString {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", unsafe { self.to_s_infallible() })
-    }
-}
-
-impl fmt::Debug for RString {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.inspect())
-    }
-}
-
-impl EncodingCapable for RString {}
-
-impl io::Write for RString {
-    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        let len = buf.len();
-        self.cat(buf);
-        Ok(len)
-    }
-
-    fn flush(&mut self) -> io::Result<()> {
-        Ok(())
-    }
-}
 
-/// Conversions from Rust types into [`RString`].
-pub trait IntoRString: Sized {
-    /// Convert `self` into [`RString`].
+    /// Return an iterator over the lines of `self`.
+    ///
+    /// Lines are separated by `sep`, or `"\n"` if `sep` is `None`. If `chomp`
+    /// is `false` each line includes its trailing separator, otherwise the
+    /// separator is removed (for a `"\n"` separator a preceding `"\r"` is
+    /// also removed). The final line is returned even if it does not end with
+    /// the separator. Separators are only matched at character boundaries
+    /// for `self`'s encoding, and each line has the same encoding as `self`.
+    ///
+    /// This is equivalent to Ruby's `String#each_line`, but lines are created
+    /// as they are iterated over, rather than all at once. The string is
+    /// re-read for each line, so it is safe to modify `self` while iterating.
+    ///
+    /// See also [`RString::line_indices`].
     ///
     /// # Panics
     ///
-    /// Panics if called from a non-Ruby thread. See
-    /// [`IntoRString::into_r_string_with`] for the non-panicking version.
-    #[cfg_attr(
-        not(feature = "old-api"),
-        deprecated(note = "please use `IntoRString::into_r_string_with` instead")
-    )]
-    #[cfg_attr(docsrs, doc(cfg(feature = "old-api")))]
-    #[inline]
-    fn into_r_string(self) -> RString {
-        self.into_r_string_with(&get_ruby!())
-    }
-
-    /// Convert `self` into [`RString`].
+    /// Panics if `sep` is an empty string. Ruby's 'paragraph mode' is not
+    /// supported.
     ///
-    /// # Safety
+    /// # Examples
     ///
-    /// This method should only be called from a Ruby thread.
-    unsafe fn into_r_string_unchecked(self) -> RString {
-        self.into_r_string_with(&Ruby::get_unchecked())
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let s = ruby.str_new("foo\nbar\r\nbaz");
+    ///     let lines = s
+    ///         .lines_iter(None, false)
+    ///         .map(|l| l.to_string())
+    ///         .collect::<Result<Vec<_>, Error>>()?;
+    ///     assert_eq!(lines, ["foo\n", "bar\r\n", "baz"]);
+    ///
+    ///     let lines = s
+    ///         .lines_iter(None, true)
+    ///         .map(|l| l.to_string())
+    ///         .collect::<Result<Vec<_>, Error>>()?;
+    ///     assert_eq!(lines, ["foo", "bar", "baz"]);
+    ///
+    ///     let s = ruby.str_new("a, b, c, ");
+    ///     let lines = s
+    ///         .lines_iter(Some(", "), true)
+    ///         .map(|l| l.to_string())
+    ///         .collect::<Result<Vec<_>, Error>>()?;
+    ///     assert_eq!(lines, ["a", "b", "c"]);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn lines_iter(self, sep: Option<&str>, chomp: bool) -> Lines {
+        let sep = sep.map(str::as_bytes).unwrap_or(b"\n");
+        assert!(!sep.is_empty(), "line separator must not be empty");
+        Lines {
+            string: self,
+            sep,
+            chomp,
+            pos: 0,
+        }
     }
 
-    /// Convert `self` into [`RString`].
-    fn into_r_string_with(self, handle: &Ruby) -> RString;
-}
-
-impl IntoRString for RString {
-    fn into_r_string_with(self, _: &Ruby) -> RString {
-        self
+    /// Return an iterator over the lines of `self`, along with the byte
+    /// offset of the start of each line.
+    ///
+    /// See [`RString::lines_iter`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sep` is an empty string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let s = ruby.str_new("foo\nbar\nbaz");
+    ///     let lines = s
+    ///         .line_indices(None, true)
+    ///         .map(|(i, l)| Ok((i, l.to_string()?)))
+    ///         .collect::<Result<Vec<_>, Error>>()?;
+    ///     assert_eq!(
+    ///         lines,
+    ///         [
+    ///             (0, String::from("foo")),
+    ///             (4, String::from("bar")),
+    ///             (8, String::from("baz"))
+    ///         ]
+    ///     );
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn line_indices(self, sep: Option<&str>, chomp: bool) -> LineIndices {
+        LineIndices(self.lines_iter(sep, chomp))
     }
-}
 
-impl IntoRString for &str {
-    fn into_r_string_with(self, handle: &Ruby) -> RString {
-        handle.str_new(self)
+    /// Return an iterator over `self`'s characters.
+    ///
+    /// Characters are found according to `self`'s encoding, and converted to
+    /// Rust [`char`]s, transcoding to UTF-8 if required. Invalid byte
+    /// sequences, or characters that can not be represented as a `char`, will
+    /// produce an `Err`, iteration then continues from the next character.
+    ///
+    /// Unlike [`RString::codepoints`] the iterator holds `self`, rather than a
+    /// reference to its contents, so this is safe even if the string is
+    /// modified during iteration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, Error, RString, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let s = ruby.str_new("🦀 café");
+    ///     let chars = s.chars_iter().collect::<Result<Vec<_>, Error>>()?;
+    ///     assert_eq!(chars, ['🦀', ' ', 'c', 'a', 'f', 'é']);
+    ///
+    ///     let s: RString = eval!(ruby, r#""café".encode("ISO-8859-1")"#)?;
+    ///     let chars = s.chars_iter().collect::<Result<Vec<_>, Error>>()?;
+    ///     assert_eq!(chars, ['c', 'a', 'f', 'é']);
+    ///
+    ///     let s: RString = eval!(ruby, r#""a\xffb""#)?;
+    ///     let mut chars = s.chars_iter();
+    ///     assert_eq!(chars.next().unwrap()?, 'a');
+    ///     assert!(chars.next().unwrap().is_err());
+    ///     assert_eq!(chars.next().unwrap()?, 'b');
+    ///     assert!(chars.next().is_none());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn chars_iter(self) -> Chars {
+        Chars {
+            string: self,
+            encoding: self.enc_get().into(),
+            utf8: self.is_utf8_compatible_encoding(),
+            pos: 0,
+        }
+    }
+
+    /// Return an iterator over `self`'s characters, along with the byte
+    /// offset of the start of each character.
+    ///
+    /// See [`RString::chars_iter`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let s = ruby.str_new("🦀 café");
+    ///     let chars = s.char_indices().collect::<Result<Vec<_>, Error>>()?;
+    ///     assert_eq!(
+    ///         chars,
+    ///         [(0, '🦀'), (4, ' '), (5, 'c'), (6, 'a'), (7, 'f'), (8, 'é')]
+    ///     );
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn char_indices(self) -> CharIndices {
+        CharIndices(self.chars_iter())
+    }
+
+    /// Call `func` with each grapheme cluster in `self`.
+    ///
+    /// This calls Ruby's `String#each_grapheme_cluster`, so segmentation
+    /// matches the Unicode version of the running Ruby.
+    ///
+    /// To stop iterating early return the error from
+    /// [`Ruby::iter_break_value`] from `func`. Any other error returned by
+    /// `func` will stop iteration and be returned from this function.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let s = ruby.str_new("🇬🇧e\u{301}!");
+    ///     let mut graphemes = Vec::new();
+    ///     s.each_grapheme(|g| {
+    ///         graphemes.push(g.to_string()?);
+    ///         Ok(())
+    ///     })?;
+    ///     assert_eq!(graphemes, ["🇬🇧", "e\u{301}", "!"]);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn each_grapheme<F>(self, mut func: F) -> Result<(), Error>
+    where
+        F: FnMut(RString) -> Result<(), Error>,
+    {
+        let handle = Ruby::get_with(self);
+        block_call_closure(
+            self.as_value(),
+            "each_grapheme_cluster".into_id_with(&handle),
+            &[],
+            |val| func(unsafe { RString::from_rb_value_unchecked(val.as_rb_value()) }),
+        )
+    }
+
+    /// Return an Array of the grapheme clusters in `self`.
+    ///
+    /// This calls Ruby's `String#grapheme_clusters`. See
+    /// [`RString::each_grapheme`] to avoid collecting the grapheme clusters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let s = ruby.str_new("🇬🇧e\u{301}!");
+    ///     let graphemes = s.grapheme_clusters()?.to_vec::<String>()?;
+    ///     assert_eq!(graphemes, ["🇬🇧", "e\u{301}", "!"]);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn grapheme_clusters(self) -> Result<RArray, Error> {
+        self.funcall("grapheme_clusters", ())
+    }
+
+    /// Return a copy of `self` with all characters converted to uppercase.
+    ///
+    /// This is equivalent to Ruby's `String#upcase` with no options, so uses
+    /// full Unicode case mapping for Unicode encodings, and the encoding of
+    /// the returned string is the same as `self`.
+    ///
+    /// Ruby's C API doesn't expose string case mapping, so this calls the
+    /// Ruby method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let s = ruby.str_new("straße");
+    ///     assert_eq!(s.upcase()?.to_string()?, "STRASSE");
+    ///     assert_eq!(s.to_string()?, "straße");
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn upcase(self) -> Result<Self, Error> {
+        self.funcall("upcase", ())
+    }
+
+    /// Convert all characters in `self` to uppercase, modifying `self`.
+    ///
+    /// Returns `Ok(false)` if no changes were made. Returns `Err` if `self` is
+    /// frozen.
+    ///
+    /// See [`RString::upcase`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let s = ruby.str_new("foo");
+    ///     assert!(s.upcase_in_place()?);
+    ///     assert_eq!(s.to_string()?, "FOO");
+    ///     assert!(!s.upcase_in_place()?);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn upcase_in_place(self) -> Result<bool, Error> {
+        self.funcall_in_place("upcase!")
+    }
+
+    /// Return a copy of `self` with all characters converted to lowercase.
+    ///
+    /// This is equivalent to Ruby's `String#downcase` with no options, so
+    /// uses full Unicode case mapping for Unicode encodings, and the encoding
+    /// of the returned string is the same as `self`.
+    ///
+    /// Ruby's C API doesn't expose string case mapping, so this calls the
+    /// Ruby method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let s = ruby.str_new("ÀÉÎ");
+    ///     assert_eq!(s.downcase()?.to_string()?, "àéî");
+    ///     assert_eq!(s.to_string()?, "ÀÉÎ");
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn downcase(self) -> Result<Self, Error> {
+        self.funcall("downcase", ())
+    }
+
+    /// Convert all characters in `self` to lowercase, modifying `self`.
+    ///
+    /// Returns `Ok(false)` if no changes were made. Returns `Err` if `self` is
+    /// frozen.
+    ///
+    /// See [`RString::downcase`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let s = ruby.str_new("FOO");
+    ///     assert!(s.downcase_in_place()?);
+    ///     assert_eq!(s.to_string()?, "foo");
+    ///     assert!(!s.downcase_in_place()?);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn downcase_in_place(self) -> Result<bool, Error> {
+        self.funcall_in_place("downcase!")
+    }
+
+    /// Return a copy of `self` with leading and trailing whitespace and null
+    /// bytes removed.
+    ///
+    /// This is equivalent to Ruby's `String#strip`. Whitespace is defined as
+    /// any of the ASCII characters space, tab, line feed, vertical tab, form
+    /// feed, and carriage return.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let s = ruby.str_new("\t foo bar \r\n");
+    ///     assert_eq!(s.strip()?.to_string()?, "foo bar");
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn strip(self) -> Result<Self, Error> {
+        self.funcall("strip", ())
+    }
+
+    /// Remove leading and trailing whitespace and null bytes from `self`,
+    /// modifying `self`.
+    ///
+    /// Returns `Ok(false)` if no changes were made. Returns `Err` if `self` is
+    /// frozen.
+    ///
+    /// See [`RString::strip`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let s = ruby.str_new("  foo  ");
+    ///     assert!(s.strip_in_place()?);
+    ///     assert_eq!(s.to_string()?, "foo");
+    ///     assert!(!s.strip_in_place()?);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn strip_in_place(self) -> Result<bool, Error> {
+        self.funcall_in_place("strip!")
+    }
+
+    /// Return a copy of `self` with a single trailing line separator (`\n`,
+    /// `\r`, or `\r\n`) removed.
+    ///
+    /// This is equivalent to Ruby's `String#chomp` with no arguments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     assert_eq!(ruby.str_new("foo\r\n").chomp()?.to_string()?, "foo");
+    ///     assert_eq!(ruby.str_new("foo\n\n").chomp()?.to_string()?, "foo\n");
+    ///     assert_eq!(ruby.str_new("foo").chomp()?.to_string()?, "foo");
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn chomp(self) -> Result<Self, Error> {
+        self.funcall("chomp", ())
+    }
+
+    /// Remove a single trailing line separator (`\n`, `\r`, or `\r\n`) from
+    /// `self`, modifying `self`.
+    ///
+    /// Returns `Ok(false)` if no changes were made. Returns `Err` if `self` is
+    /// frozen.
+    ///
+    /// See [`RString::chomp`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let s = ruby.str_new("foo\n");
+    ///     assert!(s.chomp_in_place()?);
+    ///     assert_eq!(s.to_string()?, "foo");
+    ///     assert!(!s.chomp_in_place()?);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn chomp_in_place(self) -> Result<bool, Error> {
+        self.funcall_in_place("chomp!")
+    }
+
+    // Ruby's bang methods return `nil` if no changes were made.
+    fn funcall_in_place(self, method: &str) -> Result<bool, Error> {
+        self.funcall::<_, _, Value>(method, ()).map(|v| !v.is_nil())
+    }
+}
+
+impl fmt::Display for RString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", unsafe { self.to_s_infallible() })
+    }
+}
+
+impl fmt::Debug for RString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.inspect())
+    }
+}
+
+impl EncodingCapable for RString {}
+
+impl io::Write for RString {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = buf.len();
+        self.cat(buf);
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Conversions from Rust types into [`RString`].
+pub trait IntoRString: Sized {
+    /// Convert `self` into [`RString`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from a non-Ruby thread. See
+    /// [`IntoRString::into_r_string_with`] for the non-panicking version.
+    #[cfg_attr(
+        not(feature = "old-api"),
+        deprecated(note = "please use `IntoRString::into_r_string_with` instead")
+    )]
+    #[cfg_attr(docsrs, doc(cfg(feature = "old-api")))]
+    #[inline]
+    fn into_r_string(self) -> RString {
+        self.into_r_string_with(&get_ruby!())
+    }
+
+    /// Convert `self` into [`RString`].
+    ///
+    /// # Safety
+    ///
+    /// This method should only be called from a Ruby thread.
+    unsafe fn into_r_string_unchecked(self) -> RString {
+        self.into_r_string_with(&Ruby::get_unchecked())
+    }
+
+    /// Convert `self` into [`RString`].
+    fn into_r_string_with(self, handle: &Ruby) -> RString;
+}
+
+impl IntoRString for RString {
+    fn into_r_string_with(self, _: &Ruby) -> RString {
+        self
+    }
+}
+
+impl IntoRString for &str {
+    fn into_r_string_with(self, handle: &Ruby) -> RString {
+        handle.str_new(self)
     }
 }
 
@@ -1981,6 +2748,114 @@ impl TryConvert for RString {
     }
 }
 
+/// Builds an [`RString`] of a single encoding, fixed when the builder is
+/// created.
+///
+/// Appends are checked against the builder's encoding, and unlike
+/// [`RString::enc_cat`] never change it. Bytes in another encoding are only
+/// accepted if they are all ASCII and both encodings are ASCII compatible.
+///
+/// See [`Ruby::str_builder`] to create an `RStringBuilder`.
+///
+/// As this holds an `RString`, it must not be moved to the heap.
+pub struct RStringBuilder {
+    s: RString,
+    enc: Index,
+}
+
+impl RStringBuilder {
+    /// Append the Rust string `s`.
+    ///
+    /// Errors with an `Encoding::CompatibilityError` if the builder's
+    /// encoding is not UTF-8 and `s` is not compatible with it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{rb_assert, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let mut builder = ruby.str_builder(16, ruby.usascii_encindex())?;
+    ///     builder.push_str("abc")?;
+    ///     assert!(builder.push_str("café").is_err());
+    ///     let s = builder.finish();
+    ///     rb_assert!(ruby, r#"s == "abc" && s.encoding == Encoding::US_ASCII"#, s);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn push_str(&mut self, s: &str) -> Result<(), Error> {
+        let utf8 = Ruby::get_with(self.s).utf8_encindex();
+        self.push_enc(s, utf8)
+    }
+
+    /// Append `buf`, encoded as `enc`.
+    ///
+    /// Errors with an `Encoding::CompatibilityError` if `enc` is not the
+    /// builder's encoding and `buf` is not compatible with it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{rb_assert, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let mut builder = ruby.str_builder(16, ruby.find_encindex("EUC-JP")?)?;
+    ///     builder.push_enc("abc", ruby.utf8_encindex())?;
+    ///     builder.push_enc(&[0xa4, 0xa2], ruby.find_encindex("EUC-JP")?)?;
+    ///     assert!(builder.push_enc(&[0xff], ruby.ascii8bit_encindex()).is_err());
+    ///     let s = builder.finish();
+    ///     rb_assert!(ruby, r#"s == "abc\u3042".encode("EUC-JP")"#, s);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn push_enc<T, E>(&mut self, buf: T, enc: E) -> Result<(), Error>
+    where
+        T: AsRef<[u8]>,
+        E: Into<Index>,
+    {
+        let buf = buf.as_ref();
+        let enc = enc.into();
+        if enc != self.enc
+            && !(buf.is_ascii() && is_ascii_compatible(self.enc) && is_ascii_compatible(enc))
+        {
+            return Err(Error::new(
+                Ruby::get_with(self.s).exception_enc_compat_error(),
+                format!(
+                    "incompatible character encodings: {} and {}",
+                    RbEncoding::from(self.enc).name(),
+                    RbEncoding::from(enc).name()
+                ),
+            ));
+        }
+        self.s.cat(buf);
+        Ok(())
+    }
+
+    /// Returns the number of bytes appended so far.
+    pub fn len(&self) -> usize {
+        self.s.len()
+    }
+
+    /// Returns whether nothing has been appended yet.
+    pub fn is_empty(&self) -> bool {
+        self.s.is_empty()
+    }
+
+    /// Finish building, returning the string.
+    pub fn finish(self) -> RString {
+        self.s
+    }
+}
+
+fn is_ascii_compatible(enc: Index) -> bool {
+    let enc = RbEncoding::from(enc);
+    enc.mbminlen() == 1 && unsafe { rb_enc_dummy_p(enc.as_ptr()) } == 0
+}
+
 /// FString contains an RString known to be interned.
 ///
 /// Interned strings won't be garbage collected or modified, so should be
@@ -2179,6 +3054,158 @@ impl<'a> Iterator for CharBytes<'a> {
     }
 }
 
+/// An iterator over the lines of a Ruby string.
+///
+/// See [`RString::lines_iter`].
+pub struct Lines<'a> {
+    string: RString,
+    sep: &'a [u8],
+    chomp: bool,
+    pos: usize,
+}
+
+impl<'a> Lines<'a> {
+    fn next_line(&mut self) -> Option<(usize, RString)> {
+        let start = self.pos;
+        // find the line's bounds before creating the new string, as that can
+        // trigger GC, which may move the string's contents
+        let (end, next) = {
+            let slice = unsafe { self.string.as_slice() };
+            if start >= slice.len() {
+                return None;
+            }
+            let encoding: RbEncoding = self.string.enc_get().into();
+            let mut i = start;
+            loop {
+                if i >= slice.len() {
+                    break (slice.len(), slice.len());
+                }
+                if slice[i..].starts_with(self.sep) {
+                    let next = i + self.sep.len();
+                    if !self.chomp {
+                        break (next, next);
+                    }
+                    let mut end = i;
+                    if self.sep == b"\n" && end > start && slice[end - 1] == b'\r' {
+                        end -= 1;
+                    }
+                    break (end, next);
+                }
+                i += encoding.mbclen(&slice[i..]);
+            }
+        };
+        self.pos = next;
+        let line = unsafe {
+            RString::from_rb_value_unchecked(rb_str_subseq(
+                self.string.as_rb_value(),
+                start as c_long,
+                (end - start) as c_long,
+            ))
+        };
+        Some((start, line))
+    }
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = RString;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_line().map(|(_, line)| line)
+    }
+}
+
+/// An iterator over the lines of a Ruby string, along with the byte offset
+/// of the start of each line.
+///
+/// See [`RString::line_indices`].
+pub struct LineIndices<'a>(Lines<'a>);
+
+impl<'a> Iterator for LineIndices<'a> {
+    type Item = (usize, RString);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next_line()
+    }
+}
+
+/// An iterator over the characters of a Ruby string.
+///
+/// See [`RString::chars_iter`].
+pub struct Chars {
+    string: RString,
+    encoding: RbEncoding,
+    utf8: bool,
+    pos: usize,
+}
+
+impl Chars {
+    fn next_char(&mut self) -> Option<(usize, Result<char, Error>)> {
+        let start = self.pos;
+        // find the char's bounds before any conversion, as that can trigger
+        // GC, which may move the string's contents
+        let (len, c) = {
+            let slice = unsafe { self.string.as_slice() };
+            if start >= slice.len() {
+                return None;
+            }
+            let rest = &slice[start..];
+            match self.encoding.precise_mbclen(rest) {
+                MbcLen::CharFound(len) if self.utf8 => (
+                    len,
+                    str::from_utf8(&rest[..len])
+                        .ok()
+                        .and_then(|s| s.chars().next()),
+                ),
+                MbcLen::CharFound(len) => (len, None),
+                MbcLen::NeedMore(_) | MbcLen::Invalid => {
+                    self.pos = start + self.encoding.mbclen(rest).clamp(1, rest.len());
+                    let handle = Ruby::get_with(self.string);
+                    let err = Error::new(
+                        handle.exception_encoding_error(),
+                        format!("invalid byte sequence in {}", self.encoding.name()),
+                    );
+                    return Some((start, Err(err)));
+                }
+            }
+        };
+        self.pos = start + len;
+        let res = match c {
+            Some(c) => Ok(c),
+            None => unsafe {
+                RString::from_rb_value_unchecked(rb_str_subseq(
+                    self.string.as_rb_value(),
+                    start as c_long,
+                    len as c_long,
+                ))
+            }
+            .to_char(),
+        };
+        Some((start, res))
+    }
+}
+
+impl Iterator for Chars {
+    type Item = Result<char, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_char().map(|(_, c)| c)
+    }
+}
+
+/// An iterator over the characters of a Ruby string, along with the byte
+/// offset of the start of each character.
+///
+/// See [`RString::char_indices`].
+pub struct CharIndices(Chars);
+
+impl Iterator for CharIndices {
+    type Item = Result<(usize, char), Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next_char().map(|(i, c)| c.map(|c| (i, c)))
+    }
+}
+
 /// Create a [`RString`] from a Rust str literal.
 ///
 /// # Panics
@@ -2209,3 +3236,58 @@ macro_rules! r_string {
         unsafe { $ruby.str_new_lit(s.as_ptr() as *const _, len as _) }
     }};
 }
+
+/// Get a frozen [`RString`] for a Rust str literal, creating it on first use.
+///
+/// The string is created once, deduplicated with Ruby's other frozen string
+/// literals (on Ruby 3.0+), and stored in a hidden `static`
+/// [`Lazy`](crate::value::Lazy), so subsequent calls return the same instance
+/// without allocating. This is useful for constant strings, such as status
+/// strings or header names, returned from frequently called methods.
+///
+/// For a string that needs to be shared between multiple functions, assign
+/// it to a `static` [`Lazy`](crate::value::Lazy) instead:
+///
+/// ```
+/// use magnus::{prelude::*, value::Lazy, RString};
+///
+/// static STATUS_OK: Lazy<RString> = Lazy::new(|ruby| ruby.str_new("OK").freeze());
+/// ```
+///
+/// # Panics
+///
+/// Panics if called from a non-Ruby thread.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{frozen_str, prelude::*, rb_assert, Error, RString, Ruby};
+///
+/// fn status(ruby: &Ruby) -> RString {
+///     frozen_str!(ruby, "OK")
+/// }
+///
+/// fn example(ruby: &Ruby) -> Result<(), Error> {
+///     let a = status(ruby);
+///     let b = status(ruby);
+///     assert!(a.is_frozen());
+///     rb_assert!(ruby, r#"a == "OK" && a.equal?(b)"#, a, b);
+///
+///     Ok(())
+/// }
+/// # Ruby::init(example).unwrap()
+/// ```
+#[macro_export]
+macro_rules! frozen_str {
+    ($lit:expr) => {{
+        $crate::frozen_str!($crate::Ruby::get().unwrap(), $lit)
+    }};
+    ($ruby:expr, $lit:expr) => {{
+        static STR: $crate::value::Lazy<$crate::RString> = $crate::value::Lazy::new(|ruby| {
+            let s = concat!($lit, "\0");
+            let len = s.len() - 1;
+            unsafe { ruby.str_new_frozen_lit(s.as_ptr() as *const _, len as _) }
+        });
+        $ruby.get_inner(&STR)
+    }};
+}
@@ -14,8 +14,8 @@ use std::{
 #[cfg(ruby_gte_3_3)]
 use rb_sys::rb_data_define;
 use rb_sys::{
-    rb_struct_aref, rb_struct_aset, rb_struct_define, rb_struct_getmember, rb_struct_members,
-    rb_struct_size, ruby_value_type, VALUE,
+    rb_apply, rb_struct_aref, rb_struct_aset, rb_struct_define, rb_struct_getmember,
+    rb_struct_members, rb_struct_size, ruby_value_type, VALUE,
 };
 use seq_macro::seq;
 
@@ -435,7 +435,18 @@ impl Ruby {
     /// If provided, `super_class` must be a subclass of Ruby's `Data` class
     /// (or `Data` itself).
     ///
-    /// `members` is a tuple of `&str`, of between lengths 1 to 12 inclusive.
+    /// `members` is a tuple of `&str`, of between lengths 1 to 12 inclusive,
+    /// or a slice of `&str`.
+    ///
+    /// Instances of Data classes are immutable, and can be read with the
+    /// [`RStruct`] methods, such as [`RStruct::getmember`].
+    ///
+    /// # Errors
+    ///
+    /// Ruby's `Data` class was added in Ruby 3.2, on older Rubies this
+    /// returns a `NotImpError`.
+    ///
+    /// # Examples
     ///
     /// ```
     /// use magnus::{kwargs, prelude::*, Error, Ruby};
@@ -453,8 +464,24 @@ impl Ruby {
     /// }
     /// # Ruby::init(example).unwrap()
     /// ```
-    #[cfg(any(ruby_gte_3_3, docsrs))]
-    #[cfg_attr(docsrs, doc(cfg(ruby_gte_3_3)))]
+    ///
+    /// ```
+    /// use magnus::{kwargs, prelude::*, Error, RStruct, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let members = ["x", "y"];
+    ///     let point_class = ruby.define_data(None, &members[..])?;
+    ///
+    ///     let point: RStruct = point_class.new_instance((kwargs!("x" => 1, "y" => 2),))?;
+    ///     assert_eq!(point.getmember::<_, i64>("x")?, 1);
+    ///     assert_eq!(point.getmember::<_, i64>("y")?, 2);
+    ///     assert_eq!(point.members()?, ["x", "y"]);
+    ///     assert!(point.is_frozen());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
     pub fn define_data<T>(&self, super_class: Option<RClass>, members: T) -> Result<RClass, Error>
     where
         T: StructMembers,
@@ -525,7 +552,6 @@ mod private {
     pub trait StructMembers {
         fn define(self, name: Option<&str>) -> Result<RClass, Error>;
 
-        #[cfg(ruby_gte_3_3)]
         fn define_data(self, super_class: Option<RClass>) -> Result<RClass, Error>;
     }
 }
@@ -558,6 +584,11 @@ macro_rules! impl_struct_members {
                         ))
                     })
                 }
+
+                #[cfg(not(ruby_gte_3_3))]
+                fn define_data(self, super_class: Option<RClass>) -> Result<RClass, Error> {
+                    data_define(super_class, &[#(self.N,)*])
+                }
             }
         });
     }
@@ -566,3 +597,63 @@ macro_rules! impl_struct_members {
 seq!(N in 1..=12 {
     impl_struct_members!(N);
 });
+
+impl StructMembers for &[&str] {
+    fn define(self, name: Option<&str>) -> Result<RClass, Error> {
+        let handle = unsafe { Ruby::get_unchecked() };
+        // collected in to an RArray, rather than a Vec, so the new strings and
+        // symbols are visible to the GC
+        let args = handle.ary_new_capa(self.len() + 1);
+        if let Some(name) = name {
+            args.push(handle.str_new(name))?;
+        }
+        for member in self {
+            args.push(handle.to_symbol(member))?;
+        }
+        apply(handle.class_struct(), "new", args)
+    }
+
+    fn define_data(self, super_class: Option<RClass>) -> Result<RClass, Error> {
+        data_define(super_class, self)
+    }
+}
+
+// Call `method` on `recv` with the elements of `args` as the arguments.
+fn apply(recv: RClass, method: &str, args: RArray) -> Result<RClass, Error> {
+    let handle = Ruby::get_with(recv);
+    let id = method.into_id_with(&handle);
+    protect(|| unsafe {
+        Value::new(rb_apply(
+            recv.as_rb_value(),
+            id.as_rb_id(),
+            args.as_rb_value(),
+        ))
+    })
+    .and_then(TryConvert::try_convert)
+}
+
+// rb_data_define is only available from Ruby 3.3, so call `Data.define`.
+#[cfg(not(ruby_lt_3_2))]
+fn data_define(super_class: Option<RClass>, members: &[&str]) -> Result<RClass, Error> {
+    use crate::module::Module;
+
+    let handle = unsafe { Ruby::get_unchecked() };
+    let super_class = match super_class {
+        Some(class) => class,
+        None => handle.class_object().const_get("Data")?,
+    };
+    let args = handle.ary_new_capa(members.len());
+    for member in members {
+        args.push(handle.to_symbol(member))?;
+    }
+    apply(super_class, "define", args)
+}
+
+#[cfg(ruby_lt_3_2)]
+fn data_define(_super_class: Option<RClass>, _members: &[&str]) -> Result<RClass, Error> {
+    let handle = unsafe { Ruby::get_unchecked() };
+    Err(Error::new(
+        handle.exception_not_imp_error(),
+        "Data is only available in Ruby 3.2 and later",
+    ))
+}
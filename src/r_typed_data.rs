@@ -8,6 +8,7 @@ use crate::{
     into_value::IntoValue,
     module::Module,
     object::Object,
+    try_convert::TryConvert,
     typed_data::TypedData,
     value::{
         private::{self, ReprValue as _},
@@ -345,6 +346,10 @@ impl RTypedData {
 
     /// Get a reference to the Rust type wrapped in the Ruby object `self`.
     ///
+    /// Wrapped data can only be borrowed immutably, as references to it can't
+    /// be tracked across calls back in to Ruby. Use interior mutability, such
+    /// as a [`RefCell`](std::cell::RefCell), to mutate it.
+    ///
     /// # Examples
     ///
     /// ```
@@ -408,6 +413,24 @@ impl RTypedData {
             )
         })
     }
+
+    /// Get a reference to the Rust type wrapped in the Ruby object `self`,
+    /// without checking the type.
+    ///
+    /// # Safety
+    ///
+    /// `self` must wrap a `T`. This method can magic any lifetime needed out
+    /// of thin air, even `'static`.
+    #[inline]
+    pub(crate) unsafe fn get_unchecked<'a, T>(self) -> &'a T
+    where
+        T: TypedData,
+    {
+        &*(rb_check_typeddata(
+            self.as_rb_value(),
+            T::data_type().as_rb_data_type() as *const _,
+        ) as *const T)
+    }
 }
 
 impl fmt::Display for RTypedData {
@@ -434,3 +457,9 @@ impl Object for RTypedData {}
 unsafe impl private::ReprValue for RTypedData {}
 
 impl ReprValue for RTypedData {}
+
+impl TryConvert for RTypedData {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        Self::from_value(val).ok_or_else(|| Error::type_error("typed data", val))
+    }
+}
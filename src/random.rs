@@ -0,0 +1,251 @@
+//! Types for working with Ruby's random number generator.
+
+use std::{fmt, os::raw::c_ulong};
+
+use rb_sys::{rb_random_bytes, rb_random_real, rb_random_ulong_limited};
+
+use crate::{
+    error::{protect, Error},
+    integer::Integer,
+    into_value::IntoValue,
+    object::Object,
+    r_string::RString,
+    r_typed_data::RTypedData,
+    try_convert::TryConvert,
+    value::{
+        private::{self, ReprValue as _},
+        ReprValue, Value,
+    },
+    Ruby,
+};
+
+/// # `Random`
+///
+/// Functions that can be used to create Ruby `Random` number generators.
+///
+/// See also the [`Random`] type.
+impl Ruby {
+    /// Create a new Ruby `Random` number generator with a random seed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let rng = ruby.random_new()?;
+    ///     assert!(rng.rand_float()? < 1.0);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn random_new(&self) -> Result<Random, Error> {
+        self.class_random().funcall("new", ())
+    }
+
+    /// Create a new Ruby `Random` number generator from `seed`.
+    ///
+    /// Generators created with the same seed produce the same sequence of
+    /// numbers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let a = ruby.random_from_seed(1234)?;
+    ///     let b = ruby.random_from_seed(1234)?;
+    ///     assert_eq!(a.rand_int(100)?, b.rand_int(100)?);
+    ///     assert_eq!(a.seed()?.to_u64()?, 1234);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn random_from_seed<T>(&self, seed: T) -> Result<Random, Error>
+    where
+        T: IntoValue,
+    {
+        self.class_random()
+            .funcall("new", (seed.into_value_with(self),))
+    }
+}
+
+/// Wrapper type for a Value known to be an instance of Ruby's `Random` class.
+///
+/// This allows an extension to draw random numbers from a generator passed in
+/// from Ruby, sharing its state, so that e.g. results are reproducible when
+/// the generator is seeded from Ruby.
+///
+/// See the [`ReprValue`] and [`Object`] traits for additional methods
+/// available on this type. See [`Ruby`](Ruby#random) for methods to create a
+/// `Random`.
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct Random(RTypedData);
+
+impl Random {
+    /// Return `Some(Random)` if `val` is a `Random`, `None` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Random, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     assert!(Random::from_value(ruby.eval("Random.new")?).is_some());
+    ///     assert!(Random::from_value(ruby.eval("1")?).is_none());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    #[inline]
+    pub fn from_value(val: Value) -> Option<Self> {
+        let random_class = Ruby::get_with(val).class_random();
+        RTypedData::from_value(val)
+            .filter(|_| val.is_kind_of(random_class))
+            .map(Self)
+    }
+
+    /// Return a random integer greater than or equal to 0 and less than
+    /// `max`, as with Ruby's `Random#rand` with an Integer argument.
+    ///
+    /// Errors if `max` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let rng = ruby.random_new()?;
+    ///     assert!(rng.rand_int(6)? < 6);
+    ///     assert!(rng.rand_int(0).is_err());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn rand_int(self, max: u64) -> Result<u64, Error> {
+        let handle = Ruby::get_with(self);
+        if max == 0 {
+            return Err(Error::new(
+                handle.exception_arg_error(),
+                "invalid argument - 0",
+            ));
+        }
+        let limit = match c_ulong::try_from(max - 1) {
+            Ok(limit) => limit,
+            // c_ulong is 32 bits on some platforms
+            Err(_) => return self.funcall("rand", (max,)),
+        };
+        let mut res = 0;
+        protect(|| {
+            unsafe { res = rb_random_ulong_limited(self.as_rb_value(), limit) };
+            handle.qnil()
+        })?;
+        Ok(res as u64)
+    }
+
+    /// Return a random float greater than or equal to 0.0 and less than 1.0,
+    /// as with Ruby's `Random#rand` with no arguments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let rng = ruby.random_new()?;
+    ///     let f = rng.rand_float()?;
+    ///     assert!((0.0..1.0).contains(&f));
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn rand_float(self) -> Result<f64, Error> {
+        let handle = Ruby::get_with(self);
+        let mut res = 0.0;
+        protect(|| {
+            unsafe { res = rb_random_real(self.as_rb_value()) };
+            handle.qnil()
+        })?;
+        Ok(res)
+    }
+
+    /// Return a binary String of `n` random bytes, as with Ruby's
+    /// `Random#bytes`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let rng = ruby.random_new()?;
+    ///     assert_eq!(rng.bytes(16)?.len(), 16);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn bytes(self, n: usize) -> Result<RString, Error> {
+        protect(|| unsafe {
+            RString::from_rb_value_unchecked(rb_random_bytes(self.as_rb_value(), n as _))
+        })
+    }
+
+    /// Return the seed used to initialise `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let rng = ruby.random_from_seed(42)?;
+    ///     assert_eq!(rng.seed()?.to_i64()?, 42);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn seed(self) -> Result<Integer, Error> {
+        self.funcall("seed", ())
+    }
+}
+
+impl fmt::Display for Random {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", unsafe { self.to_s_infallible() })
+    }
+}
+
+impl fmt::Debug for Random {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.inspect())
+    }
+}
+
+impl IntoValue for Random {
+    #[inline]
+    fn into_value_with(self, _: &Ruby) -> Value {
+        self.0.as_value()
+    }
+}
+
+impl Object for Random {}
+
+unsafe impl private::ReprValue for Random {}
+
+impl ReprValue for Random {}
+
+impl TryConvert for Random {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        Self::from_value(val).ok_or_else(|| Error::type_error("Random", val))
+    }
+}
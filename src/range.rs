@@ -10,8 +10,10 @@ use rb_sys::{rb_range_beg_len, rb_range_new};
 
 use crate::{
     error::{protect, Error},
+    integer::Integer,
     into_value::{IntoValue, IntoValueFromNative},
     object::Object,
+    r_string::RString,
     r_struct::RStruct,
     try_convert::TryConvert,
     value::{
@@ -293,6 +295,166 @@ impl Range {
         let (beg, len) = self.beg_len(length)?;
         Ok(beg..(beg + len))
     }
+
+    /// Call `func` with each integer in the range.
+    ///
+    /// This iterates in Rust, avoiding the overhead of calling Ruby's
+    /// `Range#each` with a block. The end value is included or excluded as
+    /// per [`excl`](Range::excl).
+    ///
+    /// Errors if the beginning or end of the range is not an Integer, or is
+    /// out of range for an `i64`, if the range is endless, or if `func`
+    /// returns an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Range, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let range: Range = ruby.eval("1..5")?;
+    ///     let mut sum = 0;
+    ///     range.each_i64(|i| {
+    ///         sum += i;
+    ///         Ok(())
+    ///     })?;
+    ///     assert_eq!(sum, 15);
+    ///
+    ///     let range: Range = ruby.eval("1..")?;
+    ///     assert!(range.each_i64(|_| Ok(())).is_err());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn each_i64<F>(self, func: F) -> Result<(), Error>
+    where
+        F: FnMut(i64) -> Result<(), Error>,
+    {
+        self.step_i64(1, func)
+    }
+
+    /// Call `func` with every `step`th integer in the range, starting from the
+    /// beginning of the range.
+    ///
+    /// As [`each_i64`](Range::each_i64), but errors if `step` is not
+    /// positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Range, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let range: Range = ruby.eval("0...10")?;
+    ///     let mut res = Vec::new();
+    ///     range.step_i64(3, |i| {
+    ///         res.push(i);
+    ///         Ok(())
+    ///     })?;
+    ///     assert_eq!(res, vec![0, 3, 6, 9]);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn step_i64<F>(self, step: i64, mut func: F) -> Result<(), Error>
+    where
+        F: FnMut(i64) -> Result<(), Error>,
+    {
+        let handle = Ruby::get_with(self);
+        if step <= 0 {
+            return Err(Error::new(
+                handle.exception_arg_error(),
+                if step == 0 {
+                    "step can't be 0"
+                } else {
+                    "step can't be negative"
+                },
+            ));
+        }
+        let beg = int_bound(self.beg()?)?;
+        let end = int_bound(self.end_bound()?)?;
+        let mut i = beg;
+        while i < end || (i == end && !self.excl()) {
+            func(i)?;
+            i = match i.checked_add(step) {
+                Some(i) => i,
+                None => break,
+            };
+        }
+        Ok(())
+    }
+
+    /// Call `func` with each character in a range of single character
+    /// Strings, such as `"a".."z"`.
+    ///
+    /// Characters are iterated in order of their Unicode scalar value, which
+    /// for ASCII characters matches the behaviour of Ruby's `Range#each`.
+    ///
+    /// Errors if the beginning or end of the range is not a single character
+    /// String, if the range is endless, or if `func` returns an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Range, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let range: Range = ruby.eval(r#""a".."e""#)?;
+    ///     let mut res = String::new();
+    ///     range.each_char(|c| {
+    ///         res.push(c);
+    ///         Ok(())
+    ///     })?;
+    ///     assert_eq!(res, "abcde");
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn each_char<F>(self, mut func: F) -> Result<(), Error>
+    where
+        F: FnMut(char) -> Result<(), Error>,
+    {
+        let beg = char_bound(self.beg()?)?;
+        let end = char_bound(self.end_bound()?)?;
+        let chars = (beg as u32..=end as u32).filter_map(char::from_u32);
+        for c in chars {
+            if c == end && self.excl() {
+                break;
+            }
+            func(c)?;
+        }
+        Ok(())
+    }
+
+    fn end_bound(self) -> Result<Value, Error> {
+        let end: Value = self.end()?;
+        if end.is_nil() {
+            return Err(Error::new(
+                Ruby::get_with(self).exception_range_error(),
+                "cannot iterate endless range",
+            ));
+        }
+        Ok(end)
+    }
+}
+
+fn int_bound(val: Value) -> Result<i64, Error> {
+    Integer::from_value(val)
+        .ok_or_else(|| Error::type_error("Integer", val))?
+        .to_i64()
+}
+
+fn char_bound(val: Value) -> Result<char, Error> {
+    let s = RString::from_value(val).ok_or_else(|| Error::type_error("String", val))?;
+    let s = s.to_string()?;
+    let mut chars = s.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Ok(c),
+        _ => Err(Error::arg_error("single character String", val)),
+    }
 }
 
 impl fmt::Display for Range {
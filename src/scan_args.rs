@@ -30,6 +30,7 @@
 //! See also [`Ruby`](Ruby#argument-parsing).
 
 use std::{
+    borrow::Cow,
     ffi::CString,
     fmt,
     mem::transmute,
@@ -37,12 +38,12 @@ use std::{
     os::raw::c_int,
 };
 
-use rb_sys::{rb_error_arity, rb_get_kwargs, rb_scan_args, ID, VALUE};
+use rb_sys::{rb_error_arity, rb_get_kwargs, rb_keyword_given_p, rb_scan_args, ID, VALUE};
 use seq_macro::seq;
 
 use crate::{
     block::Proc,
-    error::{protect, Error},
+    error::{self, protect, Error},
     r_array::RArray,
     r_hash::RHash,
     try_convert::{TryConvert, TryConvertOwned},
@@ -649,6 +650,12 @@ pub struct KwArgs<Req, Opt, Splat> {
 /// determined by type parameters. The type `()` is used as a placeholder when
 /// a set of arguments is not required.
 ///
+/// `kw` is usually the `keywords` field of the [`Args`] returned by
+/// [`scan_args`], which only contains keywords if the method was called with
+/// keyword arguments. When taking `kw` as the last argument of a method with a
+/// fixed arity a `Hash` passed positionally will also be accepted, see
+/// [`Ruby::keyword_given`] to reject this.
+///
 /// # Panics
 ///
 /// This function will panic if `required` or `optional` arguments don't match
@@ -867,6 +874,87 @@ impl Ruby {
         }
         Ok(())
     }
+
+    /// Returns `Err` containing a Ruby `ArgumentError` with the message
+    /// returned by `msg` if `cond` is `false`.
+    ///
+    /// This is a shorthand for validating preconditions on arguments, built on
+    /// [`error::ensure`](crate::error::ensure). `msg` is only called if `cond`
+    /// is `false`. See [`check_arity`](Ruby::check_arity) to check the number
+    /// of arguments, and [`Error::arg_error`] for a standard message for an
+    /// invalid value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{function, Error, Ruby};
+    ///
+    /// fn percent(ruby: &Ruby, n: i64, total: i64) -> Result<f64, Error> {
+    ///     ruby.check_arg(total != 0, || "total must not be zero")?;
+    ///     ruby.check_arg(n <= total, || {
+    ///         format!("{} is greater than total {}", n, total)
+    ///     })?;
+    ///     Ok(n as f64 / total as f64 * 100.0)
+    /// }
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     ruby.define_global_function("percent", function!(percent, 2));
+    ///
+    ///     assert_eq!(ruby.eval::<f64>("percent(1, 4)")?, 25.0);
+    ///     let err = ruby.eval::<f64>("percent(1, 0)").unwrap_err();
+    ///     assert!(err.is_kind_of(ruby.exception_arg_error()));
+    ///     assert_eq!(err.to_string(), "total must not be zero");
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn check_arg<F, T>(&self, cond: bool, msg: F) -> Result<(), Error>
+    where
+        F: FnOnce() -> T,
+        T: Into<Cow<'static, str>>,
+    {
+        error::ensure(cond, || Error::new(self.exception_arg_error(), msg()))
+    }
+
+    /// Returns whether the current method was called with keyword arguments.
+    ///
+    /// When a method is called with keywords they are passed to the Rust
+    /// function as a trailing [`RHash`], which is indistinguishable from a
+    /// `Hash` passed as the last positional argument. [`scan_args`] uses this
+    /// to implement Ruby 3's separation of positional and keyword arguments,
+    /// but methods with a fixed arity that take keywords as an `RHash` can
+    /// use this to do the same.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{function, rb_assert, scan_args::get_kwargs, Error, RHash, Ruby};
+    ///
+    /// fn test(ruby: &Ruby, kw: RHash) -> Result<String, Error> {
+    ///     if !ruby.keyword_given() {
+    ///         return Err(Error::new(
+    ///             ruby.exception_arg_error(),
+    ///             "wrong number of arguments (given 1, expected 0)",
+    ///         ));
+    ///     }
+    ///     let args = get_kwargs::<_, (String,), (), ()>(kw, &["a"], &[])?;
+    ///     Ok(args.required.0)
+    /// }
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     ruby.define_global_function("test", function!(test, 1));
+    ///
+    ///     rb_assert!(ruby, r#"test(a: "foo") == "foo""#);
+    ///     rb_assert!(ruby, r#"(test({a: "foo"}) rescue $!).is_a?(ArgumentError)"#);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn keyword_given(&self) -> bool {
+        unsafe { rb_keyword_given_p() != 0 }
+    }
 }
 
 /// Returns `Err` containing a Ruby `ArgumentError` if `len` is not within
@@ -1,10 +1,13 @@
 //! Types and traits for working with Ruby symbols.
 
-use std::{borrow::Cow, fmt};
+use std::{borrow::Cow, collections::HashMap, fmt};
 
-use rb_sys::{rb_check_id, rb_intern_str, rb_sym2str, rb_to_symbol, ruby_value_type, VALUE};
+use rb_sys::{
+    rb_check_id, rb_intern_str, rb_sym2str, rb_sym_to_proc, rb_to_symbol, ruby_value_type, VALUE,
+};
 
 use crate::{
+    block::Proc,
     encoding::EncodingCapable,
     error::{protect, Error},
     into_value::IntoValue,
@@ -42,6 +45,39 @@ impl Ruby {
     pub fn to_symbol<T: AsRef<str>>(&self, name: T) -> Symbol {
         name.as_ref().into_symbol_with(self)
     }
+
+    /// Create a new [`SymbolTable`], interning each of `names`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{rb_assert, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let table = ruby.symbol_table(["id", "name", "email"]);
+    ///     let sym = table.get("name").unwrap();
+    ///     rb_assert!(ruby, ":name == sym", sym);
+    ///     assert!(table.get("address").is_none());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn symbol_table<I, T>(&self, names: I) -> SymbolTable
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        SymbolTable(
+            names
+                .into_iter()
+                .map(|name| {
+                    let name = name.as_ref();
+                    (name.to_owned(), self.sym_new(name))
+                })
+                .collect(),
+        )
+    }
 }
 
 /// A type wrapping either a [`StaticSymbol`] or a Value pointer to a RSymbol
@@ -220,6 +256,58 @@ impl Symbol {
             Id::from_rb_id(rb_intern_str(name)).into()
         }
     }
+
+    /// Return the [`Id`] for `self`.
+    ///
+    /// `Id`s are interned integers, so comparing them is much cheaper than
+    /// comparing Symbol names. See also [`match_id!`](crate::match_id).
+    ///
+    /// As with [`Symbol::to_static`], if `self` is not static it will be made
+    /// so, and will never be garbage collected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby, Symbol};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let sym: Symbol = ruby.eval(":example")?;
+    ///     assert_eq!(sym.to_id(), ruby.intern("example"));
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    #[inline]
+    pub fn to_id(self) -> Id {
+        Id::from(self)
+    }
+
+    /// Convert `self` to a [`Proc`] that calls the method named by `self` on
+    /// its first argument, passing along any further arguments.
+    ///
+    /// This is equivalent to Ruby's `Symbol#to_proc`, as used by the
+    /// `&:method` idiom.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{rb_assert, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let proc = ruby.to_symbol("upcase").to_proc();
+    ///     let res: String = proc.call(("example",))?;
+    ///     assert_eq!(res, "EXAMPLE");
+    ///
+    ///     rb_assert!(ruby, r#"["a", "b"].map(&proc) == ["A", "B"]"#, proc);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn to_proc(self) -> Proc {
+        unsafe { Proc::from_rb_value_unchecked(rb_sym_to_proc(self.as_rb_value())) }
+    }
 }
 
 impl fmt::Display for Symbol {
@@ -396,3 +484,162 @@ impl TryConvert for Symbol {
         })
     }
 }
+
+/// A set of names interned as Ruby [`StaticSymbol`]s up front.
+///
+/// Looking up a name in a `SymbolTable` is a Rust [`HashMap`] lookup, rather
+/// than a call into Ruby to intern the name, which is useful when the same
+/// small set of names are converted to Symbols repeatedly, e.g. the column
+/// names when loading rows of data.
+///
+/// [`StaticSymbol`]s are never garbage collected, so it is safe to keep a
+/// `SymbolTable` for as long as required.
+///
+/// See [`Ruby::symbol_table`] to create a `SymbolTable`.
+#[derive(Clone, Debug, Default)]
+pub struct SymbolTable(HashMap<String, StaticSymbol>);
+
+impl SymbolTable {
+    /// Return the Symbol for `name`, or `None` if `name` was not included
+    /// when the table was created.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let table = ruby.symbol_table(["id", "name"]);
+    ///     assert_eq!(table.get("id"), Some(ruby.sym_new("id")));
+    ///     assert_eq!(table.get("email"), None);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    #[inline]
+    pub fn get(&self, name: &str) -> Option<StaticSymbol> {
+        self.0.get(name).copied()
+    }
+
+    /// Return the number of names in the table.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Return whether the table is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// Branch on an [`Id`] by comparing it to pre-interned names.
+///
+/// Each name is interned once, the first time the arm is reached, with a
+/// [`LazyId`], so after that each arm is a cheap integer comparison rather
+/// than a comparison of Symbol names.
+///
+/// The first argument can be an [`Id`], [`Symbol`], or [`StaticSymbol`]. The
+/// arms are string literals, and a final `_` arm is required.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{match_id, Error, Ruby, Symbol};
+///
+/// fn mode(sym: Symbol) -> u8 {
+///     match_id!(sym, {
+///         "read" => 1,
+///         "write" => 2,
+///         "append" => 3,
+///         _ => 0,
+///     })
+/// }
+///
+/// fn example(ruby: &Ruby) -> Result<(), Error> {
+///     assert_eq!(mode(ruby.eval(":write")?), 2);
+///     assert_eq!(mode(ruby.eval(":other")?), 0);
+///
+///     Ok(())
+/// }
+/// # Ruby::init(example).unwrap()
+/// ```
+#[macro_export]
+macro_rules! match_id {
+    ($id:expr, { $($name:literal => $arm:expr,)+ _ => $default:expr $(,)? }) => {{
+        let id = $crate::value::Id::from($id);
+        $(if {
+            static ID: $crate::value::LazyId = $crate::value::LazyId::new($name);
+            id == ID
+        } {
+            $arm
+        } else)+ {
+            $default
+        }
+    }};
+}
+
+/// Define a module of functions returning pre-interned [`StaticSymbol`]s.
+///
+/// Each function interns its name once, the first time it is called, with a
+/// [`LazyId`], and then returns the same [`StaticSymbol`] on every call. This
+/// allows a fixed vocabulary of Symbols, such as hash keys or option names,
+/// to be declared in one place and used cheaply throughout an extension.
+///
+/// By default the Symbol's name is the function name. A different name can be
+/// given with `= "..."`, for names that are not valid Rust identifiers.
+///
+/// # Panics
+///
+/// The generated functions panic if called from a non-Ruby thread.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{rb_assert, symbols, Error, Ruby};
+///
+/// symbols!(mod sym {
+///     name,
+///     value,
+///     empty = "empty?",
+/// });
+///
+/// fn example(ruby: &Ruby) -> Result<(), Error> {
+///     let hash = ruby.hash_new();
+///     hash.aset(sym::name(), "example")?;
+///     hash.aset(sym::value(), 42)?;
+///     rb_assert!(ruby, r#"hash == {name: "example", value: 42}"#, hash);
+///
+///     assert_eq!(sym::name(), ruby.sym_new("name"));
+///     assert_eq!(sym::empty(), ruby.sym_new("empty?"));
+///
+///     Ok(())
+/// }
+/// # Ruby::init(example).unwrap()
+/// ```
+#[macro_export]
+macro_rules! symbols {
+    (@name $name:ident) => {
+        stringify!($name)
+    };
+    (@name $name:ident $sym:literal) => {
+        $sym
+    };
+    ($vis:vis mod $module:ident { $($name:ident $(= $sym:literal)?),* $(,)? }) => {
+        $vis mod $module {
+            $(
+                #[allow(missing_docs)]
+                pub fn $name() -> $crate::value::StaticSymbol {
+                    static ID: $crate::value::LazyId =
+                        $crate::value::LazyId::new($crate::symbols!(@name $name $($sym)?));
+                    let ruby = $crate::Ruby::get().unwrap();
+                    $crate::value::StaticSymbol::from($crate::value::LazyId::get_inner_with(
+                        &ID, &ruby,
+                    ))
+                }
+            )*
+        }
+    };
+}
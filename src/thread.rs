@@ -79,6 +79,16 @@ impl Ruby {
 
     /// Create a Ruby thread.
     ///
+    /// The new thread runs `func`, and the value it returns becomes the
+    /// thread's value, available with `Thread#value`. If `func` returns
+    /// `Err` the error is raised in the thread, and re-raised by
+    /// `Thread#join` and `Thread#value`.
+    ///
+    /// `func` is run as a regular Ruby thread, holding the Global VM Lock
+    /// (GVL), so will not run in parallel with other Ruby threads, but Ruby
+    /// will switch between threads as usual, e.g. during blocking IO. `func`
+    /// must be `Send` as it is moved to, and run on, the new thread.
+    ///
     /// See also [`thread_create`](Ruby::thread_create), which is more
     /// efficient when `func` is a function or closure that does not
     /// capture any variables.
@@ -86,13 +96,22 @@ impl Ruby {
     /// # Examples
     ///
     /// ```
-    /// use magnus::{rb_assert, Error, Ruby};
+    /// use magnus::{prelude::*, rb_assert, Error, Ruby};
     ///
     /// fn example(ruby: &Ruby) -> Result<(), Error> {
     ///     let i = 1;
     ///     let t = ruby.thread_create_from_fn(move |_ruby| i + 2);
     ///     rb_assert!("t.value == 3", t);
     ///
+    ///     let t = ruby.thread_create_from_fn(|ruby| {
+    ///         Err::<(), _>(Error::new(ruby.exception_runtime_error(), "bang"))
+    ///     });
+    ///     t.funcall::<_, _, magnus::Value>("report_on_exception=", (false,))?;
+    ///     rb_assert!(
+    ///         r#"(begin; t.value; rescue RuntimeError => e; e.message; end) == "bang""#,
+    ///         t
+    ///     );
+    ///
     ///     Ok(())
     /// }
     /// # Ruby::init(example).unwrap()
@@ -335,6 +354,47 @@ impl Ruby {
         Ok(())
     }
 
+    /// Blocks for `seconds` seconds, like Ruby's `Kernel#sleep`.
+    ///
+    /// Unlike [`std::thread::sleep`], this releases the GVL, allowing other
+    /// Ruby threads to run, and can be woken by `Thread#wakeup` or `Thread#run`.
+    ///
+    /// Returns an error if `seconds` is negative or `NaN`, or if sleep is
+    /// interrupted by a signal or an exception raised in this thread.
+    ///
+    /// See also [`Ruby::thread_sleep`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Instant;
+    ///
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let now = Instant::now();
+    ///     ruby.sleep(0.1)?;
+    ///     let elapsed = now.elapsed();
+    ///     assert!(elapsed.as_millis() > 90);
+    ///     assert!(elapsed.as_secs() < 1);
+    ///
+    ///     assert!(ruby.sleep(-1.0).is_err());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    pub fn sleep(&self, seconds: f64) -> Result<(), Error> {
+        // `seconds >= 0.0` is false for NaN
+        if !(seconds >= 0.0 && seconds < u64::MAX as f64) {
+            return Err(Error::new(
+                self.exception_arg_error(),
+                format!("invalid sleep duration {}", seconds),
+            ));
+        }
+        self.thread_sleep(Duration::from_secs_f64(seconds))
+    }
+
     /// Blocks indefinitely.
     ///
     /// Returns an error if sleep is intrrupted by a signal.
@@ -386,6 +446,32 @@ impl Ruby {
     /// If any interrupt raises an error it will be returned as `Err`.
     ///
     /// Calling this function may execute code on another thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{Error, Ruby};
+    ///
+    /// fn sum_of_squares(ruby: &Ruby, n: u64) -> Result<u64, Error> {
+    ///     let mut sum: u64 = 0;
+    ///     for i in 0..n {
+    ///         // check every so often, rather than every iteration, to keep
+    ///         // the overhead low
+    ///         if i % 10_000 == 0 {
+    ///             ruby.thread_check_ints()?;
+    ///         }
+    ///         sum = sum.wrapping_add(i * i);
+    ///     }
+    ///     Ok(sum)
+    /// }
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     assert_eq!(sum_of_squares(ruby, 100_000)?, 333328333350000);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
     pub fn thread_check_ints(&self) -> Result<(), Error> {
         protect(|| {
             unsafe { rb_thread_check_ints() };
@@ -1,13 +1,18 @@
+#[cfg(feature = "chrono")]
+use std::os::raw::c_int;
 use std::{
     fmt,
     time::{Duration, SystemTime},
 };
 
 use rb_sys::{rb_time_new, rb_time_timeval, rb_time_utc_offset, timeval, VALUE};
+#[cfg(feature = "chrono")]
+use rb_sys::{rb_time_timespec, rb_time_timespec_new, timespec};
 
 use crate::{
     api::Ruby,
     error::{protect, Error},
+    integer::Integer,
     into_value::IntoValue,
     object::Object,
     r_typed_data::RTypedData,
@@ -147,6 +152,110 @@ impl IntoValue for SystemTime {
     }
 }
 
+/// Converts to a `Float` of seconds.
+///
+/// Ruby APIs such as `sleep` and `Timeout.timeout` take a number of seconds,
+/// commonly a `Float`, so a `Float` is used rather than an exact `Rational`.
+/// A `Float` can not exactly represent every number of nanoseconds, durations
+/// longer than around 52 days will lose nanosecond precision.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use magnus::{rb_assert, Error, Ruby};
+///
+/// fn example(ruby: &Ruby) -> Result<(), Error> {
+///     let duration = Duration::from_millis(1500);
+///     rb_assert!(ruby, "duration == 1.5", duration);
+///
+///     Ok(())
+/// }
+/// # Ruby::init(example).unwrap()
+/// ```
+impl IntoValue for Duration {
+    #[inline]
+    fn into_value_with(self, ruby: &Ruby) -> Value {
+        self.as_secs_f64().into_value_with(ruby)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl Time {
+    // Ruby's special `offset` value for `rb_time_timespec_new` meaning UTC.
+    const OFFSET_UTC: c_int = c_int::MAX - 1;
+
+    fn from_timespec(secs: i64, nanos: u32, offset: c_int) -> Result<Self, Error> {
+        // chrono represents leap seconds with nanos > 999,999,999, Ruby
+        // requires nanos to be less than one second.
+        let ts = timespec {
+            tv_sec: (secs + i64::from(nanos / 1_000_000_000)) as _,
+            tv_nsec: (nanos % 1_000_000_000) as _,
+        };
+        protect(|| unsafe { Self::from_rb_value_unchecked(rb_time_timespec_new(&ts, offset)) })
+    }
+
+    fn to_chrono(self) -> Result<chrono::DateTime<chrono::Utc>, Error> {
+        let mut ts = timespec {
+            tv_sec: 0,
+            tv_nsec: 0,
+        };
+        protect(|| unsafe {
+            ts = rb_time_timespec(self.as_rb_value());
+            Ruby::get_unchecked().qnil()
+        })?;
+        chrono::DateTime::from_timestamp(ts.tv_sec.into(), ts.tv_nsec as u32).ok_or_else(|| {
+            Error::new(
+                Ruby::get_with(self).exception_range_error(),
+                "time out of range",
+            )
+        })
+    }
+}
+
+/// Converts to a `Time` in UTC.
+#[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+#[cfg(feature = "chrono")]
+impl IntoValue for chrono::DateTime<chrono::Utc> {
+    #[inline]
+    fn into_value_with(self, _: &Ruby) -> Value {
+        Time::from_timespec(
+            self.timestamp(),
+            self.timestamp_subsec_nanos(),
+            Time::OFFSET_UTC,
+        )
+        .unwrap()
+        .as_value()
+    }
+}
+
+/// Converts to a `Time` with the same UTC offset.
+#[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+#[cfg(feature = "chrono")]
+impl IntoValue for chrono::DateTime<chrono::FixedOffset> {
+    #[inline]
+    fn into_value_with(self, _: &Ruby) -> Value {
+        Time::from_timespec(
+            self.timestamp(),
+            self.timestamp_subsec_nanos(),
+            self.offset().local_minus_utc(),
+        )
+        .unwrap()
+        .as_value()
+    }
+}
+
+/// Converts to a `Time` in UTC, treating `self` as a UTC date and time.
+#[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+#[cfg(feature = "chrono")]
+impl IntoValue for chrono::NaiveDateTime {
+    #[inline]
+    fn into_value_with(self, ruby: &Ruby) -> Value {
+        self.and_utc().into_value_with(ruby)
+    }
+}
+
 impl Object for Time {}
 
 unsafe impl private::ReprValue for Time {}
@@ -188,3 +297,104 @@ impl TryConvert for SystemTime {
         }
     }
 }
+
+/// Converts from a Ruby `Numeric` number of seconds.
+///
+/// `Integer`s are converted exactly, other numeric values are converted via
+/// a `Float`, so have the same precision caveats as the [`IntoValue`]
+/// implementation.
+///
+/// Errors with an `ArgumentError` if the number is negative or `NaN`, and a
+/// `RangeError` if it is too large (or infinite) to be a [`Duration`].
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use magnus::{Error, Ruby};
+///
+/// fn example(ruby: &Ruby) -> Result<(), Error> {
+///     let duration: Duration = ruby.eval("2")?;
+///     assert_eq!(duration, Duration::from_secs(2));
+///
+///     let duration: Duration = ruby.eval("0.25")?;
+///     assert_eq!(duration, Duration::from_millis(250));
+///
+///     let duration: Duration = ruby.eval("1/2r")?;
+///     assert_eq!(duration, Duration::from_millis(500));
+///
+///     assert!(ruby.eval::<Duration>("-1").is_err());
+///
+///     Ok(())
+/// }
+/// # Ruby::init(example).unwrap()
+/// ```
+impl TryConvert for Duration {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        let ruby = Ruby::get_with(val);
+        let negative = || {
+            Error::new(
+                ruby.exception_arg_error(),
+                "time interval must not be negative",
+            )
+        };
+        let too_big = || Error::new(ruby.exception_range_error(), "time interval too big");
+        if let Some(i) = Integer::from_value(val) {
+            if i.is_negative() {
+                return Err(negative());
+            }
+            return i.to_u64().map(Duration::from_secs).map_err(|_| too_big());
+        }
+        let secs = f64::try_convert(val)?;
+        if secs.is_nan() {
+            Err(Error::new(
+                ruby.exception_arg_error(),
+                "time interval must be a number",
+            ))
+        } else if secs < 0.0 {
+            Err(negative())
+        } else if secs >= u64::MAX as f64 {
+            Err(too_big())
+        } else {
+            Ok(Duration::from_secs_f64(secs))
+        }
+    }
+}
+
+/// Converts from a `Time`, preserving nanoseconds.
+#[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+#[cfg(feature = "chrono")]
+impl TryConvert for chrono::DateTime<chrono::Utc> {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        Time::try_convert(val)?.to_chrono()
+    }
+}
+
+/// Converts from a `Time`, preserving nanoseconds and the UTC offset.
+#[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+#[cfg(feature = "chrono")]
+impl TryConvert for chrono::DateTime<chrono::FixedOffset> {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        let time = Time::try_convert(val)?;
+        let offset = i32::try_from(time.utc_offset())
+            .ok()
+            .and_then(chrono::FixedOffset::east_opt)
+            .ok_or_else(|| {
+                Error::new(
+                    Ruby::get_with(val).exception_range_error(),
+                    "utc offset out of range",
+                )
+            })?;
+        Ok(time.to_chrono()?.with_timezone(&offset))
+    }
+}
+
+/// Converts from a `Time`, returning the date and time in UTC.
+#[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+#[cfg(feature = "chrono")]
+impl TryConvert for chrono::NaiveDateTime {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        Ok(Time::try_convert(val)?.to_chrono()?.naive_utc())
+    }
+}
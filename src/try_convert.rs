@@ -1,6 +1,6 @@
 //! Traits for converting from Ruby [`Value`]s to Rust types.
 
-use std::path::PathBuf;
+use std::{cell::Cell, fmt, path::PathBuf};
 
 use rb_sys::{rb_get_path, rb_num2dbl};
 use seq_macro::seq;
@@ -13,11 +13,43 @@ use crate::{
     r_array::RArray,
     r_hash::RHash,
     r_string::RString,
-    value::{Fixnum, ReprValue, Value},
+    value::{private::ReprValue as _, Fixnum, ReprValue, Value},
     Ruby,
 };
 
 /// Conversions from [`Value`] to Rust types.
+///
+/// # `Option`
+///
+/// `Option<T>` converts Ruby `nil` to `None`, and any other value to
+/// `Some(T)`. If a non-`nil` value fails to convert to `T` the error is
+/// returned, rather than being treated as `None`. This applies to every `T`,
+/// so for a nested `Option<Option<T>>` Ruby `nil` is always the outer `None`,
+/// and `Some(None)` is never produced.
+///
+/// The reverse conversion, [`IntoValue`](crate::IntoValue) for `Option<T>`,
+/// converts `None` to `nil`.
+///
+/// ```
+/// use magnus::{prelude::*, Error, Ruby, TryConvert};
+///
+/// fn example(ruby: &Ruby) -> Result<(), Error> {
+///     assert_eq!(<Option<i64>>::try_convert(ruby.qnil().as_value())?, None);
+///     assert_eq!(
+///         <Option<i64>>::try_convert(ruby.integer_from_i64(1).as_value())?,
+///         Some(1)
+///     );
+///     assert!(<Option<i64>>::try_convert(ruby.str_new("1").as_value()).is_err());
+///
+///     assert_eq!(
+///         <Option<Option<i64>>>::try_convert(ruby.qnil().as_value())?,
+///         None
+///     );
+///
+///     Ok(())
+/// }
+/// # Ruby::init(example).unwrap()
+/// ```
 pub trait TryConvert: Sized {
     /// Convert `val` into `Self`.
     fn try_convert(val: Value) -> Result<Self, Error>;
@@ -34,6 +66,159 @@ pub trait TryConvert: Sized {
 /// This trait must not be implemented for types that contain `Value`.
 pub unsafe trait TryConvertOwned: TryConvert {}
 
+/// The default maximum depth of nested collections that will be converted.
+///
+/// See [`with_max_depth`].
+pub const DEFAULT_MAX_DEPTH: usize = 128;
+
+thread_local! {
+    static WITH_PATH: Cell<bool> = const { Cell::new(false) };
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+    static MAX_DEPTH: Cell<usize> = const { Cell::new(DEFAULT_MAX_DEPTH) };
+}
+
+/// Convert `val` into `T`, annotating errors with the path to the element
+/// that failed to convert.
+///
+/// When converting nested values, such as a `Vec<HashMap<String, i64>>`, an
+/// error converting a deeply nested element doesn't usually say where that
+/// element is. With this function the conversions for Rust collections
+/// ([`Vec`], arrays, tuples, [`HashMap`](std::collections::HashMap), etc.)
+/// prefix errors with their Array index or Hash key, such as
+/// `at [2]["foo"]: no implicit conversion of String into Integer`.
+///
+/// This only changes the message, the class of the error is preserved.
+/// Errors raised in Ruby are replaced by a new error with the annotated
+/// message, so any other details of the original exception are lost.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use magnus::{try_convert, Error, Ruby, Value};
+///
+/// fn example(ruby: &Ruby) -> Result<(), Error> {
+///     let val: Value = ruby.eval(r#"[{"a" => 1}, {"b" => 2}, {"c" => "three"}]"#)?;
+///
+///     let err = try_convert::with_path::<Vec<HashMap<String, i64>>>(val).unwrap_err();
+///     assert!(err.is_kind_of(ruby.exception_type_error()));
+///     assert_eq!(
+///         err.to_string(),
+///         r#"TypeError: at [2]["c"]: no implicit conversion of String into Integer"#
+///     );
+///
+///     Ok(())
+/// }
+/// # Ruby::init(example).unwrap()
+/// ```
+pub fn with_path<T>(val: Value) -> Result<T, Error>
+where
+    T: TryConvert,
+{
+    // restores the previous setting even if `try_convert` panics
+    let _guard = WithPathGuard(WITH_PATH.with(|c| c.replace(true)));
+    T::try_convert(val)
+}
+
+struct WithPathGuard(bool);
+
+impl Drop for WithPathGuard {
+    fn drop(&mut self) {
+        WITH_PATH.with(|c| c.set(self.0));
+    }
+}
+
+/// Convert `val` into `T`, with a maximum depth of `max` nested collections.
+///
+/// The conversions for Rust collections ([`Vec`], arrays, tuples,
+/// [`HashMap`](std::collections::HashMap), etc.) and other recursive
+/// operations such as [`ReprValue::deep_freeze`] track how deeply they are
+/// nested, and return an `ArgumentError` ("nesting of N is too deep") rather
+/// than overflowing the stack when the limit is exceeded. This is only a
+/// concern for types that can nest to an arbitrary depth, such as a recursive
+/// enum with a [`TryConvert`] implementation that converts each element of an
+/// Array.
+///
+/// Outside of this function the limit is [`DEFAULT_MAX_DEPTH`].
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{try_convert, Error, Ruby, Value};
+///
+/// fn example(ruby: &Ruby) -> Result<(), Error> {
+///     let val: Value = ruby.eval("[[[1]]]")?;
+///
+///     let res = try_convert::with_max_depth::<Vec<Vec<Vec<i64>>>>(val, 3)?;
+///     assert_eq!(res, vec![vec![vec![1]]]);
+///
+///     let err = try_convert::with_max_depth::<Vec<Vec<Vec<i64>>>>(val, 2).unwrap_err();
+///     assert!(err.is_kind_of(ruby.exception_arg_error()));
+///     assert_eq!(err.to_string(), "ArgumentError: nesting of 3 is too deep");
+///
+///     Ok(())
+/// }
+/// # Ruby::init(example).unwrap()
+/// ```
+pub fn with_max_depth<T>(val: Value, max: usize) -> Result<T, Error>
+where
+    T: TryConvert,
+{
+    // restore the previous limit and depth even if `try_convert` panics
+    let _max_guard = MaxDepthGuard(MAX_DEPTH.with(|c| c.replace(max)));
+    let _depth_guard = DepthGuard(DEPTH.with(|c| c.replace(0)));
+    T::try_convert(val)
+}
+
+struct MaxDepthGuard(usize);
+
+impl Drop for MaxDepthGuard {
+    fn drop(&mut self) {
+        MAX_DEPTH.with(|c| c.set(self.0));
+    }
+}
+
+struct DepthGuard(usize);
+
+impl Drop for DepthGuard {
+    fn drop(&mut self) {
+        DEPTH.with(|c| c.set(self.0));
+    }
+}
+
+/// Call `func`, one level deeper in a nested conversion of `val`, erroring
+/// if that exceeds the limit set by [`with_max_depth`].
+pub(crate) fn nested<T, F>(val: Value, func: F) -> Result<T, Error>
+where
+    F: FnOnce() -> Result<T, Error>,
+{
+    let depth = DEPTH.with(Cell::get);
+    if depth >= MAX_DEPTH.with(Cell::get) {
+        return Err(Error::new(
+            Ruby::get_with(val).exception_arg_error(),
+            format!("nesting of {} is too deep", depth + 1),
+        ));
+    }
+    DEPTH.with(|c| c.set(depth + 1));
+    // restores the depth even if `func` panics
+    let _guard = DepthGuard(depth);
+    func()
+}
+
+/// Add `segment` to the path of the failed conversion in `res`, if enabled
+/// by [`with_path`].
+pub(crate) fn annotate_path<T, F, S>(res: Result<T, Error>, segment: F) -> Result<T, Error>
+where
+    F: FnOnce() -> S,
+    S: fmt::Display,
+{
+    match res {
+        Err(e) if WITH_PATH.with(Cell::get) => Err(e.with_path_segment(segment())),
+        res => res,
+    }
+}
+
 impl<T> TryConvert for Option<T>
 where
     T: TryConvert,
@@ -46,10 +231,20 @@ where
 
 unsafe impl<T> TryConvertOwned for Option<T> where T: TryConvertOwned {}
 
+/// Strict conversion, only `true` and `false` are accepted.
+///
+/// Use [`ReprValue::to_bool`](crate::value::ReprValue::to_bool) for Ruby's
+/// rules of truthiness, or `Option<bool>` to also accept `nil` (as `None`).
 impl TryConvert for bool {
     #[inline]
     fn try_convert(val: Value) -> Result<Self, Error> {
-        Ok(val.to_bool())
+        if val.is_true() {
+            Ok(true)
+        } else if val.is_false() {
+            Ok(false)
+        } else {
+            Err(Error::type_error("boolean", val))
+        }
     }
 }
 unsafe impl TryConvertOwned for bool {}
@@ -94,6 +289,14 @@ impl TryConvert for isize {
 }
 unsafe impl TryConvertOwned for isize {}
 
+impl TryConvert for i128 {
+    #[inline]
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        Integer::try_convert(val)?.to_i128()
+    }
+}
+unsafe impl TryConvertOwned for i128 {}
+
 impl TryConvert for u8 {
     #[inline]
     fn try_convert(val: Value) -> Result<Self, Error> {
@@ -134,6 +337,14 @@ impl TryConvert for usize {
 }
 unsafe impl TryConvertOwned for usize {}
 
+impl TryConvert for u128 {
+    #[inline]
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        Integer::try_convert(val)?.to_u128()
+    }
+}
+unsafe impl TryConvertOwned for u128 {}
+
 impl TryConvert for f32 {
     #[inline]
     fn try_convert(val: Value) -> Result<Self, Error> {
@@ -199,7 +410,7 @@ where
     #[inline]
     fn try_convert(val: Value) -> Result<Self, Error> {
         debug_assert_value!(val);
-        RArray::try_convert(val)?.to_vec()
+        nested(val, || RArray::try_convert(val)?.to_vec())
     }
 }
 unsafe impl<T> TryConvertOwned for Vec<T> where T: TryConvertOwned {}
@@ -211,7 +422,7 @@ where
     #[inline]
     fn try_convert(val: Value) -> Result<Self, Error> {
         debug_assert_value!(val);
-        RArray::try_convert(val)?.to_array()
+        nested(val, || RArray::try_convert(val)?.to_array())
     }
 }
 unsafe impl<T, const N: usize> TryConvertOwned for [T; N] where T: TryConvert {}
@@ -225,17 +436,19 @@ macro_rules! impl_try_convert {
             {
                 fn try_convert(val: Value) -> Result<Self, Error> {
                     debug_assert_value!(val);
-                    let array = RArray::try_convert(val)?;
-                    let slice = unsafe { array.as_slice() };
-                    if slice.len() != $n {
-                        return Err(Error::new(
-                            Ruby::get_with(val).exception_type_error(),
-                            concat!("expected Array of length ", $n),
-                        ));
-                    }
-                    Ok((
-                        #(TryConvert::try_convert(slice[N])?,)*
-                    ))
+                    nested(val, || {
+                        let array = RArray::try_convert(val)?;
+                        let slice = unsafe { array.as_slice() };
+                        if slice.len() != $n {
+                            return Err(Error::new(
+                                Ruby::get_with(val).exception_type_error(),
+                                format!(concat!("expected Array of length ", $n, ", got {}"), slice.len()),
+                            ));
+                        }
+                        Ok((
+                            #(annotate_path(TryConvert::try_convert(slice[N]), || format!("[{}]", N))?,)*
+                        ))
+                    })
                 }
             }
             unsafe impl<#(T~N,)*> TryConvertOwned for (#(T~N,)*)
@@ -259,7 +472,7 @@ where
     #[inline]
     fn try_convert(val: Value) -> Result<Self, Error> {
         debug_assert_value!(val);
-        RHash::try_convert(val)?.to_hash_map()
+        nested(val, || RHash::try_convert(val)?.to_hash_map())
     }
 }
 unsafe impl<K, V> TryConvertOwned for std::collections::HashMap<K, V>
@@ -269,6 +482,26 @@ where
 {
 }
 
+#[cfg(feature = "indexmap")]
+impl<K, V> TryConvert for indexmap::IndexMap<K, V>
+where
+    K: TryConvertOwned + Eq + std::hash::Hash,
+    V: TryConvertOwned,
+{
+    #[inline]
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        debug_assert_value!(val);
+        nested(val, || RHash::try_convert(val)?.to_index_map())
+    }
+}
+#[cfg(feature = "indexmap")]
+unsafe impl<K, V> TryConvertOwned for indexmap::IndexMap<K, V>
+where
+    K: TryConvertOwned + Eq + std::hash::Hash,
+    V: TryConvertOwned,
+{
+}
+
 #[cfg(unix)]
 impl TryConvert for PathBuf {
     fn try_convert(val: Value) -> Result<Self, Error> {
@@ -4,6 +4,7 @@
 //! `rb_data_typed_object_wrap` function from Ruby's C API.
 
 use std::{
+    cell::RefCell,
     collections::hash_map::DefaultHasher,
     ffi::{c_void, CStr},
     fmt,
@@ -11,15 +12,16 @@ use std::{
     marker::PhantomData,
     mem::size_of_val,
     ops::Deref,
-    panic::catch_unwind,
+    panic::{catch_unwind, AssertUnwindSafe},
     ptr,
 };
 
 #[cfg(ruby_gte_3_0)]
 use rb_sys::rbimpl_typeddata_flags::{self, RUBY_TYPED_FREE_IMMEDIATELY, RUBY_TYPED_WB_PROTECTED};
 use rb_sys::{
-    self, rb_data_type_struct__bindgen_ty_1, rb_data_type_t, rb_obj_reveal,
-    rb_singleton_class_attached, rb_singleton_class_clone, size_t, VALUE,
+    self, rb_any_to_s, rb_data_type_struct__bindgen_ty_1, rb_data_type_t, rb_num_coerce_bin,
+    rb_obj_reveal, rb_singleton_class_attached, rb_singleton_class_clone, rb_typeddata_is_kind_of,
+    size_t, VALUE,
 };
 
 #[cfg(ruby_lt_3_0)]
@@ -30,10 +32,12 @@ const RUBY_TYPED_WB_PROTECTED: u32 = rb_sys::ruby_fl_type::RUBY_FL_WB_PROTECTED
 
 use crate::{
     class::RClass,
-    error::{bug_from_panic, Error},
+    error::{bug_from_panic, protect, Error},
     gc,
+    integer::Integer,
     into_value::IntoValue,
     object::Object,
+    r_string::RString,
     r_typed_data::RTypedData,
     scan_args::{get_kwargs, scan_args},
     try_convert::TryConvert,
@@ -94,6 +98,9 @@ where
     /// [`wrap`](macro@crate::wrap)/[`TypedData`](macro@crate::TypedData)
     /// macro or [`DataTypeBuilder::free_immediately`].
     ///
+    /// See [`ManagedResource`] for a pattern to perform clean up that needs
+    /// to call Ruby in an explicit `close` method instead.
+    ///
     /// This function **must not** panic. The process will abort if this
     /// function panics.
     fn free(self: Box<Self>) {}
@@ -118,12 +125,35 @@ where
     ///
     /// This function is only called when the `size` flag is set with the
     /// [`wrap`](macro@crate::wrap)/[`TypedData`](macro@crate::TypedData)
-    /// macro or [`DataTypeBuilder::mark`].
+    /// macro or [`DataTypeBuilder::size`].
     ///
     /// The default implementation delegates to [`std::mem::size_of_val`].
+    /// Types owning heap allocations should override this to include the
+    /// size of those allocations, so Ruby (and tools like
+    /// `ObjectSpace.memsize_of`) can accurately attribute memory use.
     ///
     /// This function **must not** panic. The process will abort if this
     /// function panics.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::mem::size_of_val;
+    ///
+    /// use magnus::{DataTypeFunctions, TypedData};
+    ///
+    /// #[derive(TypedData)]
+    /// #[magnus(class = "Buffer", size, free_immediately)]
+    /// struct Buffer {
+    ///     data: Vec<u8>,
+    /// }
+    ///
+    /// impl DataTypeFunctions for Buffer {
+    ///     fn size(&self) -> usize {
+    ///         size_of_val(self) + self.data.capacity()
+    ///     }
+    /// }
+    /// ```
     fn size(&self) -> usize {
         size_of_val(self)
     }
@@ -145,7 +175,7 @@ where
     ///
     /// This function is only called when the `compact` flag is set with the
     /// [`wrap`](macro@crate::wrap)/[`TypedData`](macro@crate::TypedData)
-    /// macro or [`DataTypeBuilder::mark`].
+    /// macro or [`DataTypeBuilder::compact`].
     ///
     /// The default implementation does nothing.
     ///
@@ -367,6 +397,12 @@ where
 /// A trait for Rust types that can be used with the
 /// `rb_data_typed_object_wrap` API.
 ///
+/// Types implementing `TypedData` are automatically wrapped in a Ruby object
+/// when converted to a Ruby value with [`IntoValue`], e.g. when returned from
+/// a method. To wrap a value and keep a typed handle to it in Rust, use
+/// [`Ruby::obj_wrap`], which returns an [`Obj<T>`](Obj) that derefs to `&T`
+/// and can be passed back to Ruby.
+///
 /// # Safety
 ///
 /// This trait is unsafe to implement as the fields of [`DataType`] returned by
@@ -414,6 +450,18 @@ where
     /// ```
     fn class(ruby: &Ruby) -> RClass;
 
+    /// Fallible version of [`TypedData::class`].
+    ///
+    /// The provided implementation simply returns the value of
+    /// [`TypedData::class`]. The [`TypedData`](`derive@crate::TypedData`) and
+    /// [`wrap`](`crate::wrap`) macros override this to return `Err` with a
+    /// `NameError` if the class has not yet been defined.
+    ///
+    /// See also [`Ruby::class_for_type`].
+    fn try_class(ruby: &Ruby) -> Result<RClass, Error> {
+        Ok(Self::class(ruby))
+    }
+
     /// Should return a static reference to a [`DataType`] with metadata about
     /// the wrapped type.
     ///
@@ -677,12 +725,92 @@ impl Ruby {
             phantom: PhantomData,
         }
     }
+
+    /// Get the Ruby class for the wrapped Rust type `T`.
+    ///
+    /// This returns the result of [`TypedData::try_class`], which for types
+    /// using the [`TypedData`](`derive@crate::TypedData`) or
+    /// [`wrap`](`crate::wrap`) macros returns `Err` with a `NameError`,
+    /// rather than panicking, if the class has not yet been defined.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, Error, Ruby};
+    ///
+    /// #[magnus::wrap(class = "Point")]
+    /// struct Point {
+    ///     x: isize,
+    ///     y: isize,
+    /// }
+    ///
+    /// #[magnus::wrap(class = "Undefined")]
+    /// struct Undefined;
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let point_class = ruby.define_class("Point", ruby.class_object())?;
+    ///     assert!(ruby.class_for_type::<Point>()?.equal(point_class)?);
+    ///
+    ///     assert!(ruby.class_for_type::<Undefined>().is_err());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap();
+    /// # let _ = Point { x: 1, y: 2 }.x + Point { x: 3, y: 4 }.y;
+    /// ```
+    pub fn class_for_type<T>(&self) -> Result<RClass, Error>
+    where
+        T: TypedData,
+    {
+        T::try_class(self)
+    }
 }
 
 impl<T> Obj<T>
 where
     T: TypedData,
 {
+    /// Return `Some(Obj<T>)` if `val` is a Ruby object wrapping a `T`, `None`
+    /// otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{typed_data, Error, Ruby};
+    ///
+    /// #[magnus::wrap(class = "Point")]
+    /// struct Point {
+    ///     x: isize,
+    ///     y: isize,
+    /// }
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     ruby.define_class("Point", ruby.class_object())?;
+    ///     let val = ruby.into_value(Point { x: 4, y: 2 });
+    ///
+    ///     assert!(typed_data::Obj::<Point>::from_value(val).is_some());
+    ///     assert!(typed_data::Obj::<Point>::from_value(ruby.eval("Object.new")?).is_none());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap();
+    /// # let _ = Point { x: 1, y: 2 }.x + Point { x: 3, y: 4 }.y;
+    /// ```
+    #[inline]
+    pub fn from_value(val: Value) -> Option<Self> {
+        RTypedData::from_value(val)
+            .filter(|_| unsafe {
+                rb_typeddata_is_kind_of(
+                    val.as_rb_value(),
+                    T::data_type().as_rb_data_type() as *const _,
+                ) != 0
+            })
+            .map(|inner| Self {
+                inner,
+                phantom: PhantomData,
+            })
+    }
+
     /// Wrap the Rust type `T` in a Ruby object.
     ///
     /// # Panics
@@ -840,7 +968,8 @@ where
     /// # Ruby::init(example).unwrap()
     /// ```
     fn deref(&self) -> &Self::Target {
-        self.inner.get().unwrap()
+        // `Obj` is only constructed for a `T`
+        unsafe { self.inner.get_unchecked() }
     }
 }
 
@@ -1272,6 +1401,17 @@ where
     }
 }
 
+/// Implementation of `#==` for `#[magnus(cmp)]`.
+#[doc(hidden)]
+pub fn derived_eq<T>(rb_self: &T, other: Value) -> bool
+where
+    T: TypedData + PartialEq,
+{
+    <&T>::try_convert(other)
+        .map(|o| rb_self == o)
+        .unwrap_or(false)
+}
+
 /// Trait for a Ruby-compatible `#inspect` method.
 ///
 /// Automatically implemented for any type implementing [`Debug`].
@@ -1356,6 +1496,54 @@ where
     }
 }
 
+/// The maximum length, in bytes, of strings returned by the `#inspect` and
+/// `#to_s` methods generated by `#[magnus(inspect)]` and `#[magnus(to_s)]`.
+///
+/// Longer strings are truncated and suffixed with `...`.
+pub const DERIVED_FMT_MAX_LEN: usize = 4096;
+
+/// Implementation of `#inspect` for `#[magnus(inspect)]`.
+#[doc(hidden)]
+pub fn derived_inspect<T>(rb_self: Obj<T>) -> RString
+where
+    T: TypedData + fmt::Debug,
+{
+    derived_fmt(rb_self, |v| format!("{:?}", v))
+}
+
+/// Implementation of `#to_s` for `#[magnus(to_s)]`.
+#[doc(hidden)]
+pub fn derived_to_s<T>(rb_self: Obj<T>) -> RString
+where
+    T: TypedData + fmt::Display,
+{
+    derived_fmt(rb_self, |v| v.to_string())
+}
+
+fn derived_fmt<T, F>(rb_self: Obj<T>, func: F) -> RString
+where
+    T: TypedData,
+    F: FnOnce(&T) -> String,
+{
+    let handle = Ruby::get_with(rb_self);
+    match catch_unwind(AssertUnwindSafe(|| func(&rb_self))) {
+        Ok(mut s) => {
+            if s.len() > DERIVED_FMT_MAX_LEN {
+                let mut end = DERIVED_FMT_MAX_LEN;
+                while !s.is_char_boundary(end) {
+                    end -= 1;
+                }
+                s.truncate(end);
+                s.push_str("...");
+            }
+            handle.str_new(&s)
+        }
+        // fall back to Ruby's default `#<Foo:0x...>` format if the Rust
+        // formatting impl panics
+        Err(_) => unsafe { RString::from_rb_value_unchecked(rb_any_to_s(rb_self.as_rb_value())) },
+    }
+}
+
 /// Trait for a Ruby-compatible `#dup` and `#clone` methods.
 ///
 /// Automatically implemented for any type implementing [`Clone`].
@@ -1437,10 +1625,790 @@ where
         unsafe { rb_obj_reveal(clone.as_rb_value(), class_clone) };
         unsafe { rb_singleton_class_attached(class_clone, clone.as_rb_value()) };
         match freeze {
-            Some(true) => clone.freeze(),
-            None if rbself.is_frozen() => clone.freeze(),
+            Some(true) => {
+                clone.freeze();
+            }
+            None if rbself.is_frozen() => {
+                clone.freeze();
+            }
             _ => (),
         }
         Ok(clone)
     }
 }
+
+/// Trait for a Ruby-compatible `#+` method.
+///
+/// Automatically implemented for any type implementing [`Clone`] and
+/// [`std::ops::Add`].
+///
+/// If the other operand is not the same type, Ruby's coercion protocol is
+/// followed, calling `#coerce` on the other operand and retrying the
+/// operation with the results.
+///
+/// See also [`Sub`], [`Mul`], [`Div`], [`Rem`], [`Neg`], and [`Index`].
+///
+/// # Examples
+///
+/// ```
+/// use std::ops;
+///
+/// use magnus::{function, method, prelude::*, rb_assert, typed_data, Error, Ruby};
+///
+/// #[derive(Clone)]
+/// #[magnus::wrap(class = "Vector")]
+/// struct Vector {
+///     x: f64,
+///     y: f64,
+/// }
+///
+/// impl Vector {
+///     fn new(x: f64, y: f64) -> Self {
+///         Self { x, y }
+///     }
+///
+///     fn to_a(&self) -> (f64, f64) {
+///         (self.x, self.y)
+///     }
+/// }
+///
+/// impl ops::Add for Vector {
+///     type Output = Self;
+///
+///     fn add(self, other: Self) -> Self {
+///         Self::new(self.x + other.x, self.y + other.y)
+///     }
+/// }
+///
+/// fn example(ruby: &Ruby) -> Result<(), Error> {
+///     let class = ruby.define_class("Vector", ruby.class_object())?;
+///     class.define_singleton_method("new", function!(Vector::new, 2))?;
+///     class.define_method("to_a", method!(Vector::to_a, 0))?;
+///     class.define_method("+", method!(<Vector as typed_data::Add>::add, 1))?;
+///
+///     let a = Vector::new(1.0, 2.0);
+///     let b = Vector::new(3.0, 4.0);
+///     rb_assert!(ruby, "(a + b).to_a == [4.0, 6.0]", a, b);
+///
+///     let a = Vector::new(1.0, 2.0);
+///     rb_assert!(ruby, "begin; a + 1; false; rescue TypeError; true; end", a);
+///
+///     Ok(())
+/// }
+/// # Ruby::init(example).unwrap()
+/// ```
+pub trait Add: Sized {
+    // Docs at trait level.
+    #![allow(missing_docs)]
+    fn add(rb_self: Obj<Self>, other: Value) -> Result<Value, Error>;
+}
+
+impl<T> Add for T
+where
+    T: TypedData + Clone + std::ops::Add,
+    <T as std::ops::Add>::Output: IntoValue,
+{
+    fn add(rb_self: Obj<Self>, other: Value) -> Result<Value, Error> {
+        binop(rb_self, other, "+", std::ops::Add::add)
+    }
+}
+
+/// Trait for a Ruby-compatible `#-` method.
+///
+/// Automatically implemented for any type implementing [`Clone`] and
+/// [`std::ops::Sub`].
+///
+/// See [`Add`] for details and an example.
+pub trait Sub: Sized {
+    // Docs at trait level.
+    #![allow(missing_docs)]
+    fn sub(rb_self: Obj<Self>, other: Value) -> Result<Value, Error>;
+}
+
+impl<T> Sub for T
+where
+    T: TypedData + Clone + std::ops::Sub,
+    <T as std::ops::Sub>::Output: IntoValue,
+{
+    fn sub(rb_self: Obj<Self>, other: Value) -> Result<Value, Error> {
+        binop(rb_self, other, "-", std::ops::Sub::sub)
+    }
+}
+
+/// Trait for a Ruby-compatible `#*` method.
+///
+/// Automatically implemented for any type implementing [`Clone`] and
+/// [`std::ops::Mul`].
+///
+/// See [`Add`] for details and an example.
+pub trait Mul: Sized {
+    // Docs at trait level.
+    #![allow(missing_docs)]
+    fn mul(rb_self: Obj<Self>, other: Value) -> Result<Value, Error>;
+}
+
+impl<T> Mul for T
+where
+    T: TypedData + Clone + std::ops::Mul,
+    <T as std::ops::Mul>::Output: IntoValue,
+{
+    fn mul(rb_self: Obj<Self>, other: Value) -> Result<Value, Error> {
+        binop(rb_self, other, "*", std::ops::Mul::mul)
+    }
+}
+
+/// Trait for a Ruby-compatible `#/` method.
+///
+/// Automatically implemented for any type implementing [`Clone`] and
+/// [`std::ops::Div`].
+///
+/// See [`Add`] for details and an example.
+pub trait Div: Sized {
+    // Docs at trait level.
+    #![allow(missing_docs)]
+    fn div(rb_self: Obj<Self>, other: Value) -> Result<Value, Error>;
+}
+
+impl<T> Div for T
+where
+    T: TypedData + Clone + std::ops::Div,
+    <T as std::ops::Div>::Output: IntoValue,
+{
+    fn div(rb_self: Obj<Self>, other: Value) -> Result<Value, Error> {
+        binop(rb_self, other, "/", std::ops::Div::div)
+    }
+}
+
+/// Trait for a Ruby-compatible `#%` method.
+///
+/// Automatically implemented for any type implementing [`Clone`] and
+/// [`std::ops::Rem`].
+///
+/// See [`Add`] for details and an example.
+pub trait Rem: Sized {
+    // Docs at trait level.
+    #![allow(missing_docs)]
+    fn rem(rb_self: Obj<Self>, other: Value) -> Result<Value, Error>;
+}
+
+impl<T> Rem for T
+where
+    T: TypedData + Clone + std::ops::Rem,
+    <T as std::ops::Rem>::Output: IntoValue,
+{
+    fn rem(rb_self: Obj<Self>, other: Value) -> Result<Value, Error> {
+        binop(rb_self, other, "%", std::ops::Rem::rem)
+    }
+}
+
+fn binop<T, F, O>(rb_self: Obj<T>, other: Value, op: &str, func: F) -> Result<Value, Error>
+where
+    T: TypedData + Clone,
+    F: FnOnce(T, T) -> O,
+    O: IntoValue,
+{
+    let handle = Ruby::get_with(rb_self);
+    if let Ok(other) = <&T>::try_convert(other) {
+        return Ok(handle.into_value(func((*rb_self).clone(), other.clone())));
+    }
+    let op = handle.intern(op);
+    protect(|| unsafe {
+        Value::new(rb_num_coerce_bin(
+            rb_self.as_rb_value(),
+            other.as_rb_value(),
+            op.as_rb_id(),
+        ))
+    })
+}
+
+/// Trait for a Ruby-compatible `#-@` (unary minus) method.
+///
+/// Automatically implemented for any type implementing [`Clone`] and
+/// [`std::ops::Neg`].
+///
+/// # Examples
+///
+/// ```
+/// use std::ops;
+///
+/// use magnus::{function, method, prelude::*, rb_assert, typed_data, Error, Ruby};
+///
+/// #[derive(Clone)]
+/// #[magnus::wrap(class = "Vector")]
+/// struct Vector {
+///     x: f64,
+///     y: f64,
+/// }
+///
+/// impl Vector {
+///     fn new(x: f64, y: f64) -> Self {
+///         Self { x, y }
+///     }
+///
+///     fn to_a(&self) -> (f64, f64) {
+///         (self.x, self.y)
+///     }
+/// }
+///
+/// impl ops::Neg for Vector {
+///     type Output = Self;
+///
+///     fn neg(self) -> Self {
+///         Self::new(-self.x, -self.y)
+///     }
+/// }
+///
+/// fn example(ruby: &Ruby) -> Result<(), Error> {
+///     let class = ruby.define_class("Vector", ruby.class_object())?;
+///     class.define_singleton_method("new", function!(Vector::new, 2))?;
+///     class.define_method("to_a", method!(Vector::to_a, 0))?;
+///     class.define_method("-@", method!(<Vector as typed_data::Neg>::neg, 0))?;
+///
+///     let a = Vector::new(1.0, -2.0);
+///     rb_assert!(ruby, "(-a).to_a == [-1.0, 2.0]", a);
+///
+///     Ok(())
+/// }
+/// # Ruby::init(example).unwrap()
+/// ```
+pub trait Neg {
+    // Docs at trait level.
+    #![allow(missing_docs)]
+    type Output;
+    fn neg(&self) -> Self::Output;
+}
+
+impl<T> Neg for T
+where
+    T: Clone + std::ops::Neg,
+{
+    type Output = <T as std::ops::Neg>::Output;
+
+    fn neg(&self) -> Self::Output {
+        -self.clone()
+    }
+}
+
+/// Trait for a Ruby-compatible `#[]` method.
+///
+/// Implement [`get`](Index::get) to return the element at an index, or `None`
+/// if there is no such element, and the provided [`index`](Index::index)
+/// method can be used as the `#[]` method, returning `nil` when `get` returns
+/// `None`, as Ruby's `Array#[]` and `Hash#[]` do.
+///
+/// For index types that can't represent negative numbers, such as `usize`,
+/// `index` also returns `nil` for a negative index. Use a signed type such as
+/// `isize` to handle negative indices, e.g. to count from the end.
+///
+/// There is no equivalent for `#[]=`, as wrapped data is only available by
+/// shared reference.
+///
+/// # Examples
+///
+/// ```
+/// use magnus::{function, method, prelude::*, rb_assert, typed_data, Error, Ruby};
+///
+/// #[magnus::wrap(class = "Vector")]
+/// struct Vector(Vec<f64>);
+///
+/// impl Vector {
+///     fn new(items: Vec<f64>) -> Self {
+///         Self(items)
+///     }
+/// }
+///
+/// impl typed_data::Index<usize> for Vector {
+///     type Output = f64;
+///
+///     fn get(&self, i: usize) -> Option<f64> {
+///         self.0.get(i).copied()
+///     }
+/// }
+///
+/// fn example(ruby: &Ruby) -> Result<(), Error> {
+///     let class = ruby.define_class("Vector", ruby.class_object())?;
+///     class.define_singleton_method("new", function!(Vector::new, 1))?;
+///     class.define_method(
+///         "[]",
+///         method!(<Vector as typed_data::Index<usize>>::index, 1),
+///     )?;
+///
+///     let a = Vector::new(vec![1.0, 2.0, 3.0]);
+///     rb_assert!(ruby, "a[1] == 2.0", a);
+///     rb_assert!(ruby, "a[3].nil?", a);
+///     rb_assert!(ruby, "a[-1].nil?", a);
+///
+///     Ok(())
+/// }
+/// # Ruby::init(example).unwrap()
+/// ```
+pub trait Index<Idx>
+where
+    Idx: TryConvert,
+{
+    /// The type of the elements of `Self`.
+    type Output;
+
+    /// Return the element at `idx`, or `None` if there is no such element.
+    fn get(&self, idx: Idx) -> Option<Self::Output>;
+
+    /// Return the element at `idx`, or `nil` if there is no such element.
+    ///
+    /// Errors if `idx` can't be converted to `Idx`, other than for a negative
+    /// Integer, which returns `nil`.
+    fn index(&self, idx: Value) -> Result<Option<Self::Output>, Error> {
+        match Idx::try_convert(idx) {
+            Ok(idx) => Ok(self.get(idx)),
+            Err(_) if Integer::from_value(idx).map_or(false, |i| i.is_negative()) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// Trait for a Ruby-compatible `#coerce` method.
+///
+/// Automatically implemented for any type implementing [`From<U>`](From)
+/// where `U` implements [`TryConvert`].
+///
+/// Ruby calls `#coerce` when a built in type such as `Integer` is the left
+/// operand of an operator and it does not know how to handle the right
+/// operand, e.g. `2 * vector` calls `vector.coerce(2)`. The returned
+/// `[coerced_other, self]` pair is then used to retry the operation. Without
+/// `#coerce` these operations raise `TypeError`.
+///
+/// If the other operand is already the same type it is returned unchanged,
+/// otherwise it is converted to a `U`, and then to `Self` with [`From`].
+///
+/// See also [`Add`], [`Sub`], [`Mul`], [`Div`], and [`Rem`].
+///
+/// # Examples
+///
+/// ```
+/// use std::ops;
+///
+/// use magnus::{function, method, prelude::*, rb_assert, typed_data, Error, Ruby};
+///
+/// #[derive(Clone)]
+/// #[magnus::wrap(class = "Vector")]
+/// struct Vector {
+///     x: f64,
+///     y: f64,
+/// }
+///
+/// impl Vector {
+///     fn new(x: f64, y: f64) -> Self {
+///         Self { x, y }
+///     }
+///
+///     fn to_a(&self) -> (f64, f64) {
+///         (self.x, self.y)
+///     }
+/// }
+///
+/// impl From<f64> for Vector {
+///     fn from(n: f64) -> Self {
+///         Self::new(n, n)
+///     }
+/// }
+///
+/// impl ops::Mul for Vector {
+///     type Output = Self;
+///
+///     fn mul(self, other: Self) -> Self {
+///         Self::new(self.x * other.x, self.y * other.y)
+///     }
+/// }
+///
+/// fn example(ruby: &Ruby) -> Result<(), Error> {
+///     let class = ruby.define_class("Vector", ruby.class_object())?;
+///     class.define_singleton_method("new", function!(Vector::new, 2))?;
+///     class.define_method("to_a", method!(Vector::to_a, 0))?;
+///     class.define_method("*", method!(<Vector as typed_data::Mul>::mul, 1))?;
+///     class.define_method(
+///         "coerce",
+///         method!(<Vector as typed_data::Coerce<f64>>::coerce, 1),
+///     )?;
+///
+///     let a = Vector::new(1.0, 2.0);
+///     rb_assert!(ruby, "(2 * a).to_a == [2.0, 4.0]", a);
+///
+///     let a = Vector::new(1.0, 2.0);
+///     rb_assert!(ruby, "(2.5 * a).to_a == [2.5, 5.0]", a);
+///
+///     Ok(())
+/// }
+/// # Ruby::init(example).unwrap()
+/// ```
+pub trait Coerce<U>: Sized {
+    // Docs at trait level.
+    #![allow(missing_docs)]
+    fn coerce(rb_self: Obj<Self>, other: Value) -> Result<(Obj<Self>, Obj<Self>), Error>;
+}
+
+impl<T, U> Coerce<U> for T
+where
+    T: TypedData + From<U>,
+    U: TryConvert,
+{
+    fn coerce(rb_self: Obj<Self>, other: Value) -> Result<(Obj<Self>, Obj<Self>), Error> {
+        if let Ok(other) = Obj::<T>::try_convert(other) {
+            return Ok((other, rb_self));
+        }
+        let other = T::from(U::try_convert(other)?);
+        Ok((Ruby::get_with(rb_self).obj_wrap(other), rb_self))
+    }
+}
+
+/// Trait for a Rust type that can be used as a Ruby IO-like object.
+///
+/// Implementors provide [`read_bytes`](Io::read_bytes),
+/// [`write_bytes`](Io::write_bytes), and [`is_eof`](Io::is_eof), and the
+/// provided functions implement the Ruby IO interface on top of them, so that
+/// the wrapped object can be passed to Ruby code expecting an IO, such as
+/// `IO.copy_stream` or `CSV`.
+///
+/// * [`read`](Io::read) follows the `read([length [, outbuf]])` signature of
+///   Ruby's `IO#read`. With no `length` all remaining data is returned as a
+///   UTF-8 string, with a `length` the data is returned as a binary string,
+///   or `nil` at EOF. When `outbuf` is given its contents are replaced and it
+///   is returned.
+/// * [`write`](Io::write) converts each argument to a string with `#to_s`
+///   and returns the total number of bytes written.
+/// * [`each_line`](Io::each_line) yields each line of the remaining data,
+///   split on an optional separator (`"\n"` by default, `nil` for the whole
+///   of the remaining data). Paragraph mode (an empty separator) is not
+///   supported.
+///
+/// The [`wrap`](macro@crate::wrap) and [`TypedData`](macro@crate::TypedData)
+/// macros can define these methods with the `io` attribute, otherwise they
+/// can be defined with [`method`](macro@crate::method) as in the example.
+///
+/// # Examples
+///
+/// ```
+/// use std::cell::RefCell;
+///
+/// use magnus::{function, method, prelude::*, rb_assert, typed_data, Error, Ruby};
+///
+/// #[derive(Default)]
+/// #[magnus::wrap(class = "Buffer")]
+/// struct Buffer {
+///     data: RefCell<Vec<u8>>,
+///     pos: RefCell<usize>,
+/// }
+///
+/// impl typed_data::Io for Buffer {
+///     fn read_bytes(&self, len: Option<usize>) -> Result<Vec<u8>, Error> {
+///         let data = self.data.borrow();
+///         let mut pos = self.pos.borrow_mut();
+///         let end = len.map_or(data.len(), |len| data.len().min(*pos + len));
+///         let bytes = data[*pos..end].to_vec();
+///         *pos = end;
+///         Ok(bytes)
+///     }
+///
+///     fn write_bytes(&self, buf: &[u8]) -> Result<usize, Error> {
+///         self.data.borrow_mut().extend_from_slice(buf);
+///         Ok(buf.len())
+///     }
+///
+///     fn is_eof(&self) -> Result<bool, Error> {
+///         Ok(*self.pos.borrow() >= self.data.borrow().len())
+///     }
+/// }
+///
+/// fn example(ruby: &Ruby) -> Result<(), Error> {
+///     let class = ruby.define_class("Buffer", ruby.class_object())?;
+///     class.define_singleton_method("new", function!(Buffer::default, 0))?;
+///     class.define_method("read", method!(<Buffer as typed_data::Io>::read, -1))?;
+///     class.define_method("write", method!(<Buffer as typed_data::Io>::write, -1))?;
+///     class.define_method(
+///         "each_line",
+///         method!(<Buffer as typed_data::Io>::each_line, -1),
+///     )?;
+///     class.define_method("eof?", method!(<Buffer as typed_data::Io>::is_eof, 0))?;
+///
+///     rb_assert!(
+///         ruby,
+///         r#"
+///         buf = Buffer.new
+///         buf.write("foo\n", "bar\n", 42) == 10 &&
+///           buf.read(3) == "foo" &&
+///           buf.each_line.to_a == ["\n", "bar\n", "42"] &&
+///           buf.eof? &&
+///           buf.read(3).nil?
+///         "#
+///     );
+///
+///     rb_assert!(
+///         ruby,
+///         r#"
+///         src = Buffer.new
+///         src.write("hello, world")
+///         dst = Buffer.new
+///         IO.copy_stream(src, dst)
+///         dst.read == "hello, world"
+///         "#
+///     );
+///
+///     Ok(())
+/// }
+/// # Ruby::init(example).unwrap()
+/// ```
+pub trait Io: TypedData {
+    /// Read up to `len` bytes, or all remaining bytes if `len` is `None`.
+    ///
+    /// Should return an empty `Vec` at EOF.
+    fn read_bytes(&self, len: Option<usize>) -> Result<Vec<u8>, Error>;
+
+    /// Write `buf`, returning the number of bytes written.
+    fn write_bytes(&self, buf: &[u8]) -> Result<usize, Error>;
+
+    /// Returns whether all data has been read.
+    fn is_eof(&self) -> Result<bool, Error>;
+
+    /// Implements Ruby's `IO#read`, see the [trait level docs](Io).
+    fn read(ruby: &Ruby, rb_self: &Self, args: &[Value]) -> Result<Option<RString>, Error> {
+        let args = scan_args::<(), (Option<Option<usize>>, Option<Option<RString>>), (), (), (), ()>(
+            args,
+        )?;
+        let (len, outbuf) = args.optional;
+        let (len, outbuf) = (len.flatten(), outbuf.flatten());
+        let bytes = rb_self.read_bytes(len)?;
+        let s = match len {
+            Some(len) if len > 0 && bytes.is_empty() => None,
+            Some(_) => Some(ruby.str_from_slice(&bytes)),
+            None => Some(ruby.enc_str_new(&bytes, ruby.utf8_encoding())),
+        };
+        match (s, outbuf) {
+            (Some(s), Some(buf)) => {
+                buf.replace(s)?;
+                Ok(Some(buf))
+            }
+            (None, Some(buf)) => {
+                buf.replace(ruby.str_new(""))?;
+                Ok(None)
+            }
+            (s, None) => Ok(s),
+        }
+    }
+
+    /// Implements Ruby's `IO#write`, see the [trait level docs](Io).
+    fn write(rb_self: &Self, args: &[Value]) -> Result<usize, Error> {
+        let mut written = 0;
+        for arg in args {
+            let s = arg.to_r_string()?;
+            // copy the bytes, as `write_bytes` may call Ruby, which could
+            // modify the string
+            let buf = unsafe { s.as_slice() }.to_vec();
+            written += rb_self.write_bytes(&buf)?;
+        }
+        Ok(written)
+    }
+
+    /// Implements Ruby's `IO#each_line`, see the [trait level docs](Io).
+    fn each_line(ruby: &Ruby, rb_self: Obj<Self>, args: &[Value]) -> Result<Value, Error> {
+        if !ruby.block_given() {
+            return Ok(rb_self.enumeratorize("each_line", args).as_value());
+        }
+        let args = scan_args::<(), (Option<Option<RString>>,), (), (), (), ()>(args)?;
+        let sep = match args.optional.0 {
+            None => Some(String::from("\n")),
+            Some(None) => None,
+            Some(Some(sep)) => Some(sep.to_string()?),
+        };
+        if sep.as_deref() == Some("") {
+            return Err(Error::new(
+                ruby.exception_not_imp_error(),
+                "paragraph mode is not supported",
+            ));
+        }
+        let s = ruby.enc_str_new(rb_self.read_bytes(None)?, ruby.utf8_encoding());
+        match sep {
+            Some(sep) => {
+                for line in s.lines_iter(Some(&sep), false) {
+                    ruby.yield_value::<_, Value>(line)?;
+                }
+            }
+            None if !s.is_empty() => {
+                ruby.yield_value::<_, Value>(s)?;
+            }
+            None => (),
+        }
+        Ok(rb_self.as_value())
+    }
+}
+
+/// A native resource owned by a wrapped Rust type, that can be explicitly
+/// closed from Ruby.
+///
+/// Ruby's garbage collector frees wrapped types in an awkward context, where
+/// calling Ruby is not allowed (and will crash the process if the
+/// `free_immediately` flag is set). `ManagedResource` helps separate cleanup
+/// into two parts:
+///
+/// * 'Ruby-level' cleanup, such as flushing buffered data, calling a
+///   callback, or raising an error on failure, should be done in a `close`
+///   method called from Ruby. This runs as a normal method, so is free to call
+///   Ruby. Call [`ManagedResource::close`] to take the resource, and then
+///   perform any cleanup.
+/// * If the resource was never closed it is dropped when the wrapper object is
+///   garbage collected. This must only release native resources (e.g. close a
+///   file descriptor), and the [`Drop`] implementation of `T` must never call
+///   Ruby.
+///
+/// Once closed, further attempts to use the resource return an `IOError`,
+/// matching Ruby's behaviour for a closed `IO`.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Write;
+///
+/// use magnus::{
+///     function, method, prelude::*, rb_assert, typed_data::ManagedResource, Error, Ruby,
+/// };
+///
+/// #[magnus::wrap(class = "Log", free_immediately)]
+/// struct Log(ManagedResource<Vec<u8>>);
+///
+/// impl Log {
+///     fn new() -> Self {
+///         Self(ManagedResource::new(Vec::new()))
+///     }
+///
+///     fn write(ruby: &Ruby, rb_self: &Self, s: String) -> Result<(), Error> {
+///         rb_self.0.with_mut(|buf| {
+///             buf.write_all(s.as_bytes())
+///                 .map_err(|e| Error::new(ruby.exception_io_error(), e.to_string()))
+///         })?
+///     }
+///
+///     fn close(ruby: &Ruby, rb_self: &Self) -> Result<(), Error> {
+///         if let Some(buf) = rb_self.0.close()? {
+///             // can safely call Ruby here, unlike in `Drop`/`free`
+///             ruby.warn(&format!("closed log with {} bytes", buf.len()))?;
+///         }
+///         Ok(())
+///     }
+///
+///     fn is_closed(&self) -> Result<bool, Error> {
+///         self.0.is_closed()
+///     }
+/// }
+///
+/// fn example(ruby: &Ruby) -> Result<(), Error> {
+///     let class = ruby.define_class("Log", ruby.class_object())?;
+///     class.define_singleton_method("new", function!(Log::new, 0))?;
+///     class.define_method("write", method!(Log::write, 1))?;
+///     class.define_method("close", method!(Log::close, 0))?;
+///     class.define_method("closed?", method!(Log::is_closed, 0))?;
+///
+///     rb_assert!(
+///         ruby,
+///         r#"
+///             log = Log.new
+///             log.write("hello")
+///             log.close
+///             log.closed? && (log.write("world") rescue $!).is_a?(IOError)
+///         "#
+///     );
+///
+///     Ok(())
+/// }
+/// # Ruby::init(example).unwrap()
+/// ```
+pub struct ManagedResource<T> {
+    inner: RefCell<Option<T>>,
+}
+
+impl<T> ManagedResource<T> {
+    /// Create a new `ManagedResource` owning `resource`.
+    pub fn new(resource: T) -> Self {
+        Self {
+            inner: RefCell::new(Some(resource)),
+        }
+    }
+
+    /// Returns whether the resource has been closed.
+    ///
+    /// Errors with a `RuntimeError` if the resource is currently being
+    /// mutated, e.g. if called re-entrantly from within
+    /// [`with_mut`](Self::with_mut).
+    pub fn is_closed(&self) -> Result<bool, Error> {
+        let handle = Ruby::get()?;
+        let inner = self.inner.try_borrow().map_err(|_| {
+            Error::new(
+                handle.exception_runtime_error(),
+                "resource already mutably borrowed",
+            )
+        })?;
+        Ok(inner.is_none())
+    }
+
+    /// Take the resource, leaving `self` closed.
+    ///
+    /// Returns `Ok(None)` if the resource was already closed, so calling this
+    /// more than once is safe.
+    ///
+    /// The caller can then perform any cleanup, calling Ruby if needed,
+    /// before dropping the returned resource.
+    ///
+    /// Errors with a `RuntimeError` if the resource is currently borrowed,
+    /// e.g. if called re-entrantly from within [`with`](Self::with) or
+    /// [`with_mut`](Self::with_mut).
+    pub fn close(&self) -> Result<Option<T>, Error> {
+        let handle = Ruby::get()?;
+        let mut inner = self.inner.try_borrow_mut().map_err(|_| {
+            Error::new(
+                handle.exception_runtime_error(),
+                "resource already borrowed",
+            )
+        })?;
+        Ok(inner.take())
+    }
+
+    /// Call `func` with a reference to the resource.
+    ///
+    /// Errors with an `IOError` if the resource has been closed, or a
+    /// `RuntimeError` if the resource is currently being mutated, e.g. if
+    /// called re-entrantly from within [`with_mut`](Self::with_mut).
+    pub fn with<F, R>(&self, func: F) -> Result<R, Error>
+    where
+        F: FnOnce(&T) -> R,
+    {
+        let handle = Ruby::get()?;
+        let inner = self.inner.try_borrow().map_err(|_| {
+            Error::new(
+                handle.exception_runtime_error(),
+                "resource already mutably borrowed",
+            )
+        })?;
+        match inner.as_ref() {
+            Some(resource) => Ok(func(resource)),
+            None => Err(Error::new(handle.exception_io_error(), "closed resource")),
+        }
+    }
+
+    /// Call `func` with a mutable reference to the resource.
+    ///
+    /// Errors with an `IOError` if the resource has been closed, or a
+    /// `RuntimeError` if the resource is already borrowed, e.g. if called
+    /// re-entrantly from within [`with`](Self::with) or `with_mut`.
+    pub fn with_mut<F, R>(&self, func: F) -> Result<R, Error>
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        let handle = Ruby::get()?;
+        let mut inner = self.inner.try_borrow_mut().map_err(|_| {
+            Error::new(
+                handle.exception_runtime_error(),
+                "resource already borrowed",
+            )
+        })?;
+        match inner.as_mut() {
+            Some(resource) => Ok(func(resource)),
+            None => Err(Error::new(handle.exception_io_error(), "closed resource")),
+        }
+    }
+}
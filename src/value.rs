@@ -7,6 +7,8 @@ mod flonum;
 use std::{
     borrow::{Borrow, Cow},
     cell::UnsafeCell,
+    cmp::Ordering,
+    collections::HashSet,
     ffi::CStr,
     fmt,
     hash::{Hash, Hasher},
@@ -15,19 +17,21 @@ use std::{
     num::NonZeroUsize,
     ops::{Deref, DerefMut},
     os::raw::{c_char, c_int, c_long, c_ulong},
-    ptr,
+    ptr, slice,
     sync::Once,
 };
 
 #[cfg(ruby_use_flonum)]
 pub use flonum::Flonum;
 use rb_sys::{
-    rb_any_to_s, rb_block_call_kw, rb_check_funcall_kw, rb_check_id, rb_check_id_cstr,
-    rb_check_symbol_cstr, rb_enumeratorize_with_size_kw, rb_eql, rb_equal,
-    rb_funcall_with_block_kw, rb_funcallv_kw, rb_funcallv_public_kw, rb_gc_register_address,
-    rb_gc_unregister_address, rb_hash, rb_id2name, rb_id2sym, rb_inspect, rb_intern3, rb_ll2inum,
-    rb_obj_as_string, rb_obj_classname, rb_obj_freeze, rb_obj_is_kind_of, rb_obj_respond_to,
-    rb_sym2id, rb_ull2inum, ruby_fl_type, ruby_special_consts, ruby_value_type, RBasic, ID, VALUE,
+    rb_any_to_s, rb_block_call_kw, rb_check_array_type, rb_check_funcall_kw, rb_check_hash_type,
+    rb_check_id, rb_check_id_cstr, rb_check_string_type, rb_check_symbol_cstr, rb_cmpint,
+    rb_enumeratorize_with_size_kw, rb_eql, rb_equal, rb_funcall, rb_funcall_with_block_kw,
+    rb_funcallv_kw, rb_funcallv_public_kw, rb_gc_register_address, rb_gc_unregister_address,
+    rb_hash, rb_id2name, rb_id2sym, rb_inspect, rb_intern3, rb_ll2inum, rb_obj_as_string,
+    rb_obj_classname, rb_obj_clone, rb_obj_dup, rb_obj_freeze, rb_obj_is_instance_of,
+    rb_obj_is_kind_of, rb_obj_method, rb_obj_respond_to, rb_sym2id, rb_ull2inum, ruby_fl_type,
+    ruby_special_consts, ruby_value_type, RBasic, ID, VALUE,
 };
 
 // These don't seem to appear consistently in bindgen output, not sure if they
@@ -38,6 +42,7 @@ const RUBY_FIXNUM_MIN: c_long = c_long::MIN / 2;
 
 use crate::{
     block::Proc,
+    bound_method::BoundMethod,
     class::RClass,
     encoding::EncodingCapable,
     enumerator::Enumerator,
@@ -48,10 +53,12 @@ use crate::{
     method::{Block, BlockReturn},
     module::Module,
     numeric::Numeric,
+    r_array::RArray,
     r_bignum::RBignum,
+    r_hash::{ForEach, RHash},
     r_string::RString,
     symbol::{IntoSymbol, Symbol},
-    try_convert::{TryConvert, TryConvertOwned},
+    try_convert::{nested, TryConvert, TryConvertOwned},
     Ruby,
 };
 
@@ -239,6 +246,49 @@ impl TryConvert for Value {
 /// let str = ruby.get_inner(opaque_str);
 /// rb_assert!(ruby, r#"str == "example""#, str);
 /// ```
+///
+/// # Garbage collection
+///
+/// Wrapping a value in `Opaque` does not keep it alive. Ruby's garbage
+/// collector can't see values stored on the heap, so an `Opaque` stored in a
+/// long-lived Rust struct must be marked, or the value it wraps may be freed
+/// while it is still in use.
+///
+/// When the struct is wrapped in a Ruby object, mark the `Opaque` from
+/// [`DataTypeFunctions::mark`](crate::typed_data::DataTypeFunctions::mark)
+/// with [`Marker::mark`](crate::gc::Marker::mark). Otherwise, register it
+/// with [`gc::register_mark_object`](crate::gc::register_mark_object) or
+/// [`gc::register_global`](crate::gc::register_global).
+///
+/// ```
+/// use magnus::{gc, prelude::*, value::Opaque, DataTypeFunctions, Error, Ruby, TypedData, Value};
+///
+/// #[derive(TypedData)]
+/// #[magnus(class = "Callback", mark)]
+/// struct Callback {
+///     target: Opaque<Value>,
+/// }
+///
+/// impl DataTypeFunctions for Callback {
+///     fn mark(&self, marker: &gc::Marker) {
+///         marker.mark(self.target);
+///     }
+/// }
+///
+/// fn example(ruby: &Ruby) -> Result<(), Error> {
+///     ruby.define_class("Callback", ruby.class_object())?;
+///
+///     let target = ruby.str_new("example").as_value();
+///     let callback = ruby.obj_wrap(Callback {
+///         target: target.into(),
+///     });
+///     let inner = ruby.get_inner(callback.target);
+///     assert!(inner.equal(target)?);
+///
+///     Ok(())
+/// }
+/// # Ruby::init(example).unwrap()
+/// ```
 #[derive(Clone, Copy)]
 #[repr(transparent)]
 pub struct Opaque<T>(T);
@@ -602,6 +652,47 @@ where
     }
 }
 
+/// Compares `a` and `b` with `<=>`, raising `ArgumentError` if they are not
+/// comparable.
+fn cmp_strict(a: Value, b: Value) -> Result<Ordering, Error> {
+    cmpint(a.funcall("<=>", (b,))?, a, b)
+}
+
+/// Converts the result of `a <=> b` to an [`Ordering`], raising
+/// `ArgumentError` if it is `nil`.
+fn cmpint(cmp: Value, a: Value, b: Value) -> Result<Ordering, Error> {
+    let mut res = 0;
+    protect(|| {
+        res = unsafe { rb_cmpint(cmp.as_rb_value(), a.as_rb_value(), b.as_rb_value()) };
+        Ruby::get_with(a).qnil()
+    })?;
+    Ok(res.cmp(&0))
+}
+
+/// Calls `mid` on `recv`, passing up to 3 arguments without keywords inline
+/// to `rb_funcall`, and any other arguments as an array to `rb_funcallv_kw`.
+///
+/// # Safety
+///
+/// Must be called from a Ruby thread, and can raise, so must be called within
+/// [`protect`].
+unsafe fn funcallv(recv: VALUE, mid: ID, args: &[VALUE], kw_splat: u32) -> VALUE {
+    let inline = kw_splat == rb_sys::RB_NO_KEYWORDS;
+    match *args {
+        [] if inline => rb_funcall(recv, mid, 0),
+        [a] if inline => rb_funcall(recv, mid, 1, a),
+        [a, b] if inline => rb_funcall(recv, mid, 2, a, b),
+        [a, b, c] if inline => rb_funcall(recv, mid, 3, a, b, c),
+        _ => rb_funcallv_kw(
+            recv,
+            mid,
+            args.len() as c_int,
+            args.as_ptr(),
+            kw_splat as c_int,
+        ),
+    }
+}
+
 pub(crate) mod private {
     use super::*;
     use crate::value::ReprValue as _;
@@ -803,6 +894,11 @@ pub trait ReprValue: private::ReprValue {
 
     /// Checks for equality, delegating to the Ruby method `#==`.
     ///
+    /// See [`Value::eql`] for the equivalent of the `#eql?` method, and
+    /// [`Value::is_same_object`] for the equivalent of `#equal?`. For example
+    /// `1 == 1.0` is `true`, but `1.eql?(1.0)` and `1.equal?(1.0)` are both
+    /// `false`.
+    ///
     /// Ruby optimises this check if `self` and `other` are the same object
     /// or some built-in types, then calling the `#==` method will be skipped.
     ///
@@ -862,7 +958,11 @@ pub trait ReprValue: private::ReprValue {
 
     /// Checks for equality, delegating to the Ruby method `#eql?`.
     ///
-    /// See [`Value::equal`] for the equivalent of the `#==` method.
+    /// See [`Value::equal`] for the equivalent of the `#==` method, and
+    /// [`Value::is_same_object`] for the equivalent of `#equal?`.
+    ///
+    /// `#eql?` is the equality used by `Hash` to compare keys, so this is the
+    /// appropriate check when implementing hash-compatible logic.
     ///
     /// Ruby optimises this check if `self` and `other` are the same object
     /// for some built-in types, then calling the `#==` method will be skipped.
@@ -921,6 +1021,208 @@ pub trait ReprValue: private::ReprValue {
         }
     }
 
+    /// Checks if `self` and `other` are the same object, as with Ruby's
+    /// `#equal?` method.
+    ///
+    /// See [`Value::equal`] for the equivalent of the `#==` method, and
+    /// [`Value::eql`] for the equivalent of `#eql?`.
+    ///
+    /// This compares the object's identity directly, without calling any Ruby
+    /// methods, so `#equal?` being overridden is not respected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let a = ruby.str_new("example");
+    ///     let b = ruby.str_new("example");
+    ///     assert!(a.is_same_object(a));
+    ///     assert!(!a.is_same_object(b));
+    ///     assert!(a.equal(b)?);
+    ///
+    ///     let one = ruby.integer_from_i64(1);
+    ///     let float = ruby.float_from_f64(1.0);
+    ///     assert!(one.is_same_object(ruby.integer_from_i64(1)));
+    ///     assert!(one.equal(float)?);
+    ///     assert!(!one.eql(float)?);
+    ///     assert!(!one.is_same_object(float));
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    #[inline]
+    fn is_same_object<T>(self, other: T) -> bool
+    where
+        T: ReprValue,
+    {
+        self.as_rb_value() == other.as_rb_value()
+    }
+
+    /// Compares `self` and `other` with Ruby's `<=>` method.
+    ///
+    /// Returns `Some(Ordering::Less)`, `Some(Ordering::Equal)`, or
+    /// `Some(Ordering::Greater)` when `<=>` returns a negative number, zero, or
+    /// a positive number respectively. Returns `None` if `<=>` returns `nil`,
+    /// as `self` and `other` are not comparable.
+    ///
+    /// Returns `Err` if `<=>` raises.
+    ///
+    /// Named `rb_cmp` so it isn't confused with, or shadowed by,
+    /// [`Ord::cmp`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    ///
+    /// use magnus::{prelude::*, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let a = ruby.integer_from_i64(1);
+    ///     let b = ruby.float_from_f64(2.5);
+    ///     assert_eq!(a.rb_cmp(b)?, Some(Ordering::Less));
+    ///     assert_eq!(b.rb_cmp(a)?, Some(Ordering::Greater));
+    ///     assert_eq!(a.rb_cmp(a)?, Some(Ordering::Equal));
+    ///
+    ///     assert_eq!(a.rb_cmp(ruby.str_new("a"))?, None);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    ///
+    /// ```
+    /// use magnus::{eval, prelude::*, Error, Ruby, Value};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let (a, b): (Value, Value) = eval!(
+    ///         ruby,
+    ///         "
+    ///           class Example
+    ///             def <=>(other)
+    ///               raise
+    ///             end
+    ///           end
+    ///           [Example.new, Example.new]
+    ///         "
+    ///     )?;
+    ///
+    ///     assert!(a.rb_cmp(b).is_err());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    fn rb_cmp<T>(self, other: T) -> Result<Option<Ordering>, Error>
+    where
+        T: ReprValue,
+    {
+        let (val, other) = (self.as_value(), other.as_value());
+        let res: Value = val.funcall("<=>", (other,))?;
+        if res.is_nil() {
+            return Ok(None);
+        }
+        cmpint(res, val, other).map(Some)
+    }
+
+    /// Returns `min` if `self` is less than `min`, `max` if `self` is greater
+    /// than `max`, and `self` otherwise.
+    ///
+    /// Values are compared with Ruby's `<=>` method, so this works with any
+    /// comparable Ruby values, matching Ruby's `Comparable#clamp`.
+    ///
+    /// Returns `Err` if `min` is greater than `max`, if `<=>` raises, or if
+    /// `<=>` returns `nil` as the values are not comparable.
+    ///
+    /// Named `rb_clamp` so it isn't confused with, or shadowed by,
+    /// [`Ord::clamp`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, rb_assert, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let min = ruby.integer_from_i64(1);
+    ///     let max = ruby.integer_from_i64(10);
+    ///
+    ///     let res = ruby.integer_from_i64(15).rb_clamp(min, max)?;
+    ///     rb_assert!(ruby, "res == 10", res);
+    ///     let res = ruby.integer_from_i64(5).rb_clamp(min, max)?;
+    ///     rb_assert!(ruby, "res == 5", res);
+    ///
+    ///     let res = ruby.str_new("a").rb_clamp(ruby.str_new("b"), ruby.str_new("d"))?;
+    ///     rb_assert!(ruby, r#"res == "b""#, res);
+    ///
+    ///     assert!(ruby.str_new("a").rb_clamp(min, max).is_err());
+    ///     assert!(ruby.integer_from_i64(5).rb_clamp(max, min).is_err());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    fn rb_clamp<T>(self, min: T, max: T) -> Result<Value, Error>
+    where
+        T: ReprValue,
+    {
+        let (val, min, max) = (self.as_value(), min.as_value(), max.as_value());
+        if cmp_strict(min, max)? == Ordering::Greater {
+            return Err(Error::new(
+                Ruby::get_with(val).exception_arg_error(),
+                "min argument must be less than or equal to max argument",
+            ));
+        }
+        match cmp_strict(val, min)? {
+            Ordering::Equal => return Ok(val),
+            Ordering::Less => return Ok(min),
+            Ordering::Greater => (),
+        }
+        if cmp_strict(val, max)? == Ordering::Greater {
+            return Ok(max);
+        }
+        Ok(val)
+    }
+
+    /// Returns whether `self` is greater than or equal to `min` and less than
+    /// or equal to `max`.
+    ///
+    /// Values are compared with Ruby's `<=>` method, so this works with any
+    /// comparable Ruby values, matching Ruby's `Comparable#between?`.
+    ///
+    /// Returns `Err` if `<=>` raises, or if `<=>` returns `nil` as the values
+    /// are not comparable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let min = ruby.integer_from_i64(1);
+    ///     let max = ruby.integer_from_i64(10);
+    ///
+    ///     assert!(ruby.integer_from_i64(5).between(min, max)?);
+    ///     assert!(ruby.integer_from_i64(10).between(min, max)?);
+    ///     assert!(!ruby.integer_from_i64(15).between(min, max)?);
+    ///
+    ///     assert!(ruby.str_new("a").between(min, max).is_err());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    fn between<T>(self, min: T, max: T) -> Result<bool, Error>
+    where
+        T: ReprValue,
+    {
+        let val = self.as_value();
+        Ok(cmp_strict(val, min.as_value())? != Ordering::Less
+            && cmp_strict(val, max.as_value())? != Ordering::Greater)
+    }
+
     /// Returns an integer non-uniquely identifying `self`.
     ///
     /// The return value is not stable between different Ruby processes.
@@ -1028,7 +1330,10 @@ pub trait ReprValue: private::ReprValue {
     /// Returns an error if `self` is 'frozen'.
     ///
     /// Useful for checking if an object is frozen in a function that would
-    /// modify it.
+    /// modify it, before any changes are made. The error is a `FrozenError`,
+    /// the same as Ruby would raise. As with [`is_frozen`](Value::is_frozen)
+    /// this is available for all types, including [`RArray`](crate::RArray),
+    /// [`RHash`](crate::RHash), and [`RString`](crate::RString).
     ///
     /// # Examples
     /// ```
@@ -1049,6 +1354,22 @@ pub trait ReprValue: private::ReprValue {
     /// }
     /// # Ruby::init(example).unwrap()
     /// ```
+    ///
+    /// ```
+    /// use magnus::{prelude::*, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let ary = ruby.ary_from_vec(vec![1, 2, 3]);
+    ///     assert!(ary.check_frozen().is_ok());
+    ///
+    ///     ary.freeze();
+    ///     let err = ary.check_frozen().unwrap_err();
+    ///     assert!(err.is_kind_of(ruby.exception_frozen_error()));
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
     fn check_frozen(self) -> Result<(), Error> {
         if self.is_frozen() {
             Err(Error::new(
@@ -1062,6 +1383,8 @@ pub trait ReprValue: private::ReprValue {
 
     /// Mark `self` as frozen.
     ///
+    /// Returns `self`, as the same type, to allow chaining.
+    ///
     /// # Examples
     ///
     /// ```
@@ -1073,17 +1396,219 @@ pub trait ReprValue: private::ReprValue {
     ///     ary.freeze();
     ///     assert!(ary.is_frozen());
     ///
+    ///     let s = ruby.str_new("example").freeze();
+    ///     assert!(s.is_frozen());
+    ///     assert_eq!(s.to_string()?, "example");
+    ///
     ///     Ok(())
     /// }
     /// # Ruby::init(example).unwrap()
     /// ```
-    fn freeze(self) {
+    fn freeze(self) -> Self {
         unsafe { rb_obj_freeze(self.as_rb_value()) };
+        self
+    }
+
+    /// Mark `self` and, recursively, the contents of any `Array`s and
+    /// `Hash`es reachable from `self` as frozen.
+    ///
+    /// Objects other than `Array`s and `Hash`es are frozen, but their
+    /// instance variables are not walked. Classes and modules are left
+    /// unfrozen, as are immediate values (which are always frozen). Objects
+    /// that have already been visited are skipped, so structures containing
+    /// cycles are handled.
+    ///
+    /// Errors if the nesting is deeper than
+    /// [`DEFAULT_MAX_DEPTH`](crate::try_convert::DEFAULT_MAX_DEPTH), see
+    /// [`try_convert::with_max_depth`](crate::try_convert::with_max_depth).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, rb_assert, Error, RArray, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let ary: RArray = ruby.eval(r#"a = ["foo", {bar: ["baz"]}]; a << a; a"#)?;
+    ///     ary.deep_freeze()?;
+    ///     rb_assert!(ruby, "ary.frozen?", ary);
+    ///     rb_assert!(ruby, "ary[0].frozen?", ary);
+    ///     rb_assert!(ruby, "ary[1].frozen?", ary);
+    ///     rb_assert!(ruby, "ary[1][:bar].frozen?", ary);
+    ///     rb_assert!(ruby, "ary[1][:bar][0].frozen?", ary);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    fn deep_freeze(self) -> Result<Self, Error> {
+        // Values in `seen` are only used for their identity, and are all
+        // reachable from `self`, so it's fine to hold them on the heap.
+        fn walk(val: Value, seen: &mut HashSet<VALUE>) -> Result<(), Error> {
+            // classes and modules are shared by the whole VM, freezing them
+            // would prevent any further methods being defined
+            if matches!(
+                val.rb_type(),
+                ruby_value_type::RUBY_T_CLASS | ruby_value_type::RUBY_T_MODULE
+            ) || val.r_basic().is_none()
+                || !seen.insert(val.as_rb_value())
+            {
+                return Ok(());
+            }
+            nested(val, || {
+                if let Some(ary) = RArray::from_value(val) {
+                    for i in 0..ary.len() {
+                        walk(ary.entry(i as isize)?, seen)?;
+                    }
+                } else if let Some(hash) = RHash::from_value(val) {
+                    hash.foreach(|k: Value, v: Value| {
+                        walk(k, seen)?;
+                        walk(v, seen)?;
+                        Ok(ForEach::Continue)
+                    })?;
+                }
+                val.freeze();
+                Ok(())
+            })
+        }
+
+        walk(self.as_value(), &mut HashSet::new())?;
+        Ok(self)
+    }
+
+    /// Returns a shallow copy of `self`, as with Ruby's `Object#dup`.
+    ///
+    /// The copy is of the same class as `self`, so is returned as the same
+    /// type. Unlike [`ReprValue::clone_obj`], the copy is never frozen, and
+    /// does not copy the singleton class of `self`.
+    ///
+    /// Immediate values, such as Integers and Symbols, and other objects that
+    /// can't be copied, such as Floats, return themselves.
+    ///
+    /// Errors if `self` can not be copied, for example if its class has no
+    /// allocator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, rb_assert, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let s = ruby.str_new("example");
+    ///     s.freeze();
+    ///     let copy = s.dup()?;
+    ///     assert!(!copy.is_frozen());
+    ///     copy.cat(" copy");
+    ///     rb_assert!(ruby, r#"s == "example""#, s);
+    ///     rb_assert!(ruby, r#"copy == "example copy""#, copy);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    fn dup(self) -> Result<Self, Error> {
+        protect(|| unsafe { Value::new(rb_obj_dup(self.as_rb_value())) })
+            .map(|val| unsafe { Self::from_value_unchecked(val) })
+    }
+
+    /// Returns a shallow copy of `self`, as with Ruby's `Object#clone`.
+    ///
+    /// The copy is of the same class as `self`, so is returned as the same
+    /// type. Unlike [`ReprValue::dup`], the singleton class of `self` is also
+    /// copied, along with any methods defined on it.
+    ///
+    /// With `freeze` as `None` the copy is frozen if `self` is frozen.
+    /// `Some(true)` or `Some(false)` will freeze or not freeze the copy,
+    /// regardless of the frozen state of `self`.
+    ///
+    /// Errors if `self` can not be copied, or for `Some(false)` if `self` is
+    /// an object that is always frozen, such as an Integer.
+    ///
+    /// With `Some(freeze)` this calls the Ruby method `clone`, which may be
+    /// overridden, so the result is converted back to `Self` with
+    /// [`TryConvert`], erroring if that conversion fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, Error, RObject, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let obj: RObject = ruby.eval("o = Object.new; def o.hello; :hi; end; o.freeze")?;
+    ///
+    ///     let copy = obj.clone_obj(None)?;
+    ///     assert!(copy.is_frozen());
+    ///     assert!(copy.respond_to("hello", false)?);
+    ///
+    ///     let copy = obj.clone_obj(Some(false))?;
+    ///     assert!(!copy.is_frozen());
+    ///
+    ///     assert!(!obj.dup()?.respond_to("hello", false)?);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    fn clone_obj(self, freeze: Option<bool>) -> Result<Self, Error>
+    where
+        Self: TryConvert,
+    {
+        match freeze {
+            None => protect(|| unsafe { Value::new(rb_obj_clone(self.as_rb_value())) })
+                .map(|val| unsafe { Self::from_value_unchecked(val) }),
+            Some(freeze) => {
+                let handle = Ruby::get_with(self);
+                self.funcall("clone", (crate::kwargs!(&handle, "freeze" => freeze),))
+            }
+        }
+    }
+
+    /// Returns a frozen shallow copy of `self`, or `self` if it is already
+    /// frozen.
+    ///
+    /// This is useful when returning a value that is also held internally,
+    /// such as a Ruby object stored in a wrapped struct, as it prevents the
+    /// caller from mutating the internal value. Only `self` is copied, values
+    /// referenced by `self` (e.g. the elements of an Array) are shared.
+    ///
+    /// Errors if `self` can not be copied.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, rb_assert, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let internal = ruby.ary_from_vec(vec![1, 2, 3]);
+    ///     let copy = internal.frozen_copy()?;
+    ///     assert!(copy.is_frozen());
+    ///     assert!(!internal.is_frozen());
+    ///     rb_assert!(ruby, "(copy << 4 rescue $!).is_a?(FrozenError)", copy);
+    ///     rb_assert!(ruby, "internal == [1, 2, 3]", internal);
+    ///
+    ///     // already frozen values are returned as-is
+    ///     let frozen = ruby.str_new("example").freeze();
+    ///     let same = frozen.frozen_copy()?;
+    ///     rb_assert!(ruby, "frozen.equal?(same)", frozen, same);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    fn frozen_copy(self) -> Result<Self, Error> {
+        if self.is_frozen() {
+            Ok(self)
+        } else {
+            Ok(self.dup()?.freeze())
+        }
     }
 
     /// Convert `self` to a `bool`, following Ruby's rules of `false` and `nil`
     /// as boolean `false` and everything else boolean `true`.
     ///
+    /// This is Ruby's notion of truthiness, as used by conditionals. It
+    /// differs from converting to a `bool` with [`TryConvert`], which only
+    /// accepts `true` and `false`, and errors for any other value.
+    ///
     /// # Examples
     ///
     /// ```
@@ -1099,6 +1624,10 @@ pub trait ReprValue: private::ReprValue {
     ///     assert!(ruby.eval::<Value>(":foo")?.to_bool());
     ///     assert!(ruby.eval::<Value>("Object.new")?.to_bool());
     ///
+    ///     // conversion with `TryConvert` is strict
+    ///     assert!(ruby.eval::<bool>("0").is_err());
+    ///     assert!(ruby.eval::<bool>("nil").is_err());
+    ///
     ///     Ok(())
     /// }
     /// # Ruby::init(example).unwrap()
@@ -1114,6 +1643,19 @@ pub trait ReprValue: private::ReprValue {
     /// value converts to a `T`, or returns `Err` if the method raises or the
     /// conversion fails.
     ///
+    /// This ignores method visibility, so can call private and protected
+    /// methods, like Ruby's `send`. When acting as an external caller, see
+    /// [`funcall_public`](ReprValue::funcall_public).
+    ///
+    /// When calling a method many times, such as a Ruby callback in a loop, a
+    /// [`LazyId`] can be used for `method` to avoid looking up the method name
+    /// on every call.
+    ///
+    /// Keyword arguments are passed by making the last element of an `args`
+    /// tuple a [`KwArgs`](crate::KwArgs), either with the
+    /// [`kwargs!`](crate::kwargs) macro, or by wrapping an [`RHash`] built at
+    /// runtime.
+    ///
     /// # Examples
     ///
     /// ```
@@ -1130,7 +1672,7 @@ pub trait ReprValue: private::ReprValue {
     /// ```
     ///
     /// ```
-    /// use magnus::{eval, kwargs, prelude::*, Error, RObject, Ruby};
+    /// use magnus::{eval, kwargs, prelude::*, Error, KwArgs, RObject, Ruby};
     ///
     /// fn example(ruby: &Ruby) -> Result<(), Error> {
     ///     let object: RObject = eval!(
@@ -1149,6 +1691,11 @@ pub trait ReprValue: private::ReprValue {
     ///     let result: i32 = object.funcall("add", (1, 2, kwargs!("c" => 3)))?;
     ///     assert_eq!(result, 6);
     ///
+    ///     let kw = ruby.hash_new();
+    ///     kw.aset(ruby.to_symbol("c"), 4)?;
+    ///     let result: i32 = object.funcall("add", (1, 2, KwArgs(kw)))?;
+    ///     assert_eq!(result, 7);
+    ///
     ///     Ok(())
     /// }
     /// # Ruby::init(example).unwrap()
@@ -1165,16 +1712,9 @@ pub trait ReprValue: private::ReprValue {
         let args = args.into_arg_list_with(&handle);
         let slice = args.as_ref();
         unsafe {
-            protect(|| {
-                Value::new(rb_funcallv_kw(
-                    self.as_rb_value(),
-                    id.as_rb_id(),
-                    slice.len() as c_int,
-                    slice.as_ptr() as *const VALUE,
-                    kw_splat as c_int,
-                ))
-            })
-            .and_then(TryConvert::try_convert)
+            let raw = slice::from_raw_parts(slice.as_ptr() as *const VALUE, slice.len());
+            protect(|| Value::new(funcallv(self.as_rb_value(), id.as_rb_id(), raw, kw_splat)))
+                .and_then(TryConvert::try_convert)
         }
     }
 
@@ -1184,6 +1724,10 @@ pub trait ReprValue: private::ReprValue {
     /// value converts to a `T`, or returns `Err` if the method raises or the
     /// conversion fails.
     ///
+    /// This respects method visibility, like Ruby's `public_send`, returning
+    /// `Err` with a `NoMethodError` if `method` is private or protected. See
+    /// [`funcall`](ReprValue::funcall) to ignore visibility.
+    ///
     /// # Examples
     ///
     /// ```
@@ -1213,7 +1757,7 @@ pub trait ReprValue: private::ReprValue {
     ///     assert_eq!(result.name()?, "bar");
     ///
     ///     let result: Result<Symbol, Error> = object.funcall_public("baz", ());
-    ///     assert!(result.is_err());
+    ///     assert!(result.unwrap_err().is_kind_of(ruby.exception_no_method_error()));
     ///
     ///     Ok(())
     /// }
@@ -1470,11 +2014,48 @@ pub trait ReprValue: private::ReprValue {
         Ok(res)
     }
 
+    /// Look up the method `name` on `self`, returning it as a
+    /// [`BoundMethod`].
+    ///
+    /// Returns `Err` if `self` does not respond to `name`. Like Ruby's
+    /// `Object#method` this will find private methods.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, Error, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let method = ruby.integer_from_i64(1).method("+")?;
+    ///     let res: i64 = method.call((2,))?;
+    ///     assert_eq!(res, 3);
+    ///
+    ///     assert!(ruby.qnil().method("non_existant").is_err());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    fn method<M>(self, name: M) -> Result<BoundMethod, Error>
+    where
+        M: IntoId,
+    {
+        let handle = Ruby::get_with(self);
+        let id = name.into_id_with(&handle);
+        unsafe {
+            protect(|| Value::new(rb_obj_method(self.as_rb_value(), rb_id2sym(id.as_rb_id()))))
+                .map(|v| BoundMethod::from_value_unchecked(v))
+        }
+    }
+
     /// Convert `self` to a Ruby `String`.
     ///
     /// If `self` is already a `String` is it wrapped as a `RString`, otherwise
     /// the Ruby `to_s` method is called.
     ///
+    /// See [`ReprValue::check_string_type`] for the implicit conversion with
+    /// `to_str`, which only applies to 'string-like' objects.
+    ///
     /// # Examples
     ///
     /// ```
@@ -1497,6 +2078,194 @@ pub trait ReprValue: private::ReprValue {
         }
     }
 
+    /// Implicitly convert `self` to a Ruby `String`, using Ruby's `to_str`
+    /// protocol.
+    ///
+    /// If `self` is already a `String` is it wrapped as a `RString`, if it
+    /// responds to `to_str` that method is called, otherwise `None` is
+    /// returned. This matches how Ruby's core methods accept 'string-like'
+    /// arguments. See [`ReprValue::to_r_string`] for the explicit conversion
+    /// with `to_s`, which works for any object.
+    ///
+    /// Returns `Err` if `to_str` raises, or doesn't return a `String`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, prelude::*, Error, Ruby, Value};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let value = ruby.str_new("foo");
+    ///     assert_eq!(value.check_string_type()?.unwrap().to_string()?, "foo");
+    ///
+    ///     let value: Value = eval!(
+    ///         ruby,
+    ///         "
+    ///           class Example
+    ///             def to_str
+    ///               \"bar\"
+    ///             end
+    ///           end
+    ///           Example.new
+    ///         "
+    ///     )?;
+    ///     assert_eq!(value.check_string_type()?.unwrap().to_string()?, "bar");
+    ///
+    ///     assert!(ruby.integer_from_i64(1).check_string_type()?.is_none());
+    ///     assert!(ruby.qnil().check_string_type()?.is_none());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    fn check_string_type(self) -> Result<Option<RString>, Error> {
+        if let Some(v) = RString::from_value(self.as_value()) {
+            return Ok(Some(v));
+        }
+        protect(|| unsafe { Value::new(rb_check_string_type(self.as_rb_value())) })
+            .map(RString::from_value)
+    }
+
+    /// Implicitly convert `self` to a Ruby `Array`, using Ruby's `to_ary`
+    /// protocol.
+    ///
+    /// If `self` is already an `Array` is it wrapped as a `RArray`, if it
+    /// responds to `to_ary` that method is called, otherwise `None` is
+    /// returned. This matches how Ruby's core methods accept 'array-like'
+    /// arguments. See [`ReprValue::to_a`] for the explicit conversion
+    /// with `to_a`.
+    ///
+    /// Returns `Err` if `to_ary` raises, or doesn't return an `Array`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, prelude::*, Error, Ruby, Value};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let value = ruby.ary_from_vec(vec![1, 2, 3]);
+    ///     assert_eq!(value.check_array_type()?.unwrap().to_vec::<i64>()?, [1, 2, 3]);
+    ///
+    ///     let value: Value = eval!(
+    ///         ruby,
+    ///         "
+    ///           class Example
+    ///             def to_ary
+    ///               [4, 5, 6]
+    ///             end
+    ///           end
+    ///           Example.new
+    ///         "
+    ///     )?;
+    ///     assert_eq!(value.check_array_type()?.unwrap().to_vec::<i64>()?, [4, 5, 6]);
+    ///
+    ///     let value: Value = ruby.eval("1..3")?;
+    ///     assert!(value.check_array_type()?.is_none());
+    ///     assert!(ruby.qnil().check_array_type()?.is_none());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    fn check_array_type(self) -> Result<Option<RArray>, Error> {
+        if let Some(v) = RArray::from_value(self.as_value()) {
+            return Ok(Some(v));
+        }
+        protect(|| unsafe { Value::new(rb_check_array_type(self.as_rb_value())) })
+            .map(RArray::from_value)
+    }
+
+    /// Implicitly convert `self` to a Ruby `Hash`, using Ruby's `to_hash`
+    /// protocol.
+    ///
+    /// If `self` is already a `Hash` is it wrapped as a `RHash`, if it
+    /// responds to `to_hash` that method is called, otherwise `None` is
+    /// returned. This matches how Ruby's core methods accept options hashes.
+    ///
+    /// Returns `Err` if `to_hash` raises, or doesn't return a `Hash`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{eval, prelude::*, Error, Ruby, Value};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let value = ruby.hash_new();
+    ///     value.aset("foo", 1)?;
+    ///     assert_eq!(value.check_hash_type()?.unwrap().len(), 1);
+    ///
+    ///     let value: Value = eval!(
+    ///         ruby,
+    ///         "
+    ///           class Example
+    ///             def to_hash
+    ///               {foo: 1, bar: 2}
+    ///             end
+    ///           end
+    ///           Example.new
+    ///         "
+    ///     )?;
+    ///     assert_eq!(value.check_hash_type()?.unwrap().len(), 2);
+    ///
+    ///     assert!(ruby.ary_new().check_hash_type()?.is_none());
+    ///     assert!(ruby.qnil().check_hash_type()?.is_none());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    fn check_hash_type(self) -> Result<Option<RHash>, Error> {
+        if let Some(v) = RHash::from_value(self.as_value()) {
+            return Ok(Some(v));
+        }
+        protect(|| unsafe { Value::new(rb_check_hash_type(self.as_rb_value())) })
+            .map(RHash::from_value)
+    }
+
+    /// Explicitly convert `self` to a Ruby `Array`, by calling `to_a`.
+    ///
+    /// If `self` is already an `Array` is it wrapped as a `RArray`, otherwise
+    /// the Ruby `to_a` method is called. See [`ReprValue::check_array_type`]
+    /// for the implicit conversion with `to_ary`.
+    ///
+    /// Returns `Err` if `self` does not respond to `to_a`, if `to_a` raises,
+    /// or if it doesn't return an `Array`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, Error, Ruby, Value};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let value: Value = ruby.eval("1..3")?;
+    ///     assert_eq!(value.to_a()?.to_vec::<i64>()?, [1, 2, 3]);
+    ///
+    ///     assert!(ruby.qnil().to_a()?.is_empty());
+    ///
+    ///     let value: Value = ruby.eval("Object.new")?;
+    ///     assert!(value.to_a().is_err());
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    fn to_a(self) -> Result<RArray, Error> {
+        if let Some(v) = RArray::from_value(self.as_value()) {
+            return Ok(v);
+        }
+        let res: Value = self.funcall("to_a", ())?;
+        RArray::from_value(res).ok_or_else(|| {
+            Error::new(
+                Ruby::get_with(self).exception_type_error(),
+                format!(
+                    "can't convert {0} to Array ({0}#to_a gives {1})",
+                    self.class(),
+                    res.class()
+                ),
+            )
+        })
+    }
+
     /// Convert `self` to a Rust string.
     ///
     /// # Safety
@@ -1596,6 +2365,36 @@ pub trait ReprValue: private::ReprValue {
         cstr.to_string_lossy()
     }
 
+    /// Return the name of `self`'s class as an owned string.
+    ///
+    /// This reads the name directly, without calling any Ruby methods, so is
+    /// suitable for error messages and logging. For anonymous classes this
+    /// returns a description of the class, such as `#<Class:0x000...>`.
+    ///
+    /// See also [`classname`](ReprValue::classname), which avoids allocating
+    /// a new string, and [`class`](ReprValue::class).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, Error, RClass, Ruby};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     assert_eq!(ruby.hash_new().class_name(), "Hash");
+    ///     assert_eq!(ruby.qnil().class_name(), "NilClass");
+    ///
+    ///     let class = RClass::new(ruby.class_object())?;
+    ///     let obj = class.new_instance(())?;
+    ///     assert!(obj.class_name().starts_with("#<Class:"));
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    fn class_name(self) -> String {
+        unsafe { self.classname() }.into_owned()
+    }
+
     /// Returns whether or not `self` is an instance of `class`.
     ///
     /// # Examples
@@ -1618,6 +2417,39 @@ pub trait ReprValue: private::ReprValue {
         unsafe { Value::new(rb_obj_is_kind_of(self.as_rb_value(), class.as_rb_value())).to_bool() }
     }
 
+    /// Returns whether or not `self` is an instance of exactly `class`, not
+    /// including subclasses of `class`.
+    ///
+    /// See also [`is_kind_of`](Value::is_kind_of).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{prelude::*, Error, Ruby, Value};
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     let value = ruby.eval::<Value>("1")?;
+    ///     assert!(value.is_instance_of(ruby.class_integer()));
+    ///     assert!(!value.is_instance_of(ruby.class_numeric()));
+    ///     assert!(value.is_kind_of(ruby.class_numeric()));
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    fn is_instance_of<T>(self, class: T) -> bool
+    where
+        T: ReprValue + Module,
+    {
+        unsafe {
+            Value::new(rb_obj_is_instance_of(
+                self.as_rb_value(),
+                class.as_rb_value(),
+            ))
+            .to_bool()
+        }
+    }
+
     /// Generate an [`Enumerator`] from `method` on `self`, passing `args` to
     /// `method`.
     ///
@@ -1659,6 +2491,139 @@ pub trait ReprValue: private::ReprValue {
             ))
         }
     }
+
+    /// Generate an [`Enumerator`] from `method` on `self`, passing `args` to
+    /// `method`, with `size` used to calculate the Enumerator's `size`.
+    ///
+    /// `size` is called each time `size` is called on the Enumerator, it
+    /// should return `None` if the size is unknown or the Enumerator is
+    /// unbounded, which will be reported to Ruby as `nil`. When the yielded
+    /// values come from an [`ExactSizeIterator`] see
+    /// [`enumeratorize_exact_size`](ReprValue::enumeratorize_exact_size).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{block::Yield, method, prelude::*, rb_assert, Error, Ruby, Value};
+    ///
+    /// fn count_to_3(
+    ///     ruby: &Ruby,
+    ///     rb_self: Value,
+    /// ) -> Result<Yield<impl Iterator<Item = u8>>, Error> {
+    ///     if ruby.block_given() {
+    ///         Ok(Yield::Iter(1..=3))
+    ///     } else {
+    ///         Ok(Yield::Enumerator(rb_self.enumeratorize_with_size(
+    ///             "count_to_3",
+    ///             (),
+    ///             || Some(3),
+    ///         )?))
+    ///     }
+    /// }
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     ruby.define_global_function("count_to_3", method!(count_to_3, 0));
+    ///
+    ///     rb_assert!(ruby, "count_to_3.size == 3");
+    ///     rb_assert!(ruby, "count_to_3.to_a == [1, 2, 3]");
+    ///
+    ///     let enumerator = ruby.ary_new().enumeratorize_with_size("cycle", (), || None)?;
+    ///     rb_assert!(ruby, "enumerator.size == nil", enumerator);
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    fn enumeratorize_with_size<M, A, F>(
+        self,
+        method: M,
+        args: A,
+        mut size: F,
+    ) -> Result<Enumerator, Error>
+    where
+        M: IntoSymbol,
+        A: ArgList,
+        F: 'static + Send + FnMut() -> Option<usize>,
+    {
+        let handle = Ruby::get_with(self);
+        let kw_splat = kw_splat(&args);
+        let method = method.into_symbol_with(&handle);
+        let args = args.into_arg_list_with(&handle);
+        let mut argv = Vec::with_capacity(args.as_ref().len() + 1);
+        argv.push(method.as_value());
+        argv.extend(args.as_ref().iter().map(|v| v.as_value()));
+        let block = handle.proc_from_fn(move |_, _, _| size());
+        unsafe {
+            protect(|| {
+                Value::new(rb_funcall_with_block_kw(
+                    self.as_rb_value(),
+                    "to_enum".into_id_with(&handle).as_rb_id(),
+                    argv.len() as c_int,
+                    argv.as_ptr() as *const VALUE,
+                    block.as_rb_value(),
+                    kw_splat as c_int,
+                ))
+            })
+            .map(|v| Enumerator::from_rb_value_unchecked(v.as_rb_value()))
+        }
+    }
+
+    /// Generate an [`Enumerator`] from `method` on `self`, passing `args` to
+    /// `method`, with the Enumerator's `size` given by the length of `iter`.
+    ///
+    /// The length is read once when the Enumerator is created, so `iter`
+    /// should produce the same values as will be yielded by `method`. This
+    /// isn't done automatically for the `Enumerator` variant of
+    /// [`Yield`](crate::block::Yield), so `iter` must be passed by hand as in
+    /// the example below.
+    ///
+    /// See also [`enumeratorize_with_size`](ReprValue::enumeratorize_with_size).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use magnus::{block::Yield, method, prelude::*, rb_assert, Error, Ruby, Value};
+    ///
+    /// fn count_to_3(
+    ///     ruby: &Ruby,
+    ///     rb_self: Value,
+    /// ) -> Result<Yield<impl Iterator<Item = u8>>, Error> {
+    ///     let iter = 1..=3;
+    ///     if ruby.block_given() {
+    ///         Ok(Yield::Iter(iter))
+    ///     } else {
+    ///         Ok(Yield::Enumerator(rb_self.enumeratorize_exact_size(
+    ///             "count_to_3",
+    ///             (),
+    ///             &iter,
+    ///         )?))
+    ///     }
+    /// }
+    ///
+    /// fn example(ruby: &Ruby) -> Result<(), Error> {
+    ///     ruby.define_global_function("count_to_3", method!(count_to_3, 0));
+    ///
+    ///     rb_assert!(ruby, "count_to_3.size == 3");
+    ///     rb_assert!(ruby, "count_to_3.to_a == [1, 2, 3]");
+    ///
+    ///     Ok(())
+    /// }
+    /// # Ruby::init(example).unwrap()
+    /// ```
+    fn enumeratorize_exact_size<M, A, I>(
+        self,
+        method: M,
+        args: A,
+        iter: &I,
+    ) -> Result<Enumerator, Error>
+    where
+        M: IntoSymbol,
+        A: ArgList,
+        I: ExactSizeIterator,
+    {
+        let len = iter.len();
+        self.enumeratorize_with_size(method, args, move || Some(len))
+    }
 }
 
 unsafe impl private::ReprValue for Value {}
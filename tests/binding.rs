@@ -0,0 +1,23 @@
+use magnus::{embed::init, function, rb_assert, Binding, Error, Value};
+
+fn double_x(binding: Binding) -> Result<i64, Error> {
+    let x: i64 = binding.local_variable_get("x")?;
+    binding.local_variable_set("x", x * 2)?;
+    binding.eval("x")
+}
+
+#[test]
+fn it_wraps_bindings() {
+    let ruby = unsafe { init() };
+
+    ruby.define_global_function("double_x", function!(double_x, 1));
+
+    rb_assert!(ruby, "x = 2; double_x(binding) == 4 && x == 4");
+    rb_assert!(ruby, "(double_x(nil) rescue $!).is_a?(TypeError)");
+    rb_assert!(ruby, "(double_x(binding) rescue $!).is_a?(NameError)");
+
+    let binding = ruby.binding_new().unwrap();
+    binding.local_variable_set("y", 1).unwrap();
+    assert!(ruby.binding_new().unwrap().eval::<Value>("y").is_err());
+    assert_eq!(binding.eval::<i64>("y + 1").unwrap(), 2);
+}
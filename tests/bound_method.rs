@@ -0,0 +1,19 @@
+use magnus::{prelude::*, BoundMethod, UnboundMethod};
+
+#[test]
+fn it_binds_and_unbinds_methods() {
+    let ruby = unsafe { magnus::embed::init() };
+
+    let method = ruby.str_new("hello").method("upcase").unwrap();
+    let res: String = method.call(()).unwrap();
+    assert_eq!(res, "HELLO");
+
+    let unbound: UnboundMethod = method.unbind().unwrap();
+    let rebound: BoundMethod = unbound.bind(ruby.str_new("world")).unwrap();
+    let res: String = rebound.call(()).unwrap();
+    assert_eq!(res, "WORLD");
+
+    let unbound = ruby.class_array().instance_method("length").unwrap();
+    assert!(unbound.bind(ruby.str_new("nope")).is_err());
+    assert!(ruby.qnil().method("non_existant").is_err());
+}
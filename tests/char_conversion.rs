@@ -0,0 +1,27 @@
+use magnus::{embed::init, function, rb_assert};
+
+fn delimiter(c: char) -> char {
+    c
+}
+
+#[test]
+fn it_converts_chars() {
+    let ruby = unsafe { init() };
+
+    ruby.define_global_function("delimiter", function!(delimiter, 1));
+
+    rb_assert!(ruby, r#"delimiter(",") == ",""#);
+    rb_assert!(ruby, r#"delimiter("🦀") == "🦀""#);
+    rb_assert!(ruby, r#"delimiter("é").encoding == Encoding::UTF_8"#);
+    rb_assert!(
+        ruby,
+        r#"delimiter("é".encode("ISO-8859-1")).encoding == Encoding::UTF_8"#
+    );
+    rb_assert!(ruby, r#"(delimiter("") rescue $!).is_a?(ArgumentError)"#);
+    rb_assert!(ruby, r#"(delimiter("ab") rescue $!).is_a?(ArgumentError)"#);
+    rb_assert!(
+        ruby,
+        r#"(delimiter("ab") rescue $!).message == 'expected a single character string, got "ab"'"#
+    );
+    rb_assert!(ruby, r#"(delimiter(1) rescue $!).is_a?(TypeError)"#);
+}
@@ -0,0 +1,53 @@
+use chrono::{DateTime, FixedOffset, NaiveDateTime, TimeZone, Utc};
+use magnus::{rb_assert, Error};
+
+#[test]
+fn it_converts_chrono_types() -> Result<(), Error> {
+    let ruby = unsafe { magnus::embed::init() };
+
+    let epoch = DateTime::from_timestamp(0, 0).unwrap();
+    rb_assert!(ruby, "t == Time.at(0) && t.utc?", t = epoch);
+    assert_eq!(ruby.eval::<DateTime<Utc>>("Time.at(0)")?, epoch);
+
+    let before_epoch = DateTime::from_timestamp(-1, 500_000_000).unwrap();
+    rb_assert!(
+        ruby,
+        "t == Time.at(-1, 500, :millisecond)",
+        t = before_epoch
+    );
+    assert_eq!(
+        ruby.eval::<DateTime<Utc>>("Time.at(-1, 500, :millisecond)")?,
+        before_epoch
+    );
+
+    let nanos = DateTime::from_timestamp(1_700_000_000, 123_456_789).unwrap();
+    rb_assert!(ruby, "t.nsec == 123_456_789", t = nanos);
+    assert_eq!(
+        ruby.eval::<DateTime<Utc>>("Time.at(1_700_000_000, 123_456_789, :nanosecond)")?,
+        nanos
+    );
+
+    let offset = FixedOffset::west_opt(7 * 3600)
+        .unwrap()
+        .with_ymd_and_hms(2022, 5, 31, 9, 8, 0)
+        .unwrap();
+    rb_assert!(
+        ruby,
+        r#"t == Time.new(2022, 5, 31, 9, 8, 0, "-07:00") && t.utc_offset == -25_200"#,
+        t = offset
+    );
+    let res: DateTime<FixedOffset> = ruby.eval(r#"Time.new(2022, 5, 31, 9, 8, 0, "-07:00")"#)?;
+    assert_eq!(res, offset);
+    assert_eq!(res.offset().local_minus_utc(), -25_200);
+
+    let naive = nanos.naive_utc();
+    rb_assert!(ruby, "t.utc? && t.nsec == 123_456_789", t = naive);
+    assert_eq!(
+        ruby.eval::<NaiveDateTime>("Time.at(1_700_000_000, 123_456_789, :nanosecond)")?,
+        naive
+    );
+
+    assert!(ruby.eval::<DateTime<Utc>>("1").is_err());
+
+    Ok(())
+}
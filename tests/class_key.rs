@@ -0,0 +1,42 @@
+use std::{cell::RefCell, collections::HashMap};
+
+use magnus::{embed::init, function, prelude::*, rb_assert, ClassKey, Ruby, Value};
+
+thread_local! {
+    static HANDLERS: RefCell<HashMap<ClassKey, &'static str>> = RefCell::new(HashMap::new());
+}
+
+fn register_handlers(ruby: &Ruby) {
+    let point = ruby.eval("Point").unwrap();
+    HANDLERS.with(|handlers| {
+        let mut handlers = handlers.borrow_mut();
+        handlers.insert(ClassKey::register(ruby.class_string()), "string");
+        handlers.insert(ClassKey::register(ruby.class_integer()), "integer");
+        handlers.insert(ClassKey::register(point), "point");
+    });
+}
+
+fn describe(val: Value) -> &'static str {
+    HANDLERS.with(|handlers| {
+        handlers
+            .borrow()
+            .get(&val.class().as_key())
+            .copied()
+            .unwrap_or("unknown")
+    })
+}
+
+#[test]
+fn it_dispatches_on_class() {
+    let ruby = unsafe { init() };
+
+    ruby.eval::<Value>("Point = Struct.new(:x, :y)").unwrap();
+    register_handlers(&ruby);
+    ruby.define_global_function("describe", function!(describe, 1));
+
+    rb_assert!(ruby, r#"describe("a") == "string""#);
+    rb_assert!(ruby, r#"describe(1) == "integer""#);
+    rb_assert!(ruby, r#"describe(Point.new(1, 2)) == "point""#);
+    rb_assert!(ruby, r#"describe(Class.new(Point).new(1, 2)) == "unknown""#);
+    rb_assert!(ruby, r#"describe(:a) == "unknown""#);
+}
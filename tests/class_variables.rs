@@ -0,0 +1,18 @@
+use magnus::{prelude::*, rb_assert, Value};
+
+#[test]
+fn it_modifies_class_ivars_and_cvars() {
+    let ruby = unsafe { magnus::embed::init() };
+
+    let class = ruby.define_class("Config", ruby.class_object()).unwrap();
+
+    class.ivar_set("@setting", "on").unwrap();
+    rb_assert!(ruby, r#"Config.instance_variable_get(:@setting) == "on""#);
+
+    class.cvar_set("@@count", 1).unwrap();
+    rb_assert!(ruby, "Config.class_variable_get(:@@count) == 1");
+    assert_eq!(1, class.cvar_get::<_, i64>("@@count").unwrap());
+
+    let err = class.cvar_get::<_, Value>("@@missing").unwrap_err();
+    assert!(err.is_kind_of(ruby.exception_name_error()));
+}
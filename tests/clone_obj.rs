@@ -0,0 +1,16 @@
+use magnus::{embed::init, prelude::*, RObject, RString};
+
+#[test]
+fn it_checks_the_type_returned_by_clone() {
+    let ruby = unsafe { init() };
+
+    let s: RString = ruby
+        .eval(r#"s = "example"; def s.clone(**); 42; end; s"#)
+        .unwrap();
+    assert!(s.clone_obj(Some(false)).is_err());
+    assert_eq!(s.clone_obj(None).unwrap().to_string().unwrap(), "example");
+
+    let obj: RObject = ruby.eval("Object.new.freeze").unwrap();
+    assert!(!obj.clone_obj(Some(false)).unwrap().is_frozen());
+    assert!(obj.clone_obj(None).unwrap().is_frozen());
+}
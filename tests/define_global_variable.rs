@@ -0,0 +1,47 @@
+use std::cell::RefCell;
+
+use magnus::{embed::init, prelude::*, rb_assert, Error, Value};
+
+thread_local! {
+    static NAME: RefCell<String> = RefCell::new(String::new());
+}
+
+#[test]
+fn it_defines_global_variables() {
+    let ruby = unsafe { init() };
+
+    ruby.define_global_variable(
+        "name",
+        |_ruby| Ok(NAME.with(|name| name.borrow().clone())),
+        |ruby, val| {
+            if val.is_nil() {
+                return Err(Error::new(ruby.exception_type_error(), "name can't be nil"));
+            }
+            let val = String::try_convert(val)?;
+            NAME.with(|name| *name.borrow_mut() = val);
+            Ok(())
+        },
+    )
+    .unwrap();
+
+    rb_assert!(ruby, r#"$name == """#);
+    ruby.eval::<Value>(r#"$name = "test""#).unwrap();
+    NAME.with(|name| assert_eq!(*name.borrow(), "test"));
+    rb_assert!(ruby, r#"$name == "test""#);
+    rb_assert!(ruby, "(($name = nil) rescue $!).is_a?(TypeError)");
+    rb_assert!(ruby, r#"$name == "test""#);
+
+    ruby.define_global_variable(
+        "$broken",
+        |ruby| Err::<Value, _>(Error::new(ruby.exception_runtime_error(), "boom")),
+        |_ruby, _val| Ok(()),
+    )
+    .unwrap();
+    rb_assert!(ruby, r#"($broken rescue $!.message) == "boom""#);
+
+    ruby.define_readonly_variable("frozen_name", ruby.str_new("fixed"))
+        .unwrap();
+    ruby.gc_start();
+    rb_assert!(ruby, r#"$frozen_name == "fixed""#);
+    rb_assert!(ruby, r#"(($frozen_name = "x") rescue $!).is_a?(NameError)"#);
+}
@@ -0,0 +1,39 @@
+use magnus::{embed::init, prelude::*, rb_assert, Error};
+
+#[test]
+fn it_defines_enumerators_from_closures() {
+    let ruby = unsafe { init() };
+
+    let e = ruby
+        .enumerator_new(|_ruby, y| {
+            for i in 1..=3 {
+                y.yield_value::<_, ()>(i)?;
+            }
+            Ok(())
+        })
+        .unwrap();
+    rb_assert!(ruby, "e.to_a == [1, 2, 3]", e);
+    rb_assert!(ruby, "e.map { |i| i * 2 } == [2, 4, 6]", e);
+
+    let mut iter = e;
+    assert_eq!(iter.next().unwrap().and_then(i64::try_convert).unwrap(), 1);
+    assert_eq!(iter.next().unwrap().and_then(i64::try_convert).unwrap(), 2);
+    assert_eq!(iter.next().unwrap().and_then(i64::try_convert).unwrap(), 3);
+    assert!(iter.next().is_none());
+
+    let e = ruby
+        .enumerator_new(|_ruby, y| {
+            y.yield_values::<_, ()>(("a", 1))?;
+            y.yield_values::<_, ()>(("b", 2))
+        })
+        .unwrap();
+    rb_assert!(ruby, r#"e.to_h == {"a" => 1, "b" => 2}"#, e);
+
+    let e = ruby
+        .enumerator_new(|ruby, y| {
+            y.yield_value::<_, ()>(1)?;
+            Err(Error::new(ruby.exception_runtime_error(), "boom"))
+        })
+        .unwrap();
+    rb_assert!(ruby, r#"(e.to_a rescue $!.message) == "boom""#, e);
+}
@@ -0,0 +1,29 @@
+use magnus::{embed::init, function, rb_assert, Error, Ruby, Value};
+
+fn to_int(val: Value) -> Result<(), Error> {
+    Err(Error::type_error("Integer", val))
+}
+
+#[test]
+fn it_matches_ruby_error_messages() {
+    let ruby = unsafe { init() };
+
+    ruby.define_global_function("to_int", function!(to_int, 1));
+
+    rb_assert!(
+        ruby,
+        r#"
+            ours = (to_int([]) rescue $!)
+            theirs = (Integer.sqrt([]) rescue $!)
+            ours.class == theirs.class && ours.message == theirs.message
+        "#
+    );
+    rb_assert!(
+        ruby,
+        r#"(to_int(nil) rescue $!).message == "no implicit conversion of nil into Integer""#
+    );
+    rb_assert!(
+        ruby,
+        r#"(to_int(false) rescue $!).message == "no implicit conversion of false into Integer""#
+    );
+}
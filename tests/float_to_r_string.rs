@@ -0,0 +1,47 @@
+use magnus::{embed::init, prelude::*};
+
+#[test]
+fn it_formats_floats_like_ruby() {
+    let ruby = unsafe { init() };
+
+    let values = [
+        0.0,
+        -0.0,
+        1.0,
+        -1.0,
+        0.1,
+        1.0 / 3.0,
+        100.0,
+        1e15,
+        1e16,
+        123456789012345.6,
+        0.0001,
+        0.00001,
+        -1.5e-7,
+        1e20,
+        1e100,
+        1e-100,
+        f64::MAX,
+        f64::MIN_POSITIVE,
+        5e-324,
+        f64::INFINITY,
+        f64::NEG_INFINITY,
+        f64::NAN,
+    ];
+    for n in values {
+        let f = ruby.float_from_f64(n);
+        let expected: String = f.funcall("to_s", ()).unwrap();
+        let actual = f.to_r_string();
+        assert_eq!(actual.to_string().unwrap(), expected, "formatting {:?}", n);
+        assert!(actual.enc_get() == ruby.usascii_encindex());
+    }
+
+    let mut x = 0.7_f64;
+    for i in -40..40 {
+        let n = x * 10_f64.powi(i);
+        let f = ruby.float_from_f64(n);
+        let expected: String = f.funcall("to_s", ()).unwrap();
+        assert_eq!(f.to_r_string().to_string().unwrap(), expected);
+        x = (x * 7.31).fract() + 0.1;
+    }
+}
@@ -1,4 +1,4 @@
-use magnus::{prelude::*, rb_assert, Value};
+use magnus::{prelude::*, rb_assert, RArray, Value};
 
 #[test]
 fn it_can_check_frozen() {
@@ -20,4 +20,19 @@ fn it_can_check_frozen() {
     rb_assert!(ruby, "!val.frozen?", val);
     val.freeze();
     rb_assert!(ruby, "val.frozen?", val);
+
+    // deep_freeze leaves classes and modules unfrozen
+    let ary: RArray = ruby
+        .eval(r#"class DeepFreezeExample; end; [DeepFreezeExample, Comparable, {k: ["v"]}]"#)
+        .unwrap();
+    ary.deep_freeze().unwrap();
+    rb_assert!(
+        ruby,
+        "ary.frozen? && ary[2].frozen? && ary[2][:k][0].frozen?",
+        ary
+    );
+    rb_assert!(ruby, "!ary[0].frozen? && !ary[1].frozen?", ary);
+    rb_assert!(ruby, "!DeepFreezeExample.frozen?");
+    ruby.eval::<Value>("class DeepFreezeExample; def example; end; end")
+        .unwrap();
 }
@@ -0,0 +1,30 @@
+use magnus::{embed::init, prelude::*, rb_assert};
+
+#[test]
+fn it_returns_frozen_copies() {
+    let ruby = unsafe { init() };
+
+    let s = ruby.str_frozen_from_slice(b"buffer");
+    assert!(s.is_frozen());
+    rb_assert!(ruby, r#"s == "buffer".b"#, s);
+    rb_assert!(ruby, "s.encoding == Encoding::BINARY", s);
+
+    let internal = ruby.str_new("internal");
+    let copy = internal.frozen_copy().unwrap();
+    assert!(copy.is_frozen());
+    assert!(!internal.is_frozen());
+    rb_assert!(ruby, "!internal.equal?(copy)", internal, copy);
+    internal.cat(" changed");
+    rb_assert!(ruby, r#"copy == "internal""#, copy);
+
+    let hash = ruby.hash_new();
+    hash.aset("a", 1).unwrap();
+    let copy = hash.frozen_copy().unwrap();
+    rb_assert!(ruby, r#"copy.frozen? && copy == {"a" => 1}"#, copy);
+
+    let frozen = copy.frozen_copy().unwrap();
+    rb_assert!(ruby, "copy.equal?(frozen)", copy, frozen);
+
+    let int = ruby.integer_from_i64(1).frozen_copy().unwrap();
+    rb_assert!(ruby, "int == 1", int);
+}
@@ -0,0 +1,62 @@
+use magnus::{
+    embed::init, function, gc, method, prelude::*, value::Opaque, DataTypeFunctions, RString, Ruby,
+    TypedData,
+};
+
+#[derive(TypedData)]
+#[magnus(class = "Holder", free_immediately, mark, compact)]
+struct Holder {
+    value: std::cell::Cell<Opaque<RString>>,
+}
+
+impl Holder {
+    fn new(value: RString) -> Self {
+        Self {
+            value: std::cell::Cell::new(value.into()),
+        }
+    }
+
+    fn value(ruby: &Ruby, rb_self: &Self) -> RString {
+        ruby.get_inner(rb_self.value.get())
+    }
+}
+
+impl DataTypeFunctions for Holder {
+    fn mark(&self, marker: &gc::Marker) {
+        marker.mark_movable(self.value.get());
+    }
+
+    fn compact(&self, compactor: &gc::Compactor) {
+        self.value.set(compactor.location(self.value.get()));
+    }
+}
+
+#[test]
+fn it_keeps_marked_values_alive_under_stress() {
+    let ruby = unsafe { init() };
+
+    let class = ruby.define_class("Holder", ruby.class_object()).unwrap();
+    class
+        .define_singleton_method("new", function!(Holder::new, 1))
+        .unwrap();
+    class
+        .define_method("value", method!(Holder::value, 0))
+        .unwrap();
+
+    let was_stressed = ruby.gc_stress(true).unwrap();
+    let holders: magnus::RArray = ruby
+        .eval(r#"(0...20).map { |i| Holder.new("value #{i}") }"#)
+        .unwrap();
+    assert!(ruby.gc_stress(was_stressed).unwrap());
+    assert!(!ruby.eval::<bool>("GC.stress").unwrap());
+
+    ruby.gc_start();
+    // not all platforms support compaction
+    let _ = ruby.gc_compact();
+
+    magnus::rb_assert!(
+        ruby,
+        r#"holders.map(&:value) == (0...20).map { |i| "value #{i}" }"#,
+        holders
+    );
+}
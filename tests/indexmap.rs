@@ -0,0 +1,28 @@
+#![cfg(feature = "indexmap")]
+
+use indexmap::IndexMap;
+use magnus::{embed::init, rb_assert, RHash};
+
+#[test]
+fn it_converts_index_maps_preserving_order() {
+    let ruby = unsafe { init() };
+
+    let map: IndexMap<String, i64> = ruby.eval(r#"{"z" => 1, "a" => 2, "m" => 3}"#).unwrap();
+    assert_eq!(map.keys().collect::<Vec<_>>(), ["z", "a", "m"]);
+
+    let hash: RHash = ruby.eval(r#"{z: 1, a: 2, m: 3}"#).unwrap();
+    assert_eq!(
+        hash.to_vec::<String, i64>().unwrap(),
+        [
+            (String::from("z"), 1),
+            (String::from("a"), 2),
+            (String::from("m"), 3)
+        ]
+    );
+
+    let mut map = IndexMap::new();
+    map.insert("z", 1);
+    map.insert("a", 2);
+    map.insert("m", 3);
+    rb_assert!(ruby, r#"map.keys == ["z", "a", "m"]"#, map);
+}
@@ -0,0 +1,28 @@
+use magnus::{embed::init, Integer};
+
+#[test]
+fn it_converts_128_bit_integers() {
+    let ruby = unsafe { init() };
+
+    assert_eq!(ruby.eval::<i128>("42").unwrap(), 42);
+    assert_eq!(ruby.eval::<i128>("-42").unwrap(), -42);
+    assert_eq!(ruby.eval::<i128>("2 ** 127 - 1").unwrap(), i128::MAX);
+    assert_eq!(ruby.eval::<i128>("-(2 ** 127)").unwrap(), i128::MIN);
+    assert_eq!(ruby.eval::<i128>("-(2 ** 70) - 3").unwrap(), -(1 << 70) - 3);
+    assert!(ruby.eval::<i128>("2 ** 127").is_err());
+    assert!(ruby.eval::<i128>("-(2 ** 127) - 1").is_err());
+
+    assert_eq!(ruby.eval::<u128>("42").unwrap(), 42);
+    assert_eq!(ruby.eval::<u128>("2 ** 128 - 1").unwrap(), u128::MAX);
+    assert_eq!(ruby.eval::<u128>("2 ** 100 + 7").unwrap(), (1 << 100) + 7);
+    assert!(ruby.eval::<u128>("2 ** 128").is_err());
+    assert!(ruby.eval::<u128>("-1").is_err());
+    assert!(ruby.eval::<u128>("-(2 ** 100)").is_err());
+
+    let err = ruby
+        .eval::<Integer>("2 ** 128")
+        .unwrap()
+        .to_u128()
+        .unwrap_err();
+    assert!(err.is_kind_of(ruby.exception_range_error()));
+}
@@ -13,6 +13,7 @@ fn test_all() {
         test_mul(ruby)?;
         test_div(ruby)?;
         test_ord(ruby)?;
+        test_bit_ops(ruby)?;
         Ok(())
     })
     .unwrap();
@@ -221,3 +222,45 @@ fn test_ord(ruby: &Ruby) -> Result<(), Error> {
 
     Ok(())
 }
+
+fn test_bit_ops(ruby: &Ruby) -> Result<(), Error> {
+    let a = ruby.integer_from_i64(0b1100);
+    let b = ruby.integer_from_i64(0b1010);
+    assert_eq!(a & b, ruby.integer_from_i64(0b1000));
+    assert_eq!(a | b, ruby.integer_from_i64(0b1110));
+    assert_eq!(a ^ b, ruby.integer_from_i64(0b0110));
+    assert_eq!(
+        ruby.integer_from_i64(-1) & ruby.integer_from_i64(0xff),
+        ruby.integer_from_i64(0xff)
+    );
+    assert_eq!(
+        ruby.integer_from_i64(-1) ^ ruby.integer_from_i64(0xff),
+        ruby.integer_from_i64(-256)
+    );
+
+    let big: Integer = ruby.eval("2**100 + 5")?;
+    assert_eq!(big & ruby.integer_from_i64(0xf), ruby.integer_from_i64(5));
+    let expected: Integer = ruby.eval("2**100 + 7")?;
+    assert_eq!(big | ruby.integer_from_i64(2), expected);
+    assert_eq!(ruby.integer_from_i64(3) | big, expected);
+
+    assert_eq!(ruby.integer_from_i64(1) << 3, ruby.integer_from_i64(8));
+    let expected: Integer = ruby.eval("2**100")?;
+    assert_eq!(ruby.integer_from_i64(1) << 100, expected);
+    assert_eq!(expected >> 98, ruby.integer_from_i64(4));
+    assert_eq!(ruby.integer_from_i64(-5) >> 1, ruby.integer_from_i64(-3));
+    assert_eq!(ruby.integer_from_i64(-5) >> 100, ruby.integer_from_i64(-1));
+    let expected: Integer = ruby.eval("-(2**70)")?;
+    assert_eq!(ruby.integer_from_i64(-1) << 70, expected);
+
+    let big: Integer = ruby.eval("-(2**64)")?;
+    assert_eq!(big.bit_length(), 64);
+    let big: Integer = ruby.eval("-(2**64) - 1")?;
+    assert_eq!(big.bit_length(), 65);
+    assert_eq!(
+        big.to_le_bytes(),
+        vec![255, 255, 255, 255, 255, 255, 255, 255, 254]
+    );
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use magnus::{rb_assert, IvarView, RObject, RString};
+
+#[derive(IvarView)]
+#[magnus(write)]
+struct Point {
+    x: i64,
+    y: i64,
+    #[magnus(ivar = "@label")]
+    name: Option<RString>,
+    #[magnus(default)]
+    tags: Vec<String>,
+}
+
+#[test]
+fn it_reads_and_writes_ivars() {
+    let ruby = unsafe { magnus::embed::init() };
+
+    let obj: RObject = ruby
+        .eval("Object.new.tap { |o| o.instance_variable_set(:@x, 1); o.instance_variable_set(:@y, 2) }")
+        .unwrap();
+
+    let point = Point::from_obj(obj).unwrap();
+    assert_eq!(point.x, 1);
+    assert_eq!(point.y, 2);
+    assert!(point.name.is_none());
+    assert!(point.tags.is_empty());
+
+    Point {
+        x: 3,
+        y: 4,
+        name: Some(ruby.str_new("origin")),
+        tags: vec!["a".to_owned()],
+    }
+    .write_to(obj)
+    .unwrap();
+    rb_assert!(
+        ruby,
+        r#"obj.instance_variables == [:@x, :@y, :@label, :@tags] && obj.instance_variable_get(:@label) == "origin""#,
+        obj
+    );
+
+    let err = Point::from_obj(ruby.eval::<RObject>("Object.new").unwrap());
+    assert!(err.is_err());
+}
@@ -0,0 +1,80 @@
+use magnus::{rb_assert, Error};
+use serde_json::json;
+
+#[test]
+fn it_converts_json_values() -> Result<(), Error> {
+    let ruby = unsafe { magnus::embed::init() };
+
+    let val = json!({
+        "null": null,
+        "bool": true,
+        "int": -42,
+        "float": 1.5,
+        "big": u64::MAX,
+        "string": "foo",
+        "array": [1, [2, {"a": "b"}]],
+    });
+    rb_assert!(
+        ruby,
+        r#"val == {
+            "null" => nil,
+            "bool" => true,
+            "int" => -42,
+            "float" => 1.5,
+            "big" => 18446744073709551615,
+            "string" => "foo",
+            "array" => [1, [2, {"a" => "b"}]],
+        }"#,
+        val = val.clone()
+    );
+    rb_assert!(ruby, "val.is_a?(Integer) && val == 3", val = json!(3));
+    rb_assert!(ruby, "val.is_a?(Float) && val == 3.0", val = json!(3.0));
+
+    let res: serde_json::Value = ruby.eval(
+        r#"{
+            "null" => nil,
+            "bool" => true,
+            "int" => -42,
+            "float" => 1.5,
+            "big" => 18446744073709551615,
+            string: :foo,
+            "array" => [1, [2, {a: "b"}]],
+        }"#,
+    )?;
+    assert_eq!(res, val);
+
+    assert!(ruby.eval::<serde_json::Value>("2 ** 64").is_err());
+    assert!(ruby.eval::<serde_json::Value>("Float::NAN").is_err());
+    assert!(ruby.eval::<serde_json::Value>("{1 => 2}").is_err());
+    assert!(ruby.eval::<serde_json::Value>("Object.new").is_err());
+    let err = ruby
+        .eval::<serde_json::Value>("a = []; a << a; a")
+        .unwrap_err();
+    assert!(err.to_string().contains("too deep"));
+
+    let res = ruby.json_parse(
+        br#"{"null": null, "bool": false, "int": -1, "big": 18446744073709551615,
+             "float": 0.5, "string": "\u00e9", "array": [[], {}], "z": 1, "a": 2}"#,
+    )?;
+    rb_assert!(
+        ruby,
+        r#"res == {
+            "null" => nil,
+            "bool" => false,
+            "int" => -1,
+            "big" => 18446744073709551615,
+            "float" => 0.5,
+            "string" => "\u00e9",
+            "array" => [[], {}],
+            "z" => 1,
+            "a" => 2,
+        } && res.keys.last(2) == ["z", "a"]"#,
+        res
+    );
+    assert!(ruby.json_parse(b"[1] 2").is_err());
+    assert!(ruby.json_parse(b"{1: 2}").is_err());
+    assert!(ruby.json_parse_with_max_nesting(b"[[]]", 1).is_err());
+    assert!(ruby.json_parse(&[b'['; 200]).is_err());
+
+    Ok(())
+}
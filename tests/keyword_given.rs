@@ -0,0 +1,34 @@
+use magnus::{
+    embed::init, function, rb_assert, scan_args::scan_args, Error, RArray, RHash, Ruby, Value,
+};
+
+fn kw_given(ruby: &Ruby, _args: &[Value]) -> bool {
+    ruby.keyword_given()
+}
+
+fn scanned(ruby: &Ruby, args: &[Value]) -> Result<RArray, Error> {
+    let args = scan_args::<(), (Option<RHash>,), (), (), Option<RHash>, ()>(args)?;
+    let (positional,) = args.optional;
+    Ok(ruby.ary_new_from_values(&[ruby.into_value(positional), ruby.into_value(args.keywords)]))
+}
+
+#[test]
+fn it_separates_keywords_from_positional_hashes() {
+    let ruby = unsafe { init() };
+
+    ruby.define_global_function("kw_given", function!(kw_given, -1));
+    ruby.define_global_function("scanned", function!(scanned, -1));
+
+    rb_assert!(ruby, "kw_given(a: 1) == true");
+    rb_assert!(ruby, "kw_given({a: 1}) == false");
+    rb_assert!(ruby, "kw_given(1) == false");
+    rb_assert!(ruby, "kw_given == false");
+    rb_assert!(ruby, "kw_given(**{a: 1}) == true");
+
+    rb_assert!(ruby, "scanned(a: 1) == [nil, {a: 1}]");
+    rb_assert!(ruby, "scanned({a: 1}, b: 2) == [{a: 1}, {b: 2}]");
+    // Ruby 2.7 treats a trailing positional Hash as keywords, with a warning
+    if ruby.version() >= (3, 0, 0) {
+        rb_assert!(ruby, "scanned({a: 1}) == [{a: 1}, nil]");
+    }
+}
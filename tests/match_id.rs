@@ -0,0 +1,19 @@
+use magnus::{match_id, Symbol};
+
+#[test]
+fn it_matches_ids() {
+    let ruby = unsafe { magnus::embed::init() };
+
+    let mode = |sym: Symbol| {
+        match_id!(sym, {
+            "read" => "r",
+            "write" => "w",
+            _ => "?",
+        })
+    };
+
+    assert_eq!(mode(ruby.eval(":read").unwrap()), "r");
+    assert_eq!(mode(ruby.eval(r#""write".to_sym"#).unwrap()), "w");
+    assert_eq!(mode(ruby.to_symbol("other")), "?");
+    assert_eq!(ruby.to_symbol("read").to_id(), ruby.intern("read"));
+}
@@ -0,0 +1,49 @@
+use magnus::{prelude::*, rb_assert, Error, Value};
+
+#[test]
+fn it_packs_and_unpacks_msgpack() -> Result<(), Error> {
+    let ruby = unsafe { magnus::embed::init() };
+
+    let val: Value = ruby.eval(
+        r#"{
+            "nil" => nil,
+            "bool" => [true, false],
+            "int" => [0, 127, 128, -1, -33, 65536, -2 ** 63, 2 ** 64 - 1],
+            "float" => [0.5, -1.0e300],
+            "string" => ["", "é" * 40, "x" * 70_000],
+            "binary" => "\xFF\x00".b,
+            sym: :value,
+            "nested" => [[{}], {[1] => {a: []}}],
+        }"#,
+    )?;
+    let packed = ruby.msgpack_pack(val)?;
+    rb_assert!(ruby, "packed.encoding == Encoding::BINARY", packed);
+    let res = ruby.msgpack_unpack(unsafe { packed.as_slice() })?;
+    assert!(res.eql(val)?);
+    rb_assert!(
+        ruby,
+        r#"res["string"][1].encoding == Encoding::UTF_8 && res["binary"].encoding == Encoding::BINARY"#,
+        res
+    );
+
+    // float 32, which is never packed from Ruby
+    let res = ruby.msgpack_unpack(b"\xCA\x3F\xC0\x00\x00")?;
+    rb_assert!(ruby, "res == 1.5", res);
+
+    assert!(ruby
+        .msgpack_pack(ruby.eval::<Value>("Object.new")?)
+        .is_err());
+    assert!(ruby.msgpack_pack(ruby.eval::<Value>("2 ** 64")?).is_err());
+    assert!(ruby
+        .msgpack_pack(ruby.eval::<Value>("a = []; a << a; a")?)
+        .is_err());
+
+    assert!(ruby.msgpack_unpack(b"").is_err());
+    assert!(ruby.msgpack_unpack(b"\xC1").is_err());
+    assert!(ruby.msgpack_unpack(b"\x01\x02").is_err());
+    assert!(ruby.msgpack_unpack(b"\xD4\x01\x00").is_err());
+    assert!(ruby.msgpack_unpack(b"\xDD\xFF\xFF\xFF\xFF").is_err());
+    assert!(ruby.msgpack_unpack(&[0x91; 200]).is_err());
+
+    Ok(())
+}
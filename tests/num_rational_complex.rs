@@ -0,0 +1,28 @@
+use magnus::{rb_assert, Error, TryIntoValue};
+use num_complex::Complex;
+use num_rational::Ratio;
+
+#[test]
+fn it_converts_rationals_and_complex_numbers() -> Result<(), Error> {
+    let ruby = unsafe { magnus::embed::init() };
+
+    let r = Ratio::new(6, 4).try_into_value_with(&ruby)?;
+    rb_assert!(ruby, "r == 3/2r", r);
+    let err = Ratio::new_raw(1, 0).try_into_value_with(&ruby).unwrap_err();
+    assert!(err.is_kind_of(ruby.exception_zero_div_error()));
+    assert_eq!(ruby.eval::<Ratio<i64>>("-3/4r")?, Ratio::new(-3, 4));
+    assert_eq!(ruby.eval::<Ratio<i64>>("7")?, Ratio::from_integer(7));
+    assert!(ruby.eval::<Ratio<i64>>("1.5").is_err());
+    assert!(ruby.eval::<Ratio<i64>>("(2**64)/3r").is_err());
+
+    let c = Complex::new(1.5, -2.0);
+    rb_assert!(ruby, "c == Complex(1.5, -2.0)", c);
+    assert_eq!(
+        ruby.eval::<Complex<f64>>("Complex(3, 4)")?,
+        Complex::new(3.0, 4.0)
+    );
+    assert_eq!(ruby.eval::<Complex<f64>>("2.5")?, Complex::new(2.5, 0.0));
+    assert!(ruby.eval::<Complex<f64>>(r#""1+2i""#).is_err());
+
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use magnus::{embed::init, function, prelude::*, rb_assert, Error, Random};
+
+fn roll(rng: Random) -> Result<u64, Error> {
+    rng.rand_int(6)
+}
+
+#[test]
+fn it_shares_rng_state_with_ruby() {
+    let ruby = unsafe { init() };
+
+    ruby.define_global_function("roll", function!(roll, 1));
+
+    rb_assert!(
+        ruby,
+        "Array.new(10) { roll(Random.new(99)) } == Array.new(10) { Random.new(99).rand(6) }"
+    );
+    rb_assert!(
+        ruby,
+        r#"
+            a = Random.new(7)
+            b = Random.new(7)
+            Array.new(10) { roll(a) } == Array.new(10) { b.rand(6) }
+        "#
+    );
+    rb_assert!(ruby, "(roll(Object.new) rescue $!).is_a?(TypeError)");
+
+    let a = ruby.random_from_seed(5).unwrap();
+    let b = ruby.random_from_seed(5).unwrap();
+    assert_eq!(a.rand_float().unwrap(), b.rand_float().unwrap());
+    let (a, b) = (a.bytes(8).unwrap(), b.bytes(8).unwrap());
+    assert!(a.equal(b).unwrap());
+}
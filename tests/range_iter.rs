@@ -0,0 +1,48 @@
+use magnus::{embed::init, Range};
+
+#[test]
+fn it_iterates_ranges_natively() {
+    let ruby = unsafe { init() };
+
+    let collect = |src: &str| {
+        let range: Range = ruby.eval(src).unwrap();
+        let mut res = Vec::new();
+        range
+            .each_i64(|i| {
+                res.push(i);
+                Ok(())
+            })
+            .map(|_| res)
+    };
+
+    assert_eq!(collect("1..3").unwrap(), vec![1, 2, 3]);
+    assert_eq!(collect("1...3").unwrap(), vec![1, 2]);
+    assert_eq!(collect("3..1").unwrap(), Vec::<i64>::new());
+    assert!(collect("1..").is_err());
+    assert!(collect("1.0..3").is_err());
+
+    let range: Range = ruby.eval("0..10").unwrap();
+    let mut res = Vec::new();
+    range
+        .step_i64(5, |i| {
+            res.push(i);
+            Ok(())
+        })
+        .unwrap();
+    assert_eq!(res, vec![0, 5, 10]);
+    assert!(range.step_i64(0, |_| Ok(())).is_err());
+    assert!(range.step_i64(-1, |_| Ok(())).is_err());
+
+    let range: Range = ruby.eval(r#""x"..."z""#).unwrap();
+    let mut res = String::new();
+    range
+        .each_char(|c| {
+            res.push(c);
+            Ok(())
+        })
+        .unwrap();
+    assert_eq!(res, "xy");
+
+    let range: Range = ruby.eval(r#""aa".."zz""#).unwrap();
+    assert!(range.each_char(|_| Ok(())).is_err());
+}
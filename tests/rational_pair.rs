@@ -0,0 +1,27 @@
+use magnus::{embed::init, function, rb_assert, Error, RRational};
+
+fn beats(rational: RRational) -> Result<(i64, i64), Error> {
+    rational.to_pair()
+}
+
+fn seconds(rational: RRational) -> Result<f64, Error> {
+    rational.to_f64()
+}
+
+#[test]
+fn it_converts_rationals_to_native() {
+    let ruby = unsafe { init() };
+
+    ruby.define_global_function("beats", function!(beats, 1));
+    ruby.define_global_function("seconds", function!(seconds, 1));
+
+    rb_assert!(ruby, "beats(3/4r) == [3, 4]");
+    rb_assert!(ruby, "beats(-6/8r) == [-3, 4]");
+    rb_assert!(ruby, "(beats(2**63 / 1r) rescue $!).is_a?(RangeError)");
+    rb_assert!(ruby, "(beats(1 / 2r**63) rescue $!).is_a?(RangeError)");
+    rb_assert!(ruby, "seconds(3/8r) == 0.375");
+    rb_assert!(
+        ruby,
+        "seconds(2**1100 / 3r**600) == (2**1100 / 3r**600).to_f"
+    );
+}
@@ -0,0 +1,43 @@
+use magnus::{class::RespondToCache, embed::init, rb_assert, Error, RArray, Value};
+
+fn count_to_json(cache: &mut RespondToCache, ary: RArray) -> Result<usize, Error> {
+    let mut count = 0;
+    for val in ary {
+        if cache.respond_to(val, "to_json", false)? {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+#[test]
+fn it_caches_respond_to() {
+    let ruby = unsafe { init() };
+    let mut cache = RespondToCache::new();
+
+    let ary: RArray = ruby
+        .eval(
+            r#"
+            class Point
+              def to_json; end
+            end
+            class Other; end
+            o = Other.new
+            def o.to_json; end
+            [Point.new, Point.new, Other.new, o, 1]
+            "#,
+        )
+        .unwrap();
+    assert_eq!(count_to_json(&mut cache, ary).unwrap(), 3);
+
+    // results are cached, and stale until cleared
+    let _: Value = ruby.eval("class Other; def to_json; end; end").unwrap();
+    assert_eq!(count_to_json(&mut cache, ary).unwrap(), 3);
+    cache.clear();
+    assert_eq!(count_to_json(&mut cache, ary).unwrap(), 4);
+
+    let val: Value = ruby.eval("Point.new").unwrap();
+    assert!(cache.respond_to(val, "initialize", true).unwrap());
+    assert!(!cache.respond_to(val, "initialize", false).unwrap());
+    rb_assert!(ruby, "val.respond_to?(:to_json)", val);
+}
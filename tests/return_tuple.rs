@@ -0,0 +1,27 @@
+use magnus::{function, method, prelude::*, rb_assert, Integer, RString, Ruby, Value};
+
+fn divmod(a: Integer, b: Integer) -> (Integer, Integer) {
+    let q = a / b;
+    (q, a - q * b)
+}
+
+fn split_name(ruby: &Ruby, rb_self: Value) -> Result<(RString, Option<RString>), magnus::Error> {
+    let s: String = rb_self.funcall("to_s", ())?;
+    let mut parts = s.splitn(2, ' ');
+    let first = ruby.str_new(parts.next().unwrap_or_default());
+    Ok((first, parts.next().map(|s| ruby.str_new(s))))
+}
+
+#[test]
+fn it_converts_tuple_returns_to_arrays() {
+    let ruby = unsafe { magnus::embed::init() };
+
+    ruby.define_global_function("divmod", function!(divmod, 2));
+    ruby.class_string()
+        .define_method("split_name", method!(split_name, 0))
+        .unwrap();
+
+    rb_assert!(ruby, "divmod(7, 2) == [3, 1]");
+    rb_assert!(ruby, r#""Ada Lovelace".split_name == ["Ada", "Lovelace"]"#);
+    rb_assert!(ruby, r#""Ada".split_name == ["Ada", nil]"#);
+}
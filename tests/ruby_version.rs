@@ -0,0 +1,19 @@
+use magnus::embed::init;
+
+#[test]
+fn it_reports_the_running_ruby_version() {
+    let ruby = unsafe { init() };
+
+    let (major, minor, teeny) = ruby.version();
+    let expected: String = ruby.eval("RUBY_VERSION").unwrap();
+    assert_eq!(format!("{}.{}.{}", major, minor, teeny), expected);
+
+    assert_eq!(ruby.supports_ractor(), major >= 3);
+    assert_eq!(ruby.supports_subclasses(), (major, minor) >= (3, 1));
+    assert_eq!(ruby.supports_data(), (major, minor) >= (3, 2));
+    assert_eq!(
+        ruby.supports_data(),
+        ruby.eval::<bool>("Object.const_defined?(:Data) && Data.respond_to?(:define)")
+            .unwrap()
+    );
+}
@@ -0,0 +1,39 @@
+#![cfg(feature = "rust_decimal")]
+
+use std::str::FromStr;
+
+use magnus::{function, rb_assert, Error};
+use rust_decimal::Decimal;
+
+#[test]
+fn it_converts_decimals() -> Result<(), Error> {
+    let ruby = unsafe { magnus::embed::init() };
+
+    assert_eq!(ruby.eval::<Decimal>("42")?, Decimal::from(42));
+
+    // bigdecimal is required on demand
+    ruby.define_global_function("make_decimal", function!(|| Decimal::new(123, 2), 0));
+    rb_assert!(ruby, r#"make_decimal.to_s("F") == "1.23""#);
+
+    let d = Decimal::from_str("12345678901234567890.123456789").unwrap();
+    rb_assert!(
+        ruby,
+        r#"d == BigDecimal("12345678901234567890.123456789")"#,
+        d
+    );
+    assert_eq!(
+        ruby.eval::<Decimal>(r#"BigDecimal("12345678901234567890.123456789")"#)?,
+        d
+    );
+    assert_eq!(
+        ruby.eval::<Decimal>(r#"BigDecimal("-0.1")"#)?,
+        Decimal::new(-1, 1)
+    );
+    rb_assert!(ruby, r#"make_decimal == BigDecimal("1.23")"#);
+
+    assert!(ruby.eval::<Decimal>(r#"BigDecimal("NaN")"#).is_err());
+    assert!(ruby.eval::<Decimal>(r#"BigDecimal("1e100")"#).is_err());
+    assert!(ruby.eval::<Decimal>("1.5").is_err());
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use std::collections::{BTreeSet, HashSet};
+
+use magnus::{function, rb_assert, Ruby};
+
+fn unique(words: Vec<String>) -> HashSet<String> {
+    words.into_iter().collect()
+}
+
+fn count(set: HashSet<i64>) -> usize {
+    set.len()
+}
+
+fn sorted(set: BTreeSet<i64>) -> BTreeSet<i64> {
+    set
+}
+
+#[test]
+fn it_converts_sets() {
+    let ruby = unsafe { magnus::embed::init() };
+
+    ruby.define_global_function("unique", function!(unique, 1));
+    ruby.define_global_function("count", function!(count, 1));
+    ruby.define_global_function("sorted", function!(sorted, 1));
+
+    rb_assert!(ruby, r#"unique(["a", "b", "a"]) == Set["a", "b"]"#);
+    rb_assert!(ruby, "count(Set[1, 2, 3]) == 3");
+
+    rb_assert!(ruby, "count([1, 2, 2, 1]) == 2");
+    rb_assert!(ruby, "sorted([3, 1, 2, 1]).to_a == [1, 2, 3]");
+    rb_assert!(ruby, "sorted(Set[2, 1]).to_a == [1, 2]");
+
+    let res = ruby.eval::<bool>(r#"begin; count("a"); rescue TypeError; true; end"#);
+    assert!(res.unwrap());
+
+    let set = ruby.set_from_iter(["c", "a", "b"]).unwrap();
+    rb_assert!(ruby, r#"set.to_a == ["c", "a", "b"]"#, set);
+}
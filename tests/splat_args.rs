@@ -0,0 +1,33 @@
+use magnus::{embed::init, function, method, prelude::*, rb_assert, RString};
+
+fn sum(nums: Vec<i64>) -> i64 {
+    nums.iter().sum()
+}
+
+fn join(rb_self: RString, parts: Vec<String>) -> String {
+    parts.join(&rb_self.to_string().unwrap())
+}
+
+#[test]
+fn it_converts_splat_args() {
+    let ruby = unsafe { init() };
+
+    ruby.define_global_function("sum", function!(sum, -2));
+    ruby.class_string()
+        .define_method("join_all", method!(join, -2))
+        .unwrap();
+
+    rb_assert!(ruby, "sum == 0");
+    rb_assert!(ruby, "sum(1, 2, 3) == 6");
+    rb_assert!(ruby, r#"(sum(1, 2, "3") rescue $!).is_a?(TypeError)"#);
+    rb_assert!(
+        ruby,
+        r#"(sum(1, 2, "3") rescue $!.message) == "at [2]: no implicit conversion of String into Integer""#
+    );
+
+    rb_assert!(ruby, r#"", ".join_all("a", "b") == "a, b""#);
+    rb_assert!(
+        ruby,
+        r#"(", ".join_all("a", 1) rescue $!.message) == "at [1]: no implicit conversion of Integer into String""#
+    );
+}
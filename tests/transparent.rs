@@ -0,0 +1,35 @@
+use magnus::{
+    embed::init, function, prelude::*, rb_assert, Error, RString, Ruby, Transparent, Value,
+};
+
+#[derive(Clone, Copy, Transparent)]
+#[repr(transparent)]
+struct Name(RString);
+
+#[derive(Clone, Copy, Transparent)]
+#[repr(transparent)]
+struct Anything(Value);
+
+fn greet(name: Name) -> Result<Name, Error> {
+    Ok(Name(name.0.funcall("+", ("!",))?))
+}
+
+fn identity(val: Anything) -> Anything {
+    val
+}
+
+#[test]
+fn it_derives_transparent_newtypes() {
+    let ruby = unsafe { init() };
+
+    ruby.define_global_function("greet", function!(greet, 1));
+    ruby.define_global_function("identity", function!(identity, 1));
+
+    rb_assert!(ruby, r#"greet("hello") == "hello!""#);
+    rb_assert!(ruby, r#"(greet(1) rescue $!).is_a?(TypeError)"#);
+    rb_assert!(ruby, "identity(:foo) == :foo");
+
+    let name: Name = ruby.eval(r#""example""#).unwrap();
+    assert!(name.is_kind_of(ruby.class_string()));
+    assert!(name.as_value().equal(name.0).unwrap());
+}
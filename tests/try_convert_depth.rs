@@ -0,0 +1,50 @@
+use magnus::{
+    embed::init,
+    try_convert::{self, TryConvertOwned},
+    Error, RArray, TryConvert, Value,
+};
+
+enum Tree {
+    Leaf(i64),
+    Node(Vec<Tree>),
+}
+
+impl TryConvert for Tree {
+    fn try_convert(val: Value) -> Result<Self, Error> {
+        match RArray::from_value(val) {
+            Some(_) => Ok(Tree::Node(TryConvert::try_convert(val)?)),
+            None => Ok(Tree::Leaf(TryConvert::try_convert(val)?)),
+        }
+    }
+}
+
+unsafe impl TryConvertOwned for Tree {}
+
+fn sum(tree: &Tree) -> i64 {
+    match tree {
+        Tree::Leaf(i) => *i,
+        Tree::Node(children) => children.iter().map(sum).sum(),
+    }
+}
+
+#[test]
+fn it_limits_nesting_depth() {
+    let ruby = unsafe { init() };
+
+    let val: Value = ruby.eval("[1, [2, [3, [4]]]]").unwrap();
+    assert_eq!(sum(&Tree::try_convert(val).unwrap()), 10);
+
+    let val: Value = ruby.eval("a = [1]; 100_000.times { a = [a] }; a").unwrap();
+    let err = Tree::try_convert(val).err().unwrap();
+    assert!(err.is_kind_of(ruby.exception_arg_error()));
+    assert_eq!(err.to_string(), "ArgumentError: nesting of 129 is too deep");
+
+    let val: Value = ruby.eval("a = [1]; 200.times { a = [a] }; a").unwrap();
+    assert!(Tree::try_convert(val).is_err());
+    let tree = try_convert::with_max_depth::<Tree>(val, 250).unwrap();
+    assert_eq!(sum(&tree), 1);
+
+    // depth is reset after an error
+    let val: Value = ruby.eval("[[1], [2]]").unwrap();
+    assert_eq!(sum(&Tree::try_convert(val).unwrap()), 3);
+}
@@ -0,0 +1,31 @@
+use std::collections::HashMap;
+
+use magnus::{try_convert, TryConvert, Value};
+
+#[test]
+fn it_annotates_conversion_errors_with_path() {
+    let ruby = unsafe { magnus::embed::init() };
+
+    let val: Value = ruby.eval(r#"[[1, ["a", 2.0]], [2, ["b", "c"]]]"#).unwrap();
+
+    let err = try_convert::with_path::<Vec<(i64, (String, f64))>>(val).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "TypeError: at [1][1][1]: no implicit conversion to float from string"
+    );
+
+    // without with_path the message is unchanged
+    let err = Vec::<(i64, (String, f64))>::try_convert(val).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "no implicit conversion to float from string"
+    );
+
+    let val: Value = ruby.eval(r#"{"x" => {"y" => [1, nil]}}"#).unwrap();
+    let err =
+        try_convert::with_path::<HashMap<String, HashMap<String, Vec<i64>>>>(val).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        r#"TypeError: at ["x"]["y"][1]: no implicit conversion from nil to integer"#
+    );
+}
@@ -0,0 +1,40 @@
+use std::cell::{Cell, RefCell};
+
+use magnus::{embed::init, eval, rb_assert, Value};
+
+#[magnus::wrap(class = "Person", free_immediately)]
+struct Person {
+    #[magnus(attr_reader)]
+    id: u64,
+    #[magnus(attr_accessor)]
+    name: RefCell<String>,
+    #[magnus(attr_accessor)]
+    age: Cell<Option<u8>>,
+}
+
+#[test]
+fn it_generates_attr_methods() {
+    let ruby = unsafe { init() };
+
+    ruby.define_class("Person", ruby.class_object()).unwrap();
+
+    let person = Person {
+        id: 1,
+        name: RefCell::new(String::from("Alice")),
+        age: Cell::new(None),
+    };
+    let obj = ruby.obj_wrap(person);
+    rb_assert!(ruby, "obj.id == 1", obj);
+    rb_assert!(ruby, r#"obj.name == "Alice""#, obj);
+    rb_assert!(ruby, "obj.age.nil?", obj);
+    rb_assert!(ruby, "!obj.respond_to?(:id=)", obj);
+
+    let _: Value = eval!(ruby, r#"obj.name = "Bob"; obj.age = 42"#, obj).unwrap();
+    rb_assert!(ruby, r#"obj.name == "Bob""#, obj);
+    rb_assert!(ruby, "obj.age == 42", obj);
+
+    let err = eval!(ruby, r#"obj.age = "old""#, obj)
+        .map(|_: Value| ())
+        .unwrap_err();
+    assert!(err.is_kind_of(ruby.exception_type_error()));
+}
@@ -0,0 +1,30 @@
+use magnus::{embed::init, rb_assert};
+
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+#[magnus::wrap(class = "Version", free_immediately, cmp)]
+struct Version(u32, u32);
+
+#[test]
+fn it_generates_comparison_methods() {
+    let ruby = unsafe { init() };
+
+    ruby.define_class("Version", ruby.class_object()).unwrap();
+
+    let a = Version(1, 2);
+    let b = Version(1, 10);
+    rb_assert!(ruby, "(a <=> b) == -1", a, b);
+
+    let a = Version(1, 2);
+    let b = Version(1, 10);
+    rb_assert!(ruby, "a < b && b > a && a != b", a, b);
+
+    let a = Version(1, 2);
+    let b = Version(1, 2);
+    rb_assert!(ruby, "a == b && a.between?(b, b)", a, b);
+
+    let a = Version(1, 2);
+    rb_assert!(ruby, "(a <=> 1).nil? && a != 1", a);
+
+    let a = Version(1, 2);
+    rb_assert!(ruby, "Version.include?(Comparable)", a);
+}
@@ -0,0 +1,57 @@
+use std::cell::RefCell;
+
+use magnus::{embed::init, rb_assert};
+
+#[magnus::wrap(class = "Point", free_immediately, deconstruct)]
+struct Point {
+    x: i64,
+    y: i64,
+    label: RefCell<String>,
+}
+
+#[magnus::wrap(class = "Pair", free_immediately, deconstruct)]
+struct Pair(i64, String);
+
+#[test]
+fn it_generates_deconstruct_methods() {
+    let ruby = unsafe { init() };
+
+    ruby.define_class("Point", ruby.class_object()).unwrap();
+    ruby.define_class("Pair", ruby.class_object()).unwrap();
+
+    let point = || Point {
+        x: 1,
+        y: 2,
+        label: RefCell::new(String::from("origin")),
+    };
+
+    let p = point();
+    rb_assert!(ruby, r#"p.deconstruct == [1, 2, "origin"]"#, p);
+
+    let p = point();
+    rb_assert!(
+        ruby,
+        r#"p.deconstruct_keys(nil) == {x: 1, y: 2, label: "origin"}"#,
+        p
+    );
+
+    let p = point();
+    rb_assert!(ruby, "p.deconstruct_keys([:y, :z]) == {y: 2}", p);
+
+    let p = point();
+    rb_assert!(
+        ruby,
+        r#"case p; in {x: 1, label: String => l} then l == "origin"; end"#,
+        p
+    );
+
+    let p = Pair(1, String::from("one"));
+    rb_assert!(
+        ruby,
+        r#"case p; in [Integer => i, "one"] then i == 1; end"#,
+        p
+    );
+
+    let p = Pair(1, String::from("one"));
+    rb_assert!(ruby, "!p.respond_to?(:deconstruct_keys)", p);
+}
@@ -0,0 +1,32 @@
+use magnus::{embed::init, rb_assert};
+
+#[derive(Hash, PartialEq, Eq)]
+#[magnus::wrap(class = "Key", free_immediately, hash)]
+struct Key(String);
+
+#[test]
+fn it_generates_hash_methods() {
+    let ruby = unsafe { init() };
+
+    ruby.define_class("Key", ruby.class_object()).unwrap();
+
+    let hash = ruby.hash_new();
+    hash.aset(Key("foo".to_owned()), 1).unwrap();
+    hash.aset(Key("bar".to_owned()), 2).unwrap();
+
+    assert_eq!(1, hash.fetch::<_, i64>(Key("foo".to_owned())).unwrap());
+    assert_eq!(2, hash.fetch::<_, i64>(Key("bar".to_owned())).unwrap());
+    assert!(hash.get(Key("baz".to_owned())).is_none());
+
+    let a = Key("foo".to_owned());
+    let b = Key("foo".to_owned());
+    rb_assert!(
+        ruby,
+        "a.hash.is_a?(Integer) && a.hash == b.hash && a.eql?(b)",
+        a,
+        b
+    );
+
+    let a = Key("foo".to_owned());
+    rb_assert!(ruby, r#"!a.eql?("foo")"#, a);
+}
@@ -0,0 +1,73 @@
+use std::fmt;
+
+use magnus::{embed::init, rb_assert, Value};
+
+#[derive(Debug)]
+#[magnus::wrap(class = "Point", free_immediately, inspect, to_s)]
+struct Point {
+    x: isize,
+    y: isize,
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+#[magnus::wrap(class = "Long", free_immediately, inspect)]
+struct Long;
+
+impl fmt::Debug for Long {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", "a".repeat(10_000))
+    }
+}
+
+#[magnus::wrap(class = "Broken", free_immediately, inspect)]
+struct Broken;
+
+impl fmt::Debug for Broken {
+    fn fmt(&self, _: &mut fmt::Formatter<'_>) -> fmt::Result {
+        panic!("can't inspect")
+    }
+}
+
+#[derive(Debug)]
+#[magnus::wrap(class = "Custom", free_immediately, inspect, to_s)]
+struct Custom;
+
+impl fmt::Display for Custom {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "derived")
+    }
+}
+
+#[test]
+fn it_generates_inspect_and_to_s() {
+    let ruby = unsafe { init() };
+
+    ruby.define_class("Point", ruby.class_object()).unwrap();
+    ruby.define_class("Long", ruby.class_object()).unwrap();
+    ruby.define_class("Broken", ruby.class_object()).unwrap();
+    ruby.eval::<Value>(r#"class Custom; def to_s; "custom"; end; end"#)
+        .unwrap();
+
+    let point = Point { x: 1, y: 2 };
+    rb_assert!(ruby, r#"point.inspect == "Point { x: 1, y: 2 }""#, point);
+    let point = Point { x: 1, y: 2 };
+    rb_assert!(ruby, r#"point.to_s == "(1, 2)""#, point);
+
+    let long = Long;
+    rb_assert!(ruby, r#"long.inspect.length == 4099"#, long);
+    let long = Long;
+    rb_assert!(ruby, r#"long.inspect.end_with?("...")"#, long);
+
+    let broken = Broken;
+    rb_assert!(ruby, r#"broken.inspect.start_with?("#<Broken:")"#, broken);
+
+    let custom = Custom;
+    rb_assert!(ruby, r#"custom.to_s == "custom""#, custom);
+    let custom = Custom;
+    rb_assert!(ruby, r#"custom.inspect == "Custom""#, custom);
+}
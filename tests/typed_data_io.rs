@@ -0,0 +1,82 @@
+use std::cell::RefCell;
+
+use magnus::{embed::init, rb_assert, typed_data, Error};
+
+#[derive(Default)]
+#[magnus::wrap(class = "Buffer", free_immediately, io)]
+struct Buffer {
+    data: RefCell<Vec<u8>>,
+    pos: RefCell<usize>,
+}
+
+impl typed_data::Io for Buffer {
+    fn read_bytes(&self, len: Option<usize>) -> Result<Vec<u8>, Error> {
+        let data = self.data.borrow();
+        let mut pos = self.pos.borrow_mut();
+        let end = len.map_or(data.len(), |len| data.len().min(*pos + len));
+        let bytes = data[*pos..end].to_vec();
+        *pos = end;
+        Ok(bytes)
+    }
+
+    fn write_bytes(&self, buf: &[u8]) -> Result<usize, Error> {
+        self.data.borrow_mut().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn is_eof(&self) -> Result<bool, Error> {
+        Ok(*self.pos.borrow() >= self.data.borrow().len())
+    }
+}
+
+fn buffer(s: &str) -> Buffer {
+    Buffer {
+        data: RefCell::new(s.as_bytes().to_vec()),
+        pos: RefCell::new(0),
+    }
+}
+
+#[test]
+fn it_implements_io_methods() {
+    let ruby = unsafe { init() };
+
+    ruby.define_class("Buffer", ruby.class_object()).unwrap();
+
+    let b = buffer("hello");
+    rb_assert!(ruby, r#"b.read == "hello" && b.read == "" && b.eof?"#, b);
+
+    let b = buffer("hello");
+    rb_assert!(
+        ruby,
+        r#"b.read(2) == "he" && b.read(10) == "llo" && b.read(1).nil? && b.read(0) == """#,
+        b
+    );
+
+    let b = buffer("hello");
+    rb_assert!(
+        ruby,
+        r#"out = +"x"; b.read(3, out).equal?(out) && out == "hel" && b.read(3, out) && b.read(3, out).nil? && out == """#,
+        b
+    );
+
+    let b = Buffer::default();
+    rb_assert!(ruby, r#"b.write("a", :b, 1) == 3 && b.read == "ab1""#, b);
+
+    let b = buffer("a\nb\nc");
+    rb_assert!(ruby, r#"b.each_line.to_a == ["a\n", "b\n", "c"]"#, b);
+
+    let b = buffer("a, b, c");
+    rb_assert!(ruby, r#"b.each_line(", ").to_a == ["a, ", "b, ", "c"]"#, b);
+
+    let b = buffer("a\nb");
+    rb_assert!(ruby, r#"b.each_line(nil).to_a == ["a\nb"]"#, b);
+
+    let src = buffer("copied");
+    let dst = Buffer::default();
+    rb_assert!(
+        ruby,
+        r#"IO.copy_stream(src, dst) == 6 && dst.read == "copied""#,
+        src,
+        dst
+    );
+}
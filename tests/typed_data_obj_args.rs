@@ -0,0 +1,50 @@
+use magnus::{embed::init, function, method, prelude::*, rb_assert, typed_data::Obj, Error, Ruby};
+
+#[magnus::wrap(class = "Counter", free_immediately)]
+struct Counter {
+    count: std::cell::Cell<i64>,
+}
+
+// Obj<T> can be used as both the receiver and arguments in methods
+fn merge(ruby: &Ruby, rb_self: Obj<Counter>, other: Obj<Counter>) -> Obj<Counter> {
+    ruby.obj_wrap(Counter {
+        count: (rb_self.count.get() + other.count.get()).into(),
+    })
+}
+
+fn incr(rb_self: Obj<Counter>) -> Obj<Counter> {
+    rb_self.count.set(rb_self.count.get() + 1);
+    rb_self
+}
+
+fn count(rb_self: &Counter) -> i64 {
+    rb_self.count.get()
+}
+
+fn new(count: i64) -> Counter {
+    Counter {
+        count: count.into(),
+    }
+}
+
+#[test]
+fn it_accepts_obj_as_method_args() -> Result<(), Error> {
+    let ruby = unsafe { init() };
+
+    let class = ruby.define_class("Counter", ruby.class_object())?;
+    class.define_singleton_method("new", function!(new, 1))?;
+    class.define_method("merge", method!(merge, 1))?;
+    class.define_method("incr", method!(incr, 0))?;
+    class.define_method("count", method!(count, 0))?;
+
+    rb_assert!(ruby, "c = Counter.new(1); c.incr.equal?(c)");
+    rb_assert!(ruby, "Counter.new(1).merge(Counter.new(2)).count == 3");
+    rb_assert!(ruby, "(Counter.new(1).merge(1) rescue $!).is_a?(TypeError)");
+
+    let counter = ruby.obj_wrap(new(41));
+    incr(counter);
+    assert_eq!(42, counter.count.get());
+    rb_assert!(ruby, "counter.count == 42", counter);
+
+    Ok(())
+}
@@ -0,0 +1,125 @@
+use std::ops;
+
+use magnus::{embed::init, rb_assert, typed_data};
+
+#[derive(Clone, Copy)]
+#[magnus::wrap(
+    class = "Vector",
+    free_immediately,
+    add,
+    sub,
+    mul,
+    div,
+    rem,
+    neg,
+    index = "usize",
+    coerce = "i64"
+)]
+struct Vector([i64; 2]);
+
+impl From<i64> for Vector {
+    fn from(n: i64) -> Self {
+        Self([n, n])
+    }
+}
+
+impl ops::Add for Vector {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self([self.0[0] + other.0[0], self.0[1] + other.0[1]])
+    }
+}
+
+impl ops::Sub for Vector {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self([self.0[0] - other.0[0], self.0[1] - other.0[1]])
+    }
+}
+
+impl ops::Mul for Vector {
+    type Output = i64;
+
+    fn mul(self, other: Self) -> i64 {
+        self.0[0] * other.0[0] + self.0[1] * other.0[1]
+    }
+}
+
+impl ops::Div for Vector {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        Self([self.0[0] / other.0[0], self.0[1] / other.0[1]])
+    }
+}
+
+impl ops::Rem for Vector {
+    type Output = Self;
+
+    fn rem(self, other: Self) -> Self {
+        Self([self.0[0] % other.0[0], self.0[1] % other.0[1]])
+    }
+}
+
+impl ops::Neg for Vector {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self([-self.0[0], -self.0[1]])
+    }
+}
+
+impl typed_data::Index<usize> for Vector {
+    type Output = i64;
+
+    fn get(&self, i: usize) -> Option<i64> {
+        self.0.get(i).copied()
+    }
+}
+
+#[test]
+fn it_generates_operator_methods() {
+    let ruby = unsafe { init() };
+
+    ruby.define_class("Vector", ruby.class_object()).unwrap();
+
+    let a = Vector([6, 9]);
+    let b = Vector([4, 2]);
+    rb_assert!(ruby, "c = a + b; c[0] == 10 && c[1] == 11", a, b);
+    rb_assert!(ruby, "c = a - b; c[0] == 2 && c[1] == 7", a, b);
+    rb_assert!(ruby, "a * b == 42", a, b);
+    rb_assert!(ruby, "c = a / b; c[0] == 1 && c[1] == 4", a, b);
+    rb_assert!(ruby, "c = a % b; c[0] == 2 && c[1] == 1", a, b);
+    rb_assert!(ruby, "c = -a; c[0] == -6 && c[1] == -9", a);
+    rb_assert!(ruby, "a[2].nil? && a[-1].nil?", a);
+    rb_assert!(ruby, r#"(a["0"] rescue $!).is_a?(TypeError)"#, a);
+
+    rb_assert!(ruby, "begin; a + 1; false; rescue TypeError; true; end", a);
+
+    let a = Vector([6, 9]);
+    rb_assert!(ruby, "c = 1 + a; c[0] == 7 && c[1] == 10", a);
+    rb_assert!(ruby, "c = 1 - a; c[0] == -5 && c[1] == -8", a);
+
+    ruby.eval::<magnus::Value>(
+        r#"
+        class Wrapper
+          def initialize(vector)
+            @vector = vector
+          end
+
+          def coerce(other)
+            [other, @vector]
+          end
+        end
+        "#,
+    )
+    .unwrap();
+    rb_assert!(
+        ruby,
+        "c = a + Wrapper.new(b); c[0] == 10 && c[1] == 11",
+        a,
+        b
+    );
+}
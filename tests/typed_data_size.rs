@@ -0,0 +1,28 @@
+use std::mem::size_of_val;
+
+use magnus::{embed::init, rb_assert, DataTypeFunctions, TypedData};
+
+#[derive(TypedData)]
+#[magnus(class = "Buffer", size, free_immediately)]
+struct Buffer {
+    data: Vec<u8>,
+}
+
+impl DataTypeFunctions for Buffer {
+    fn size(&self) -> usize {
+        size_of_val(self) + self.data.capacity()
+    }
+}
+
+#[test]
+fn it_reports_size() {
+    let ruby = unsafe { init() };
+
+    ruby.define_class("Buffer", ruby.class_object()).unwrap();
+    ruby.require("objspace").unwrap();
+
+    let buf = Buffer {
+        data: Vec::with_capacity(1024 * 1024),
+    };
+    rb_assert!(ruby, "ObjectSpace.memsize_of(buf) >= 1024 * 1024", buf);
+}
@@ -0,0 +1,32 @@
+use magnus::{embed::init, function, method, prelude::*, rb_assert, Error, RArray, Ruby};
+
+fn no_return(ary: RArray) {
+    ary.push(1).unwrap();
+}
+
+fn ok_unit(ary: RArray) -> Result<(), Error> {
+    ary.push(2)
+}
+
+fn err_unit(ruby: &Ruby) -> Result<(), Error> {
+    Err(Error::new(ruby.exception_runtime_error(), "failed"))
+}
+
+fn method_unit(_rb_self: RArray) {}
+
+#[test]
+fn it_returns_nil_for_unit() {
+    let ruby = unsafe { init() };
+
+    ruby.define_global_function("no_return", function!(no_return, 1));
+    ruby.define_global_function("ok_unit", function!(ok_unit, 1));
+    ruby.define_global_function("err_unit", function!(err_unit, 0));
+    ruby.class_array()
+        .define_method("method_unit", method!(method_unit, 0))
+        .unwrap();
+
+    rb_assert!(ruby, "a = []; no_return(a).nil? && a == [1]");
+    rb_assert!(ruby, "a = []; ok_unit(a).nil? && a == [2]");
+    rb_assert!(ruby, "(err_unit rescue $!).message == 'failed'");
+    rb_assert!(ruby, "[].method_unit.nil?");
+}